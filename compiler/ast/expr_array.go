@@ -0,0 +1,28 @@
+package ast
+
+import (
+	"compiler/token"
+	"strings"
+)
+
+// ArrayLiteral builds an array from its elements
+// Example: [1, 2, 3], []
+type ArrayLiteral struct {
+	Token    token.Token // the '[' token
+	Elements []Expression
+}
+
+func (array *ArrayLiteral) expressionNode() {}
+
+func (array *ArrayLiteral) TokenLiteral() string {
+	return array.Token.Value
+}
+
+func (array *ArrayLiteral) String() string {
+	elements := make([]string, len(array.Elements))
+	for i, element := range array.Elements {
+		elements[i] = element.String()
+	}
+
+	return "[" + strings.Join(elements, ", ") + "]"
+}
@@ -0,0 +1,22 @@
+package ast
+
+import "compiler/token"
+
+// AscriptionExpression states the type an expression is expected to have
+// It produces no code of its own beyond the inner expression
+// Example: total as int
+type AscriptionExpression struct {
+	Token      token.Token // the 'as' token
+	Expression Expression
+	Type       *TypeAnnotation
+}
+
+func (ascription *AscriptionExpression) expressionNode() {}
+
+func (ascription *AscriptionExpression) TokenLiteral() string {
+	return ascription.Token.Value
+}
+
+func (ascription *AscriptionExpression) String() string {
+	return "(" + ascription.Expression.String() + " as " + ascription.Type.Value + ")"
+}
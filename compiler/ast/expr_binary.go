@@ -10,6 +10,7 @@ type BinaryExpression struct {
 	Left     Expression
 	Operator string
 	Right    Expression
+	Grouped  bool // written inside parentheses in the source
 }
 
 func (binary *BinaryExpression) expressionNode() {}
@@ -0,0 +1,19 @@
+package ast
+
+import "compiler/token"
+
+// Example: true, false
+type BooleanLiteral struct {
+	Token token.Token
+	Value bool
+}
+
+func (boolean *BooleanLiteral) expressionNode() {}
+
+func (boolean *BooleanLiteral) TokenLiteral() string {
+	return boolean.Token.Value
+}
+
+func (boolean *BooleanLiteral) String() string {
+	return boolean.Token.Value
+}
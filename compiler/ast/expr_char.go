@@ -0,0 +1,22 @@
+package ast
+
+import (
+	"compiler/token"
+	"strconv"
+)
+
+// Example: 'a', '\n'
+type CharLiteral struct {
+	Token token.Token
+	Value rune
+}
+
+func (char *CharLiteral) expressionNode() {}
+
+func (char *CharLiteral) TokenLiteral() string {
+	return char.Token.Value
+}
+
+func (char *CharLiteral) String() string {
+	return strconv.QuoteRune(char.Value)
+}
@@ -0,0 +1,21 @@
+package ast
+
+import "compiler/token"
+
+// IndexExpression reads one element of a value
+// Example: name[0], name[i + 1]
+type IndexExpression struct {
+	Token token.Token // the '[' token
+	Left  Expression
+	Index Expression
+}
+
+func (index *IndexExpression) expressionNode() {}
+
+func (index *IndexExpression) TokenLiteral() string {
+	return index.Token.Value
+}
+
+func (index *IndexExpression) String() string {
+	return index.Left.String() + "[" + index.Index.String() + "]"
+}
@@ -3,6 +3,7 @@ package ast
 import "bytes"
 
 type Program struct {
+	Globals    []*LetStatement // top-level let statements, visible in every function
 	Statements []Statement
 }
 
@@ -15,6 +16,9 @@ func (p *Program) TokenLiteral() string {
 
 func (p *Program) String() string {
 	var out bytes.Buffer
+	for _, global := range p.Globals {
+		out.WriteString(global.String())
+	}
 	for _, stmt := range p.Statements {
 		out.WriteString(stmt.String())
 	}
@@ -0,0 +1,21 @@
+package ast
+
+import "compiler/token"
+
+// AssignmentStatement stores a new value in a variable declared with let mut
+// Example: count = count + 1
+type AssignmentStatement struct {
+	Token token.Token // the '=' token
+	Name  *Identifier
+	Value Expression
+}
+
+func (assignment *AssignmentStatement) statementNode() {}
+
+func (assignment *AssignmentStatement) TokenLiteral() string {
+	return assignment.Token.Value
+}
+
+func (assignment *AssignmentStatement) String() string {
+	return assignment.Name.String() + " = " + assignment.Value.String()
+}
@@ -8,6 +8,7 @@ import (
 type BlockStatement struct {
 	Token      token.Token
 	Statements []Statement
+	End        token.Token // the closing '}', so tools can tell whether a position is inside the block
 }
 
 func (block *BlockStatement) statementNode() {}
@@ -0,0 +1,48 @@
+package ast
+
+import (
+	"bytes"
+	"compiler/token"
+)
+
+// ForStatement runs its body once for every integer in a range, or for every character of a string
+// Example: for i in 0..10 { print(i) }
+// Example: for ch in name { print(ch) }
+type ForStatement struct {
+	Token     token.Token // the 'for' token
+	Variable  *Identifier
+	Iterable  Expression // the string to iterate over; nil for a range, which uses Start and End
+	Start     Expression
+	End       Expression
+	Inclusive bool // ..= includes End, .. stops before it
+	Body      *BlockStatement
+}
+
+func (forStmt *ForStatement) statementNode() {}
+
+func (forStmt *ForStatement) TokenLiteral() string {
+	return forStmt.Token.Value
+}
+
+func (forStmt *ForStatement) String() string {
+	var out bytes.Buffer
+
+	out.WriteString("for ")
+	out.WriteString(forStmt.Variable.String())
+	out.WriteString(" in ")
+	if forStmt.Iterable != nil {
+		out.WriteString(forStmt.Iterable.String())
+	} else {
+		out.WriteString(forStmt.Start.String())
+		if forStmt.Inclusive {
+			out.WriteString("..=")
+		} else {
+			out.WriteString("..")
+		}
+		out.WriteString(forStmt.End.String())
+	}
+	out.WriteString(" ")
+	out.WriteString(forStmt.Body.String())
+
+	return out.String()
+}
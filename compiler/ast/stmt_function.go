@@ -7,11 +7,20 @@ import (
 )
 
 type FunctionStatement struct {
-	Token      token.Token
-	Name       *Identifier
-	Parameters []*FunctionParameter
-	ReturnType *TypeAnnotation
-	Body       *BlockStatement
+	Token       token.Token
+	Annotations []*Annotation
+	Name        *Identifier
+	Parameters  []*FunctionParameter
+	ReturnType  *TypeAnnotation
+	Body        *BlockStatement
+	Prelude     bool // defined by the compiler's prelude, not the user; diagnostics about user code skip it
+}
+
+// Annotation modifies how a declaration is compiled
+// Example: @inline
+type Annotation struct {
+	Token token.Token // the '@' token
+	Name  string
 }
 
 type FunctionParameter struct {
@@ -33,6 +42,10 @@ func (function *FunctionStatement) TokenLiteral() string {
 func (function *FunctionStatement) String() string {
 	var out bytes.Buffer
 
+	for _, annotation := range function.Annotations {
+		out.WriteString("@" + annotation.Name + " ")
+	}
+
 	out.WriteString("func ")
 	out.WriteString(function.Name.String())
 	out.WriteString("(")
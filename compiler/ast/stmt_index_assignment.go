@@ -0,0 +1,21 @@
+package ast
+
+import "compiler/token"
+
+// IndexAssignmentStatement stores a new value in one element of an array
+// Example: xs[i] = 0
+type IndexAssignmentStatement struct {
+	Token  token.Token // the '=' token
+	Target *IndexExpression
+	Value  Expression
+}
+
+func (assignment *IndexAssignmentStatement) statementNode() {}
+
+func (assignment *IndexAssignmentStatement) TokenLiteral() string {
+	return assignment.Token.Value
+}
+
+func (assignment *IndexAssignmentStatement) String() string {
+	return assignment.Target.String() + " = " + assignment.Value.String()
+}
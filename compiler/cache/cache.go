@@ -0,0 +1,95 @@
+package cache
+
+import (
+	"compiler/constants"
+	"compiler/prelude"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"sync"
+)
+
+// Cache stores generated LLVM IR keyed by a hash of everything that affects it,
+// so recompiling an unchanged file skips lowering and codegen entirely
+type Cache struct {
+	directory string
+}
+
+func New(directory string) *Cache {
+	return &Cache{
+		directory: directory,
+	}
+}
+
+// Key hashes the compiler build, the runtime ABI version, the prelude, the source text,
+// and any output-affecting flags
+// TODO: include the contents of imported files once imports exist
+func Key(source string, flags ...string) string {
+	hash := sha256.New()
+
+	for _, part := range []string{
+		constants.COMPILER_VERSION,
+		buildID(),
+		strconv.Itoa(constants.RUNTIME_ABI_VERSION),
+		prelude.Source,
+	} {
+		hash.Write([]byte(part))
+		hash.Write([]byte{0})
+	}
+	for _, flag := range flags {
+		hash.Write([]byte(flag))
+		hash.Write([]byte{0})
+	}
+	hash.Write([]byte(source))
+
+	return hex.EncodeToString(hash.Sum(nil))
+}
+
+// buildID identifies the running compiler by a hash of its executable
+// COMPILER_VERSION is not bumped for every change, so IR cached by an older build
+// would otherwise be reused after an upgrade
+// It is empty when the executable cannot be read, leaving the version to tell builds apart
+var buildID = sync.OnceValue(func() string {
+	executable, err := os.Executable()
+	if err != nil {
+		return ""
+	}
+
+	content, err := os.ReadFile(executable)
+	if err != nil {
+		return ""
+	}
+
+	sum := sha256.Sum256(content)
+	return hex.EncodeToString(sum[:])
+})
+
+// Lookup returns the cached IR for key, if present
+func (cache *Cache) Lookup(key string) (string, bool) {
+	content, err := os.ReadFile(cache.path(key))
+	if err != nil {
+		return "", false
+	}
+
+	return string(content), true
+}
+
+// Store saves the IR for key, creating the cache directory when needed
+func (cache *Cache) Store(key string, llvmIR string) error {
+	if err := os.MkdirAll(cache.directory, 0755); err != nil {
+		return fmt.Errorf("failed to create cache directory: %w", err)
+	}
+
+	if err := os.WriteFile(cache.path(key), []byte(llvmIR), 0644); err != nil {
+		return fmt.Errorf("failed to write cache entry: %w", err)
+	}
+
+	return nil
+}
+
+func (cache *Cache) path(key string) string {
+	return filepath.Join(cache.directory, key+".ll")
+}
@@ -0,0 +1,35 @@
+package cache
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestCache(t *testing.T) {
+	t.Run("Stored entries are found again", func(t *testing.T) {
+		cache := New(t.TempDir())
+		key := Key("func main() {}")
+
+		_, ok := cache.Lookup(key)
+		assert.False(t, ok, "empty cache should miss")
+
+		assert.NoError(t, cache.Store(key, "define i64 @main()"))
+
+		llvmIR, ok := cache.Lookup(key)
+		assert.True(t, ok)
+		assert.Equal(t, "define i64 @main()", llvmIR)
+	})
+
+	t.Run("Keys depend on source and flags", func(t *testing.T) {
+		assert.Equal(t, Key("a"), Key("a"))
+		assert.NotEqual(t, Key("a"), Key("b"))
+		assert.NotEqual(t, Key("a"), Key("a", "--flag"))
+		assert.NotEqual(t, Key("a", "x", "y"), Key("a", "xy"))
+	})
+
+	t.Run("Keys identify the compiler build", func(t *testing.T) {
+		assert.NotEmpty(t, buildID(), "the test binary should be readable")
+		assert.Equal(t, buildID(), buildID())
+	})
+}
@@ -0,0 +1,173 @@
+package main
+
+import (
+	"compiler/constants"
+	"compiler/symbols"
+	"compiler/timing"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+const (
+	COMMAND_COMPILE  = ""
+	COMMAND_RUN      = "run"
+	COMMAND_BUILD    = "build"
+	COMMAND_NEW      = "new"
+	COMMAND_TEST     = "test"
+	COMMAND_COMPLETE = "complete"
+	COMMAND_HELP     = "help"
+	COMMAND_VERSION  = "version"
+)
+
+// Command is a parsed command line: what to do, its positional arguments and the compile flags
+type Command struct {
+	Name      string
+	Arguments []string
+	Options   CompileOptions
+}
+
+// compileFlags maps each compile flag to the option it sets
+var compileFlags = map[string]func(options *CompileOptions){
+	"--timings":      func(options *CompileOptions) { options.Timings = timing.FormatTable },
+	"--timings=json": func(options *CompileOptions) { options.Timings = timing.FormatJSON },
+	"--no-cache":     func(options *CompileOptions) { options.NoCache = true },
+	"--tail-calls":   func(options *CompileOptions) { options.TailCalls = true },
+	"--no-prelude":   func(options *CompileOptions) { options.NoPrelude = true },
+	"--verify-types": func(options *CompileOptions) { options.VerifyTypes = true },
+	"--no-pie":       func(options *CompileOptions) { options.NoPIE = true },
+	"--no-color":     func(options *CompileOptions) { options.Color = COLOR_NEVER },
+
+	"--report=symbols":      func(options *CompileOptions) { options.Report = symbols.FormatTable },
+	"--report=symbols,json": func(options *CompileOptions) { options.Report = symbols.FormatJSON },
+}
+
+// ParseArguments parses the driver's arguments, without the program name
+// Flags may appear anywhere; unknown flags and wrong argument counts are errors
+func ParseArguments(arguments []string) (Command, error) {
+	command := Command{Name: COMMAND_COMPILE, Arguments: []string{}}
+	positional := []string{}
+	completeAt := ""
+
+	for _, argument := range arguments {
+		switch {
+		case argument == "--help" || argument == "-h":
+			return Command{Name: COMMAND_HELP}, nil
+		case argument == "--version":
+			return Command{Name: COMMAND_VERSION}, nil
+		case strings.HasPrefix(argument, "--complete="):
+			completeAt = strings.TrimPrefix(argument, "--complete=")
+		case strings.HasPrefix(argument, "--entry="):
+			command.Options.Entry = strings.TrimPrefix(argument, "--entry=")
+			if command.Options.Entry == "" {
+				return Command{}, fmt.Errorf("--entry expects a function name")
+			}
+		case strings.HasPrefix(argument, "--color="):
+			command.Options.Color = strings.TrimPrefix(argument, "--color=")
+			switch command.Options.Color {
+			case COLOR_ALWAYS, COLOR_AUTO, COLOR_NEVER:
+			default:
+				return Command{}, fmt.Errorf("--color expects always, auto or never, got '%s'", command.Options.Color)
+			}
+		case strings.HasPrefix(argument, "--cc="):
+			command.Options.CC = strings.TrimPrefix(argument, "--cc=")
+		case strings.HasPrefix(argument, "--max-errors="):
+			value := strings.TrimPrefix(argument, "--max-errors=")
+			limit, err := strconv.Atoi(value)
+			if err != nil || limit < 1 {
+				return Command{}, fmt.Errorf("--max-errors expects a positive number, got '%s'", value)
+			}
+			command.Options.MaxErrors = limit
+		case strings.HasPrefix(argument, "-"):
+			setOption, ok := compileFlags[argument]
+			if !ok {
+				return Command{}, fmt.Errorf("unknown flag '%s'", argument)
+			}
+			setOption(&command.Options)
+		default:
+			positional = append(positional, argument)
+		}
+	}
+
+	// --complete names its own file, so it takes no other arguments
+	if completeAt != "" {
+		if len(positional) > 0 {
+			return Command{}, fmt.Errorf("unexpected argument '%s'", positional[0])
+		}
+		command.Name = COMMAND_COMPLETE
+		command.Arguments = []string{completeAt}
+		return command, nil
+	}
+
+	if len(positional) > 0 {
+		switch positional[0] {
+		case COMMAND_RUN, COMMAND_BUILD, COMMAND_NEW, COMMAND_TEST:
+			command.Name = positional[0]
+			positional = positional[1:]
+		}
+	}
+	command.Arguments = positional
+	command.Options.Run = command.Name == COMMAND_RUN
+
+	switch command.Name {
+	case COMMAND_BUILD:
+		if len(positional) > 1 {
+			return Command{}, fmt.Errorf("unexpected argument '%s'", positional[1])
+		}
+	case COMMAND_NEW:
+		return command, expectOneArgument(positional, "project name")
+	default:
+		return command, expectOneArgument(positional, "source file")
+	}
+
+	return command, nil
+}
+
+// expectOneArgument reports a missing argument or the first extra one
+func expectOneArgument(positional []string, name string) error {
+	if len(positional) == 0 {
+		return fmt.Errorf("missing %s", name)
+	}
+	if len(positional) > 1 {
+		return fmt.Errorf("unexpected argument '%s'", positional[1])
+	}
+	return nil
+}
+
+// Usage lists the commands and flags the driver accepts
+func Usage() string {
+	extension := constants.NAVIARY_EXTENSION
+
+	lines := []string{
+		fmt.Sprintf("Usage: naviary [run] [flags] <source_file%s>", extension),
+		"       naviary build [directory]",
+		"       naviary new <project_name>",
+		fmt.Sprintf("       naviary test <source_file%s>", extension),
+		"",
+		"Commands:",
+		fmt.Sprintf("  naviary hello%s            # Compile only", extension),
+		fmt.Sprintf("  naviary run hello%s        # Compile and run", extension),
+		"  naviary build [directory]     # Build the project described by naviary.toml",
+		"  naviary new <project_name>    # Create a new project",
+		fmt.Sprintf("  naviary test hello%s       # Run every test_ function", extension),
+		"",
+		"Flags:",
+		"  --timings[=json]  Report time spent in each phase",
+		"  --no-cache        Ignore and do not update the compile cache",
+		"  --tail-calls      Let tail calls reuse the stack frame (builds with -O2); otherwise each recursive call grows the stack",
+		"  --no-prelude      Leave out the prelude helpers (square, midpoint)",
+		"  --verify-types    Check codegen against the lowerer's types (for compiler debugging)",
+		"  --no-pie          Link a position-dependent executable for platforms without PIE support",
+		"  --entry=<name>    Start the program at this function instead of main",
+		"  --color=<when>    Color diagnostics: always, never or auto (default; only on a terminal and without NO_COLOR)",
+		"  --no-color        Same as --color=never",
+		"  --cc=<compiler>   Link with this C compiler instead of $CC, clang or cc",
+		fmt.Sprintf("  --max-errors=<n>  Show at most n errors, in source order (default %d)", constants.MAX_DISPLAYED_ERRORS),
+		"  --report=symbols[,json]  List each function with its signature, LLVM instruction count and callees",
+		fmt.Sprintf("  --complete=<file%s>:<line>:<column>  Print the variables and functions in scope there as JSON", extension),
+		"  -h, --help        Show this message",
+		"  --version         Show the compiler version",
+	}
+
+	return strings.Join(lines, "\n") + "\n"
+}
@@ -0,0 +1,16 @@
+package llvm
+
+import "fmt"
+
+// VerificationError is returned when the generated module fails LLVM verification
+// It keeps the unverified IR so the driver can dump it for debugging
+type VerificationError struct {
+	Message    string
+	IR         string
+	Function   string // name of the offending function when the message names one
+	FunctionIR string
+}
+
+func (err *VerificationError) Error() string {
+	return fmt.Sprintf("failed to verify module: %s", err.Message)
+}
@@ -2,6 +2,7 @@ package llvm
 
 import (
 	llvmvalue "compiler/codegen/llvm/value"
+	"compiler/constants"
 	"compiler/nir"
 	nirinstruction "compiler/nir/instruction"
 	nirvalue "compiler/nir/value"
@@ -16,6 +17,7 @@ type FunctionConverter struct {
 	typeConverter        *TypeConverter
 	valueConverter       *llvmvalue.Converter
 	instructionConverter *InstructionConverter
+	options              Options
 }
 
 func NewFunctionConverter(
@@ -23,6 +25,7 @@ func NewFunctionConverter(
 	module llvm.Module,
 	typeConverter *TypeConverter,
 	valueConverter *llvmvalue.Converter,
+	options Options,
 ) *FunctionConverter {
 	return &FunctionConverter{
 		context:              context,
@@ -30,10 +33,13 @@ func NewFunctionConverter(
 		typeConverter:        typeConverter,
 		valueConverter:       valueConverter,
 		instructionConverter: nil,
+		options:              options,
 	}
 }
 
-func (converter *FunctionConverter) Convert(nirFunction *nir.Function) error {
+// Declare adds the function prototype to the module
+// All functions are declared before any body is converted so calls may refer to later functions
+func (converter *FunctionConverter) Declare(nirFunction *nir.Function) error {
 	parameterTypes, err := converter.convertParameterTypes(nirFunction.Parameters)
 	if err != nil {
 		return fmt.Errorf("failed to convert parameter types: %w", err)
@@ -48,14 +54,25 @@ func (converter *FunctionConverter) Convert(nirFunction *nir.Function) error {
 
 	llvmFunction := llvm.AddFunction(converter.module, nirFunction.Name, functionType)
 
-	converter.valueConverter.Reset()
+	if nirFunction.HasFlag(nir.FunctionInline) {
+		alwaysInline := converter.context.GetRawContext().CreateEnumAttribute(llvm.AttributeKindID("alwaysinline"), 0)
+		llvmFunction.AddFunctionAttr(alwaysInline)
+	}
 
-	err = converter.registerParameters(nirFunction, llvmFunction)
-	if err != nil {
-		return fmt.Errorf("failed to register parameters: %w", err)
+	return nil
+}
+
+func (converter *FunctionConverter) Convert(nirFunction *nir.Function) error {
+	llvmFunction := converter.module.NamedFunction(nirFunction.Name)
+	if llvmFunction.IsNil() {
+		return fmt.Errorf("function %s was not declared", nirFunction.Name)
 	}
 
-	err = converter.convertBasicBlocks(nirFunction, llvmFunction)
+	converter.valueConverter.Reset()
+
+	converter.registerParameters(nirFunction, llvmFunction)
+
+	err := converter.convertBasicBlocks(nirFunction, llvmFunction)
 	if err != nil {
 		return fmt.Errorf("failed to convert basic blocks: %w", err)
 	}
@@ -77,38 +94,17 @@ func (converter *FunctionConverter) convertParameterTypes(parameters []nir.Param
 	return llvmTypes, nil
 }
 
-func (converter *FunctionConverter) registerParameters(nirFunction *nir.Function, llvmFunction llvm.Value) error {
-	if len(nirFunction.Parameters) == 0 {
-		return nil
-	}
-
-	builder := converter.context.GetRawContext().NewBuilder()
-	defer builder.Dispose()
-
-	entryBlock := nirFunction.GetEntryBlock()
-	if entryBlock == nil {
-		return fmt.Errorf("function %s has no entry block", nirFunction.Name)
-	}
-
-	llvmEntryBlock := llvm.AddBasicBlock(llvmFunction, entryBlock.Name)
-	builder.SetInsertPointAtEnd(llvmEntryBlock)
-
+// registerParameters binds each parameter variable to the incoming argument
+// Parameters cannot be reassigned, so they need no stack storage
+func (converter *FunctionConverter) registerParameters(nirFunction *nir.Function, llvmFunction llvm.Value) {
 	for i, param := range nirFunction.Parameters {
 		llvmParam := llvmFunction.Param(i)
-		llvmType, err := converter.typeConverter.Convert(param.Type)
-		if err != nil {
-			return fmt.Errorf("failed to convert parameter %s type: %w", param.Name, err)
-		}
-
-		allocaInstruction := builder.CreateAlloca(llvmType, param.Name)
-
-		builder.CreateStore(llvmParam, allocaInstruction)
+		llvmParam.SetName(param.Name)
 
 		paramVariable := nirvalue.NewVariable(param.Name, param.Type)
-		converter.valueConverter.RegisterVariable(paramVariable, allocaInstruction)
+		converter.valueConverter.RegisterImmutable(paramVariable)
+		converter.valueConverter.BindImmutable(paramVariable, llvmParam)
 	}
-
-	return nil
 }
 
 func (converter *FunctionConverter) convertBasicBlocks(nirFunction *nir.Function, llvmFunction llvm.Value) error {
@@ -119,8 +115,11 @@ func (converter *FunctionConverter) convertBasicBlocks(nirFunction *nir.Function
 		builder,
 		converter.valueConverter,
 		converter.typeConverter,
+		converter.options,
 	)
 
+	// Every block exists before any is filled, so a branch can jump forward
+	blocks := map[string]llvm.BasicBlock{}
 	for _, nirBlock := range nirFunction.BasicBlocks {
 		var llvmBlock llvm.BasicBlock
 
@@ -135,13 +134,28 @@ func (converter *FunctionConverter) convertBasicBlocks(nirFunction *nir.Function
 			llvmBlock = llvm.AddBasicBlock(llvmFunction, nirBlock.Name)
 		}
 
-		builder.SetInsertPointAtEnd(llvmBlock)
+		blocks[nirBlock.Name] = llvmBlock
+	}
+	converter.instructionConverter.SetBlocks(blocks)
+
+	for _, nirBlock := range nirFunction.BasicBlocks {
+		builder.SetInsertPointAtEnd(blocks[nirBlock.Name])
+
+		if nirFunction.Name == "main" && nirBlock == nirFunction.GetEntryBlock() {
+			converter.emitAbiCheck(builder)
+		}
 
 		for _, instruction := range nirBlock.Instructions {
 			err := converter.convertInstruction(instruction)
 			if err != nil {
 				return fmt.Errorf("failed to convert instruction %s: %w", instruction.String(), err)
 			}
+
+			if converter.options.VerifyTypes {
+				if err := converter.verifyResultType(instruction); err != nil {
+					return err
+				}
+			}
 		}
 
 		if nirBlock.Terminator != nil {
@@ -155,6 +169,41 @@ func (converter *FunctionConverter) convertBasicBlocks(nirFunction *nir.Function
 	return nil
 }
 
+// emitAbiCheck calls into the runtime with the ABI version this compiler generates code for
+// The runtime aborts when it was built for another version instead of failing later in odd ways
+func (converter *FunctionConverter) emitAbiCheck(builder llvm.Builder) {
+	checkAbi := converter.module.NamedFunction("naviary_runtime_check_abi")
+	version := llvm.ConstInt(converter.context.GetRawContext().Int32Type(), constants.RUNTIME_ABI_VERSION, false)
+	builder.CreateCall(checkAbi.GlobalValueType(), checkAbi, []llvm.Value{version}, "")
+}
+
+// verifyResultType checks that the LLVM value produced for an instruction's result
+// has the type NIR assigned to it, so the lowerer and codegen cannot silently disagree
+// Allocations are skipped: their NIR value is the variable, their LLVM value a pointer to it
+func (converter *FunctionConverter) verifyResultType(instruction nirinstruction.Instruction) error {
+	temporary, ok := instruction.GetResult().(*nirvalue.Temporary)
+	if !ok {
+		return nil
+	}
+
+	expectedType, err := converter.typeConverter.Convert(temporary.Type())
+	if err != nil {
+		return fmt.Errorf("failed to convert type of %s: %w", temporary.String(), err)
+	}
+
+	llvmValue, err := converter.valueConverter.Convert(temporary)
+	if err != nil {
+		return fmt.Errorf("failed to look up %s: %w", temporary.String(), err)
+	}
+
+	if llvmValue.Type() != expectedType {
+		return fmt.Errorf("type verification failed for %s: NIR type %s is %s in LLVM, but codegen produced %s",
+			instruction.String(), temporary.Type().String(), expectedType.String(), llvmValue.Type().String())
+	}
+
+	return nil
+}
+
 func (converter *FunctionConverter) convertInstruction(instruction nirinstruction.Instruction) error {
 	switch instruction := instruction.(type) {
 	case *nirinstruction.AllocInstruction:
@@ -169,12 +218,34 @@ func (converter *FunctionConverter) convertInstruction(instruction nirinstructio
 	case *nirinstruction.BinaryInstruction:
 		return converter.instructionConverter.ConvertBinary(instruction)
 
+	case *nirinstruction.CompareInstruction:
+		return converter.instructionConverter.ConvertCompare(instruction)
+
 	case *nirinstruction.CallInstruction:
 		return converter.instructionConverter.ConvertCall(instruction)
 
+	case *nirinstruction.MakeArrayInstruction:
+		return converter.instructionConverter.ConvertMakeArray(instruction)
+
+	case *nirinstruction.ElementInstruction:
+		return converter.instructionConverter.ConvertElement(instruction)
+
+	case *nirinstruction.SetElementInstruction:
+		return converter.instructionConverter.ConvertSetElement(instruction)
+
 	case *nirinstruction.ReturnInstruction:
 		return converter.instructionConverter.ConvertReturn(instruction)
 
+	case *nirinstruction.UnreachableInstruction:
+		converter.instructionConverter.ConvertUnreachable()
+		return nil
+
+	case *nirinstruction.BranchInstruction:
+		return converter.instructionConverter.ConvertBranch(instruction)
+
+	case *nirinstruction.ConditionalBranchInstruction:
+		return converter.instructionConverter.ConvertConditionalBranch(instruction)
+
 	default:
 		return fmt.Errorf("unsupported instruction type: %T", instruction)
 	}
@@ -3,10 +3,28 @@ package llvm
 import (
 	"compiler/nir"
 	"fmt"
+
+	"tinygo.org/x/go-llvm"
 )
 
+// Options controls optional code generation behavior
+type Options struct {
+	// TailCalls marks calls in tail position with LLVM's tail marker so the
+	// optimizer can reuse the caller's frame. The marker is only a hint: the
+	// driver also builds with -O2, since unoptimized code still gives every
+	// recursive call a new native stack frame and deep recursion overflows the stack
+	TailCalls bool
+
+	// VerifyTypes checks that every value codegen produces has the LLVM type
+	// implied by its NIR type and fails compilation when they disagree
+	VerifyTypes bool
+}
+
 type Generator struct {
 	context *Context
+	options Options
+	// moduleConverter of the last generated module, kept so its IR can be inspected
+	moduleConverter *ModuleConverter
 }
 
 func NewGenerator() *Generator {
@@ -15,8 +33,19 @@ func NewGenerator() *Generator {
 	}
 }
 
+// SetOptions changes the options used by subsequent calls to Generate
+func (generator *Generator) SetOptions(options Options) {
+	generator.options = options
+}
+
+// Generate converts a NIR module and returns its IR as a string the caller owns
+// A generator can be reused for many modules: each call frees the module of the
+// previous one, so only the last module is alive until Dispose
 func (generator *Generator) Generate(nirModule *nir.Module) (string, error) {
-	moduleConverter := NewModuleConverter(generator.context, nirModule.Name)
+	generator.disposeModule()
+
+	moduleConverter := NewModuleConverter(generator.context, nirModule.Name, generator.options)
+	generator.moduleConverter = moduleConverter
 
 	llvmIr, err := moduleConverter.Convert(nirModule)
 	if err != nil {
@@ -26,9 +55,69 @@ func (generator *Generator) Generate(nirModule *nir.Module) (string, error) {
 	return llvmIr, nil
 }
 
+// PrintToString returns the IR of the last generated module
+func (generator *Generator) PrintToString() string {
+	if generator.moduleConverter == nil {
+		return ""
+	}
+
+	return generator.moduleConverter.module.String()
+}
+
+// GetFunctionIR returns the IR of a single function in the last generated module
+func (generator *Generator) GetFunctionIR(name string) (string, bool) {
+	if generator.moduleConverter == nil {
+		return "", false
+	}
+
+	function := generator.moduleConverter.module.NamedFunction(name)
+	if function.IsNil() {
+		return "", false
+	}
+
+	return function.String(), true
+}
+
+// InstructionCounts returns the number of LLVM instructions in each function defined by the last generated module
+// Declarations of runtime functions have no body and are left out
+func (generator *Generator) InstructionCounts() map[string]int {
+	counts := map[string]int{}
+	if generator.moduleConverter == nil {
+		return counts
+	}
+
+	module := generator.moduleConverter.module
+	for function := module.FirstFunction(); !function.IsNil(); function = llvm.NextFunction(function) {
+		if function.IsDeclaration() {
+			continue
+		}
+
+		count := 0
+		for block := function.FirstBasicBlock(); !block.IsNil(); block = llvm.NextBasicBlock(block) {
+			for inst := block.FirstInstruction(); !inst.IsNil(); inst = llvm.NextInstruction(inst) {
+				count++
+			}
+		}
+		counts[function.Name()] = count
+	}
+
+	return counts
+}
+
+// Dispose frees the last module and the LLVM context; the generator cannot be used afterwards
 func (generator *Generator) Dispose() {
+	generator.disposeModule()
 	if generator.context != nil {
 		generator.context.Dispose()
 		generator.context = nil
 	}
 }
+
+func (generator *Generator) disposeModule() {
+	if generator.moduleConverter == nil {
+		return
+	}
+
+	generator.moduleConverter.module.Dispose()
+	generator.moduleConverter = nil
+}
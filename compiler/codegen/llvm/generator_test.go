@@ -0,0 +1,490 @@
+package llvm
+
+import (
+	"compiler/constants"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/nir"
+	"compiler/nir/instruction"
+	nirvalue "compiler/nir/value"
+	"compiler/parser"
+	"compiler/types"
+	goerrors "errors"
+	"fmt"
+	"os"
+	"runtime"
+	"strconv"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+// compileSource runs the full pipeline on source
+// The caller owns the returned generator and must dispose it
+func compileSource(t *testing.T, source string) *Generator {
+	return compileSourceWithOptions(t, source, Options{})
+}
+
+// compileSourceWithOptions is compileSource with explicit code generation options
+// Type verification is always on, so every IR test also checks codegen against the lowerer
+func compileSourceWithOptions(t *testing.T, source string, options Options) *Generator {
+	options.VerifyTypes = true
+
+	errorCollector := errors.New(source, "test.navi")
+	lexerInstance := lexer.New(source, "test.navi", errorCollector)
+	program := parser.New(lexerInstance, errorCollector).ParseProgram()
+	nirModule := nir.NewLowerer(errorCollector).Lower(program)
+
+	assert.False(t, errorCollector.HasErrors(), "source should compile without errors")
+
+	generator := NewGenerator()
+	generator.SetOptions(options)
+	_, err := generator.Generate(nirModule)
+	assert.NoError(t, err)
+
+	return generator
+}
+
+// generateSource runs the full pipeline on source and returns the printed LLVM IR
+func generateSource(t *testing.T, source string) string {
+	generator := compileSource(t, source)
+	defer generator.Dispose()
+
+	return generator.PrintToString()
+}
+
+// generateFunction runs the full pipeline on source and returns the IR of one function
+func generateFunction(t *testing.T, source string, name string) string {
+	generator := compileSource(t, source)
+	defer generator.Dispose()
+
+	functionIR, ok := generator.GetFunctionIR(name)
+	assert.True(t, ok, "function %s should exist", name)
+
+	return functionIR
+}
+
+func TestGenerator(t *testing.T) {
+	t.Run("Verification failure keeps the invalid IR", func(t *testing.T) {
+		// main declares an int result but returns nothing
+		function := nir.NewFunction("main", nil, types.Int)
+		entryBlock := nir.NewBasicBlock("entry")
+		entryBlock.Terminator = instruction.NewReturnInstruction(nil)
+		function.AddBasicBlock(entryBlock)
+
+		module := nir.NewModule("main")
+		module.AddFunction(function)
+
+		generator := NewGenerator()
+		defer generator.Dispose()
+
+		_, err := generator.Generate(module)
+
+		var verificationError *VerificationError
+		if assert.True(t, goerrors.As(err, &verificationError),
+			"expected a VerificationError, got %v", err) {
+			assert.Contains(t, verificationError.IR, "define i64 @main")
+			assert.Contains(t, verificationError.IR, "ret void")
+		}
+	})
+
+	t.Run("Type verification reports disagreements", func(t *testing.T) {
+		// The NIR claims an int + int addition yields a bool
+		function := nir.NewFunction("main", nil, types.Int)
+		entryBlock := nir.NewBasicBlock("entry")
+		one := nirvalue.NewConstant(1, types.Int)
+		entryBlock.AddInstruction(instruction.NewBinaryInstruction(nirvalue.NewTemporary(0, types.Bool), instruction.BinaryAdd, one, one))
+		entryBlock.Terminator = instruction.NewReturnInstruction(nirvalue.NewConstant(0, types.Int))
+		function.AddBasicBlock(entryBlock)
+
+		module := nir.NewModule("main")
+		module.AddFunction(function)
+
+		generator := NewGenerator()
+		defer generator.Dispose()
+		generator.SetOptions(Options{VerifyTypes: true})
+
+		_, err := generator.Generate(module)
+
+		if assert.Error(t, err) {
+			assert.Contains(t, err.Error(), "type verification failed for %0: bool = Add(")
+			assert.Contains(t, err.Error(), "NIR type bool is i1 in LLVM, but codegen produced i64")
+		}
+	})
+
+	t.Run("Output is deterministic across compiles", func(t *testing.T) {
+		source := "func helper() {\n  print(1)\n}\n\nfunc main() {\n  let x = 1 + 2\n  print(x)\n  helper()\n}\n"
+
+		first := generateSource(t, source)
+		second := generateSource(t, source)
+
+		assert.NotEmpty(t, first)
+		assert.Equal(t, first, second, "compiling the same source twice must produce identical IR")
+	})
+
+	t.Run("IR patterns", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			source   string
+			function string
+			contains []string
+		}{
+			{
+				name:     "Print of an int literal calls print_int",
+				source:   "func main() {\n  print(1)\n}\n",
+				function: "main",
+				contains: []string{"define i64 @main()", "call void @print_int(i64 1)", "ret i64 0"},
+			},
+			{
+				name:     "Let binds the variable to its value",
+				source:   "func main() {\n  let x = 1\n  print(x)\n}\n",
+				function: "main",
+				contains: []string{"call void @print_int(i64 1)"},
+			},
+			{
+				name:     "Arithmetic on variables emits instructions",
+				source:   "func calc(a: int) {\n  let b = a * 2 - a / 3\n  print(b)\n}\n\nfunc main() {\n  calc(6)\n}\n",
+				function: "calc",
+				contains: []string{"mul i64", "sdiv i64", "sub i64"},
+			},
+			{
+				name:     "User function calls are direct calls",
+				source:   "func helper() {\n  print(1)\n}\n\nfunc main() {\n  helper()\n}\n",
+				function: "main",
+				contains: []string{"call void @helper()"},
+			},
+			{
+				name:     "Functions without return type return void",
+				source:   "func helper() {\n  print(1)\n}\n\nfunc main() {\n  helper()\n}\n",
+				function: "helper",
+				contains: []string{"define void @helper()", "ret void"},
+			},
+			{
+				name:     "Bool equality compares i1 values",
+				source:   "func same(a: bool, b: bool) {\n  print(a == b)\n}\n\nfunc main() {\n  same(true, false)\n}\n",
+				function: "same",
+				contains: []string{"icmp eq i1 %a, %b", "call void @print_bool(i1"},
+			},
+			{
+				name:     "Int ordering uses signed predicates",
+				source:   "func order(a: int) {\n  print(a < 2)\n  print(a >= 2)\n}\n\nfunc main() {\n  order(1)\n}\n",
+				function: "order",
+				contains: []string{"icmp slt i64", "icmp sge i64"},
+			},
+			{
+				name:     "Functions load globals",
+				source:   "let mut counter = 5\n\nfunc show() {\n  print(counter)\n}\n\nfunc main() {\n  show()\n}\n",
+				function: "show",
+				contains: []string{"load i64", "@counter"},
+			},
+			{
+				name:     "Chars are 32-bit scalars",
+				source:   "func show(c: char) {\n  print(c)\n  print(c < 'z')\n}\n\nfunc main() {\n  show('a')\n}\n",
+				function: "show",
+				contains: []string{"define void @show(i32 %c)", "call void @print_char(i32 %c)", "icmp slt i32 %c, 122"},
+			},
+			{
+				name:     "Char builtins call the runtime",
+				source:   "func main() {\n  print(char_from(char_code('a') + 1))\n}\n",
+				function: "main",
+				contains: []string{"call i64 @naviary_char_code(i32 97)", "call i32 @naviary_char_from(i64"},
+			},
+			{
+				name:     "String indexing calls the runtime and yields a char",
+				source:   "func first(s: string) -> char {\n  return s[0]\n}\n\nfunc main() {\n  print(first(\"hi\"))\n}\n",
+				function: "first",
+				contains: []string{"call i32 @naviary_string_index(ptr %s, i64 0)", "ret i32"},
+			},
+			{
+				name:     "Formatting builtins return strings",
+				source:   "func main() {\n  print(format_pad(7, 4))\n  print(to_hex(255))\n}\n",
+				function: "main",
+				contains: []string{"@naviary_format_pad(i64 7, i64 4)", "@naviary_to_hex(i64 255)", "call void @print_string("},
+			},
+			{
+				name:     "Floats print through the runtime formatter",
+				source:   "func show(x: float) {\n  print(x)\n  print(to_string(x))\n  print(x, 1)\n}\n\nfunc main() {\n}\n",
+				function: "show",
+				contains: []string{"call void @print_float(double %", "@naviary_float_to_string(double %", "call void @print_string(", "@print_values(", ", i64 1)"},
+			},
+			{
+				name:     "Bool results are returned and printed",
+				source:   "func is_pos(x: int) -> bool {\n  return x > 0\n}\n\nfunc main() {\n  let p = is_pos(3)\n  print(p)\n  print(is_pos(0 - 1))\n}\n",
+				function: "main",
+				contains: []string{"call i1 @is_pos(i64 3)", "call void @print_bool(i1 %"},
+			},
+			{
+				name:     "Bool functions return the comparison",
+				source:   "func is_pos(x: int) -> bool {\n  return x > 0\n}\n\nfunc main() {\n}\n",
+				function: "is_pos",
+				contains: []string{"define i1 @is_pos(i64", "icmp sgt i64", "ret i1"},
+			},
+			{
+				name:     "Repeat calls the runtime",
+				source:   "func main() {\n  print(repeat(\"ab\", 3))\n}\n",
+				function: "main",
+				contains: []string{"@naviary_string_repeat(", "i64 3)", "call void @print_string("},
+			},
+			{
+				name:     "Call results are returned",
+				source:   "func seven() -> int {\n  return 7\n}\n\nfunc main() {\n  print(seven())\n}\n",
+				function: "main",
+				contains: []string{"call i64 @seven()", "call void @print_int(i64 %"},
+			},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				functionIR := generateFunction(t, testCase.source, testCase.function)
+
+				for _, pattern := range testCase.contains {
+					assert.Contains(t, functionIR, pattern)
+				}
+			})
+		}
+	})
+
+	t.Run("Immutable variables and parameters need no stack storage", func(t *testing.T) {
+		source := "func scale(x: int, factor: int) -> int {\n  let scaled = x * factor\n  let shifted = scaled + 1\n  return shifted\n}\n\n" +
+			"func main() {\n  let n = scale(3, 4)\n  print(n)\n}\n"
+
+		scaleIR := generateFunction(t, source, "scale")
+		assert.Contains(t, scaleIR, "define i64 @scale(i64 %x, i64 %factor)")
+		assert.Contains(t, scaleIR, "mul i64 %x, %factor")
+		for _, memoryOperation := range []string{"alloca", "load", "store"} {
+			assert.NotContains(t, scaleIR, memoryOperation)
+		}
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Regexp(t, `%\d+ = call i64 @scale\(i64 3, i64 4\)\n  call void @print_int\(i64 %\d+\)`, mainIR)
+		assert.NotContains(t, mainIR, "alloca")
+	})
+
+	t.Run("Mutable variables get stack storage", func(t *testing.T) {
+		source := "func main() {\n  let mut count = 1\n  print(count)\n}\n"
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Contains(t, mainIR, "alloca i64")
+		assert.Contains(t, mainIR, "store i64 1, ptr")
+		assert.Contains(t, mainIR, "load i64, ptr")
+	})
+
+	t.Run("Loop variables are allocated once in the entry block", func(t *testing.T) {
+		source := "func main() {\n  for i in 0..3 {\n    let mut square = i * i\n    print(square)\n  }\n}\n"
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Equal(t, 2, strings.Count(mainIR, "alloca i64"))
+		assert.Less(t, strings.LastIndex(mainIR, "alloca"), strings.Index(mainIR, "br "))
+		assert.Contains(t, mainIR, "br i1")
+		assert.Contains(t, mainIR, "for.body.0:")
+	})
+
+	t.Run("Arrays are allocated and indexed through the runtime", func(t *testing.T) {
+		source := "func main() {\n  let xs = [1, 2, 3]\n  print(xs[2])\n}\n"
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Contains(t, mainIR, "call ptr @naviary_array_init_int(ptr @.array, i64 3)")
+		assert.Contains(t, mainIR, "call void @naviary_array_check_index(ptr")
+		assert.Less(t, strings.Index(mainIR, "@naviary_array_check_index"), strings.Index(mainIR, "load i64"))
+	})
+
+	t.Run("Constant int array literals are copied from a constant global", func(t *testing.T) {
+		source := "func main() {\n  let xs = [4, 5, 6]\n  print(xs[0])\n}\n"
+
+		llvmIR := generateSource(t, source)
+		assert.Contains(t, llvmIR, "@.array = private unnamed_addr constant [3 x i64] [i64 4, i64 5, i64 6]")
+		assert.NotContains(t, llvmIR, "call ptr @naviary_array_new")
+		assert.NotContains(t, llvmIR, "store i64")
+	})
+
+	t.Run("Array literals with a non-constant element store each element", func(t *testing.T) {
+		source := "func five() -> int {\n  return 5\n}\n\n" +
+			"func main() {\n  let xs = [4, five(), 6]\n  print(xs[0])\n}\n"
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Contains(t, mainIR, "call ptr @naviary_array_new(i64 3, ")
+		assert.Equal(t, 3, strings.Count(mainIR, "store i64"))
+		assert.NotContains(t, mainIR, "@naviary_array_init_int")
+	})
+
+	t.Run("Arguments and operands are evaluated left to right", func(t *testing.T) {
+		source := "func first() -> int {\n  print(1)\n  return 1\n}\n\n" +
+			"func second() -> int {\n  print(2)\n  return 2\n}\n\n" +
+			"func pair(a: int, b: int) -> int {\n  return a + b\n}\n\n" +
+			"func main() {\n  print(pair(first(), second()))\n  print(second() + first())\n  print(first(), second())\n}\n"
+
+		mainIR := generateFunction(t, source, "main")
+
+		calls := []string{}
+		for _, line := range strings.Split(mainIR, "\n") {
+			for _, name := range []string{"@first(", "@second(", "@pair(", "@print_int(", "@print_values("} {
+				if strings.Contains(line, name) {
+					calls = append(calls, strings.TrimSuffix(strings.TrimPrefix(name, "@"), "("))
+				}
+			}
+		}
+
+		assert.Equal(t, []string{
+			"first", "second", "pair", "print_int",
+			"second", "first", "print_int",
+			"first", "second", "print_values",
+		}, calls)
+	})
+
+	t.Run("String literals are module-level constants emitted once", func(t *testing.T) {
+		source := "func greet() {\n  print(\"hello\")\n}\n\n" +
+			"func main() {\n  print(\"hello\")\n  greet()\n  print(\"bye\")\n}\n"
+
+		llvmIR := generateSource(t, source)
+
+		assert.Equal(t, 1, strings.Count(llvmIR, `c"hello\00"`), "identical literals should share one global")
+		assert.Contains(t, llvmIR, `@.str.0 = private unnamed_addr constant [6 x i8] c"hello\00"`)
+		assert.Contains(t, llvmIR, `@.str.1 = private unnamed_addr constant [4 x i8] c"bye\00"`)
+		assert.Equal(t, 2, strings.Count(llvmIR, "@print_string(ptr @.str.0)"))
+	})
+
+	t.Run("Adjacent string literals become one constant", func(t *testing.T) {
+		source := "func main() {\n  print(\"a long message that \" \"continues here\")\n}\n"
+
+		llvmIR := generateSource(t, source)
+
+		assert.Equal(t, 1, strings.Count(llvmIR, "continues here"))
+		assert.Contains(t, llvmIR, "a long message that continues here")
+		assert.Equal(t, 1, strings.Count(llvmIR, "call void @print_string("))
+	})
+
+	t.Run("Print with several values makes one runtime call", func(t *testing.T) {
+		source := "func show(n: int) {\n  print(n, 'x', n < 4, 7)\n}\n\nfunc main() {\n  show(3)\n}\n"
+
+		generator := compileSource(t, source)
+		defer generator.Dispose()
+		mainIR, _ := generator.GetFunctionIR("show")
+
+		assert.Equal(t, 1, strings.Count(mainIR, "@print_values("))
+		assert.NotContains(t, mainIR, "@print_int")
+		assert.NotContains(t, mainIR, "@print_char")
+		assert.Contains(t, mainIR, "zext i1", "bools are promoted to int for varargs")
+		assert.Contains(t, mainIR, "i32 120, i32 %")
+		assert.Contains(t, generator.PrintToString(), `c"icbi\00"`, "the descriptor names each value's type")
+	})
+
+	t.Run("Main checks the runtime ABI before running any code", func(t *testing.T) {
+		source := "func helper() {\n  print(1)\n}\n\nfunc main() {\n  helper()\n}\n"
+		check := fmt.Sprintf("call void @naviary_runtime_check_abi(i32 %d)", constants.RUNTIME_ABI_VERSION)
+
+		mainIR := generateFunction(t, source, "main")
+		assert.Contains(t, mainIR, "entry:\n  "+check, "the check is the first instruction of main")
+		assert.Less(t, strings.Index(mainIR, check), strings.Index(mainIR, "call void @helper()"))
+
+		assert.NotContains(t, generateFunction(t, source, "helper"), "naviary_runtime_check_abi")
+	})
+
+	t.Run("GetFunctionIR only returns the requested function", func(t *testing.T) {
+		generator := compileSource(t, "func helper() {\n  print(1)\n}\n\nfunc main() {\n  helper()\n}\n")
+		defer generator.Dispose()
+
+		functionIR, ok := generator.GetFunctionIR("main")
+		assert.True(t, ok)
+		assert.NotContains(t, functionIR, "define void @helper")
+
+		_, ok = generator.GetFunctionIR("missing")
+		assert.False(t, ok)
+	})
+
+	t.Run("Globals are emitted with initializers", func(t *testing.T) {
+		source := "let mut counter = 5\nlet limit = 10\nlet verbose = true\n\nfunc main() {\n  print(counter + limit)\n}\n"
+
+		llvmIR := generateSource(t, source)
+
+		assert.Contains(t, llvmIR, "@counter = global i64 5")
+		assert.Contains(t, llvmIR, "@limit = constant i64 10")
+		assert.Contains(t, llvmIR, "@verbose = constant i1 true")
+	})
+
+	t.Run("Tail calls are only marked when enabled", func(t *testing.T) {
+		source := "func countdown(n: int) -> int {\n  return countdown(n - 1)\n}\n\nfunc main() {\n  print(countdown(10))\n}\n"
+
+		tailGenerator := compileSourceWithOptions(t, source, Options{TailCalls: true})
+		defer tailGenerator.Dispose()
+		tailIR, _ := tailGenerator.GetFunctionIR("countdown")
+		assert.Contains(t, tailIR, "tail call i64 @countdown")
+
+		mainIR, _ := tailGenerator.GetFunctionIR("main")
+		assert.NotContains(t, mainIR, "tail call", "a call whose result is printed is not in tail position")
+
+		assert.NotContains(t, generateFunction(t, source, "countdown"), "tail call")
+	})
+
+	t.Run("Inline functions are marked alwaysinline", func(t *testing.T) {
+		source := "@inline func square(x: int) -> int {\n  return x * x\n}\n\nfunc main() {\n  print(square(3))\n}\n"
+
+		llvmIR := generateSource(t, source)
+
+		assert.Regexp(t, `define i64 @square\(i64 %\w+\) #\d+`, llvmIR)
+		assert.Regexp(t, `attributes #\d+ = \{ alwaysinline \}`, llvmIR)
+		assert.NotRegexp(t, `define i64 @main\(\) #\d+`, llvmIR)
+	})
+}
+
+// residentMemory returns the process's resident set size in bytes, or false where /proc is unavailable
+func residentMemory() (int64, bool) {
+	statm, err := os.ReadFile("/proc/self/statm")
+	if err != nil {
+		return 0, false
+	}
+
+	fields := strings.Fields(string(statm))
+	if len(fields) < 2 {
+		return 0, false
+	}
+
+	pages, err := strconv.ParseInt(fields[1], 10, 64)
+	if err != nil {
+		return 0, false
+	}
+
+	return pages * int64(os.Getpagesize()), true
+}
+
+func TestGeneratorReuse(t *testing.T) {
+	if testing.Short() {
+		t.Skip("compiles a thousand modules")
+	}
+	if _, ok := residentMemory(); !ok {
+		t.Skip("resident memory is only measured through /proc")
+	}
+
+	var source strings.Builder
+	for i := 0; i < 20; i++ {
+		fmt.Fprintf(&source, "func helper_%d(x: int) -> int {\n  return x * %d + 1\n}\n\n", i, i)
+	}
+	source.WriteString("func main() {\n  print(helper_0(1))\n}\n")
+
+	errorCollector := errors.New(source.String(), "test.navi")
+	program := parser.New(lexer.New(source.String(), "test.navi", errorCollector), errorCollector).ParseProgram()
+	assert.False(t, errorCollector.HasErrors())
+
+	generator := NewGenerator()
+	defer generator.Dispose()
+
+	compile := func(count int) {
+		for i := 0; i < count; i++ {
+			_, err := generator.Generate(nir.NewLowerer(errorCollector).Lower(program))
+			assert.NoError(t, err)
+		}
+	}
+
+	// Warm up so allocator pools and Go's heap have reached a steady size
+	compile(100)
+	runtime.GC()
+	before, _ := residentMemory()
+
+	compile(1000)
+	runtime.GC()
+	after, _ := residentMemory()
+
+	// Leaking every module grows by far more than this; the margin absorbs allocator noise
+	assert.Less(t, after-before, int64(32<<20),
+		"memory should stay bounded when one generator compiles many modules")
+}
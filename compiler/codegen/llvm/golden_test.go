@@ -0,0 +1,80 @@
+package llvm
+
+import (
+	"flag"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+// Rewrite the snapshots from the current compiler with:
+//
+//	go test ./codegen/llvm -run TestGolden -update
+var updateGolden = flag.Bool("update", false, "rewrite golden IR snapshots in testdata/golden")
+
+var (
+	valueNumber  = regexp.MustCompile(`%[0-9]+\b`)
+	stringGlobal = regexp.MustCompile(`@\.str\.[0-9]+\b`)
+	alignment    = regexp.MustCompile(`, align [0-9]+`)
+)
+
+// normalizeIR hides details that change whenever unrelated code is added:
+// numbered values become %_ and string literal globals lose their suffix
+// Alignments are dropped too: the module sets no data layout, so they are the defaults of the LLVM linked in
+func normalizeIR(llvmIR string) string {
+	llvmIR = valueNumber.ReplaceAllString(llvmIR, "%_")
+	llvmIR = stringGlobal.ReplaceAllString(llvmIR, "@.str")
+	llvmIR = alignment.ReplaceAllString(llvmIR, "")
+	return strings.TrimSpace(llvmIR) + "\n"
+}
+
+// assertGoldenFunction compiles source and compares one function's normalized IR
+// with testdata/golden/<name>.ll
+func assertGoldenFunction(t *testing.T, name string, source string, function string) {
+	actual := normalizeIR(generateFunction(t, source, function))
+	path := filepath.Join("testdata", "golden", name+".ll")
+
+	if *updateGolden {
+		assert.NoError(t, os.WriteFile(path, []byte(actual), 0644))
+		return
+	}
+
+	expected, err := os.ReadFile(path)
+	if !assert.NoError(t, err, "missing snapshot, run the tests with -update to create it") {
+		return
+	}
+	assert.Equal(t, string(expected), actual, "IR of %s changed; run the tests with -update if this is intended", function)
+}
+
+func TestGolden(t *testing.T) {
+	t.Run("Normalization", func(t *testing.T) {
+		llvmIR := "\n  %3 = load i64, ptr %x, align 8\n  call void @print_string(ptr @.str.12)\n"
+
+		assert.Equal(t, "%_ = load i64, ptr %x\n  call void @print_string(ptr @.str)\n", normalizeIR(llvmIR))
+	})
+
+	// if/else gets a snapshot once the language has it
+	tests := []struct {
+		name     string
+		source   string
+		function string
+	}{
+		{"print_int", "func main() {\n  print(1)\n}\n", "main"},
+		{"print_string", "func main() {\n  print(\"hello\")\n}\n", "main"},
+		{"immutable_locals", "func scale(x: int, factor: int) -> int {\n  let scaled = x * factor\n  let shifted = scaled + 1\n  return shifted\n}\n\nfunc main() {\n}\n", "scale"},
+		{"range_loop", "func total(n: int) -> int {\n  let mut sum = 0\n  for i in 0..n {\n    sum = sum + i\n  }\n  return sum\n}\n\nfunc main() {\n}\n", "total"},
+		{"string_loop", "func spell(s: string) {\n  for ch in s {\n    print(ch)\n  }\n}\n\nfunc main() {\n}\n", "spell"},
+		{"constant_array_literal", "func second() -> int {\n  let xs = [1, 2, 3]\n  return xs[1]\n}\n\nfunc main() {\n}\n", "second"},
+		{"array_literal", "func pair(x: int) -> int[] {\n  return [x, 2]\n}\n\nfunc main() {\n}\n", "pair"},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			assertGoldenFunction(t, testCase.name, testCase.source, testCase.function)
+		})
+	}
+}
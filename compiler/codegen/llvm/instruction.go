@@ -14,20 +14,29 @@ type InstructionConverter struct {
 	builder        llvm.Builder
 	valueConverter *llvmvalue.Converter
 	typeConverter  *TypeConverter
+	options        Options
+	blocks         map[string]llvm.BasicBlock // the current function's blocks by NIR name
 }
 
 func NewInstructionConverter(
 	builder llvm.Builder,
 	valueConverter *llvmvalue.Converter,
 	typeConverter *TypeConverter,
+	options Options,
 ) *InstructionConverter {
 	return &InstructionConverter{
 		builder:        builder,
 		valueConverter: valueConverter,
 		typeConverter:  typeConverter,
+		options:        options,
 	}
 }
 
+// SetBlocks gives branches the blocks of the function being converted
+func (converter *InstructionConverter) SetBlocks(blocks map[string]llvm.BasicBlock) {
+	converter.blocks = blocks
+}
+
 func (converter *InstructionConverter) ConvertAlloc(allocInstruction *instruction.AllocInstruction) error {
 	allocateType := allocInstruction.GetAllocateType()
 
@@ -46,24 +55,47 @@ func (converter *InstructionConverter) ConvertAlloc(allocInstruction *instructio
 		return fmt.Errorf("alloc result must be a variable, got %T", result)
 	}
 
-	allocaInstruction := converter.builder.CreateAlloca(llvmType, variable.String())
+	// An immutable variable needs no storage: its store binds the value and its loads reuse it
+	if !allocInstruction.IsMutable() {
+		converter.valueConverter.RegisterImmutable(variable)
+		return nil
+	}
+
+	allocaInstruction := converter.createEntryAlloca(llvmType, variable.String())
 
 	converter.valueConverter.RegisterVariable(variable, allocaInstruction)
 
 	return nil
 }
 
+// createEntryAlloca places an alloca after the others at the top of the entry block,
+// so a variable declared in a loop body reuses one stack slot instead of growing the stack every iteration
+func (converter *InstructionConverter) createEntryAlloca(llvmType llvm.Type, name string) llvm.Value {
+	current := converter.builder.GetInsertBlock()
+	entry := current.Parent().EntryBasicBlock()
+
+	position := entry.FirstInstruction()
+	for !position.IsNil() && !position.IsAAllocaInst().IsNil() {
+		position = llvm.NextInstruction(position)
+	}
+	if position.IsNil() {
+		converter.builder.SetInsertPointAtEnd(entry)
+	} else {
+		converter.builder.SetInsertPointBefore(position)
+	}
+
+	allocaInstruction := converter.builder.CreateAlloca(llvmType, name)
+	converter.builder.SetInsertPointAtEnd(current)
+
+	return allocaInstruction
+}
+
 func (converter *InstructionConverter) ConvertStore(storeInstruction *instruction.StoreInstruction) error {
 	destination := storeInstruction.GetDestination()
 	if destination == nil {
 		return fmt.Errorf("store instruction has no destination")
 	}
 
-	destinationPointer, err := converter.valueConverter.Convert(destination)
-	if err != nil {
-		return fmt.Errorf("failed to convert store destination: %w", err)
-	}
-
 	value := storeInstruction.GetValue()
 	if value == nil {
 		return fmt.Errorf("store instruction has no value")
@@ -74,6 +106,16 @@ func (converter *InstructionConverter) ConvertStore(storeInstruction *instructio
 		return fmt.Errorf("failed to convert store value: %w", err)
 	}
 
+	if variable, ok := destination.(*nirvalue.Variable); ok && converter.valueConverter.IsImmutable(variable) {
+		converter.valueConverter.BindImmutable(variable, llvmValue)
+		return nil
+	}
+
+	destinationPointer, err := converter.valueConverter.Convert(destination)
+	if err != nil {
+		return fmt.Errorf("failed to convert store destination: %w", err)
+	}
+
 	converter.builder.CreateStore(llvmValue, destinationPointer)
 
 	return nil
@@ -85,11 +127,6 @@ func (converter *InstructionConverter) ConvertLoad(loadInstruction *instruction.
 		return fmt.Errorf("load instruction has no source")
 	}
 
-	sourcePointer, err := converter.valueConverter.Convert(source)
-	if err != nil {
-		return fmt.Errorf("failed to convert load source: %w", err)
-	}
-
 	result := loadInstruction.GetResult()
 	if result == nil {
 		return fmt.Errorf("load instruction has no result")
@@ -100,6 +137,20 @@ func (converter *InstructionConverter) ConvertLoad(loadInstruction *instruction.
 		return fmt.Errorf("load result must be a temporary, got %T", result)
 	}
 
+	if variable, ok := source.(*nirvalue.Variable); ok && converter.valueConverter.IsImmutable(variable) {
+		boundValue, err := converter.valueConverter.GetImmutable(variable)
+		if err != nil {
+			return err
+		}
+		converter.valueConverter.RegisterTemporary(temporary, boundValue)
+		return nil
+	}
+
+	sourcePointer, err := converter.valueConverter.Convert(source)
+	if err != nil {
+		return fmt.Errorf("failed to convert load source: %w", err)
+	}
+
 	loadType, err := converter.typeConverter.Convert(temporary.Type())
 	if err != nil {
 		return fmt.Errorf("failed to convert load type: %w", err)
@@ -157,7 +208,7 @@ func (converter *InstructionConverter) ConvertBinary(binaryInstruction *instruct
 	case instruction.BinaryModulo:
 		llvmResult = converter.builder.CreateSRem(llvmLeft, llvmRight, "")
 	default:
-		return fmt.Errorf("unsupported binary operator: %v", operator)
+		return fmt.Errorf("unsupported binary operator '%s'", operator.Symbol())
 	}
 
 	converter.valueConverter.RegisterTemporary(temporary, llvmResult)
@@ -165,6 +216,282 @@ func (converter *InstructionConverter) ConvertBinary(binaryInstruction *instruct
 	return nil
 }
 
+func (converter *InstructionConverter) ConvertCompare(compareInstruction *instruction.CompareInstruction) error {
+	left := compareInstruction.GetLeft()
+	if left == nil {
+		return fmt.Errorf("compare instruction has no left operand")
+	}
+
+	llvmLeft, err := converter.valueConverter.Convert(left)
+	if err != nil {
+		return fmt.Errorf("failed to convert compare left operand: %w", err)
+	}
+
+	right := compareInstruction.GetRight()
+	if right == nil {
+		return fmt.Errorf("compare instruction has no right operand")
+	}
+
+	llvmRight, err := converter.valueConverter.Convert(right)
+	if err != nil {
+		return fmt.Errorf("failed to convert compare right operand: %w", err)
+	}
+
+	result := compareInstruction.GetResult()
+	if result == nil {
+		return fmt.Errorf("compare instruction has no result")
+	}
+
+	temporary, ok := result.(*nirvalue.Temporary)
+	if !ok {
+		return fmt.Errorf("compare result must be a temporary, got %T", result)
+	}
+
+	operator := compareInstruction.GetOperator()
+	var llvmResult llvm.Value
+	switch left.Type() {
+	case types.Int, types.Bool, types.Char:
+		// Bools only reach here for equality, and chars never exceed 0x10FFFF,
+		// so signed predicates order every operand correctly
+		predicate, err := intPredicate(operator)
+		if err != nil {
+			return err
+		}
+		llvmResult = converter.builder.CreateICmp(predicate, llvmLeft, llvmRight, "")
+	case types.Float:
+		predicate, err := floatPredicate(operator)
+		if err != nil {
+			return err
+		}
+		llvmResult = converter.builder.CreateFCmp(predicate, llvmLeft, llvmRight, "")
+	default:
+		return fmt.Errorf("unsupported compare operand type: %s", left.Type().String())
+	}
+
+	converter.valueConverter.RegisterTemporary(temporary, llvmResult)
+
+	return nil
+}
+
+func intPredicate(operator instruction.CompareOperator) (llvm.IntPredicate, error) {
+	switch operator {
+	case instruction.CompareEqual:
+		return llvm.IntEQ, nil
+	case instruction.CompareNotEqual:
+		return llvm.IntNE, nil
+	case instruction.CompareLess:
+		return llvm.IntSLT, nil
+	case instruction.CompareGreater:
+		return llvm.IntSGT, nil
+	case instruction.CompareLessEqual:
+		return llvm.IntSLE, nil
+	case instruction.CompareGreaterEqual:
+		return llvm.IntSGE, nil
+	default:
+		return 0, fmt.Errorf("unsupported compare operator '%s'", operator.Symbol())
+	}
+}
+
+func floatPredicate(operator instruction.CompareOperator) (llvm.FloatPredicate, error) {
+	switch operator {
+	case instruction.CompareEqual:
+		return llvm.FloatOEQ, nil
+	case instruction.CompareNotEqual:
+		return llvm.FloatONE, nil
+	case instruction.CompareLess:
+		return llvm.FloatOLT, nil
+	case instruction.CompareGreater:
+		return llvm.FloatOGT, nil
+	case instruction.CompareLessEqual:
+		return llvm.FloatOLE, nil
+	case instruction.CompareGreaterEqual:
+		return llvm.FloatOGE, nil
+	default:
+		return 0, fmt.Errorf("unsupported compare operator '%s'", operator.Symbol())
+	}
+}
+
+// ConvertMakeArray allocates the array in the runtime and stores each element in place
+// An int literal made only of constants is copied from a constant global in one call instead
+func (converter *InstructionConverter) ConvertMakeArray(makeArray *instruction.MakeArrayInstruction) error {
+	temporary, ok := makeArray.GetResult().(*nirvalue.Temporary)
+	if !ok {
+		return fmt.Errorf("array result must be a temporary, got %T", makeArray.GetResult())
+	}
+
+	array, ok, err := converter.convertConstantIntArray(temporary, makeArray.GetElements())
+	if err != nil {
+		return err
+	}
+	if ok {
+		converter.valueConverter.RegisterTemporary(temporary, array)
+		return nil
+	}
+
+	elementType, err := converter.typeConverter.Convert(temporary.Type().(*types.ArrayType).Element)
+	if err != nil {
+		return fmt.Errorf("failed to convert element type: %w", err)
+	}
+
+	context := converter.typeConverter.context.GetRawContext()
+	elements := makeArray.GetElements()
+	length := llvm.ConstInt(context.Int64Type(), uint64(len(elements)), false)
+
+	arrayNew := converter.runtimeFunction("naviary_array_new")
+	array = converter.builder.CreateCall(arrayNew.GlobalValueType(), arrayNew, []llvm.Value{length, llvm.SizeOf(elementType)}, "")
+
+	layout := converter.typeConverter.ArrayLayout(elementType)
+	for i, element := range elements {
+		llvmElement, err := converter.valueConverter.Convert(element)
+		if err != nil {
+			return fmt.Errorf("failed to convert array element %d: %w", i, err)
+		}
+
+		position := llvm.ConstInt(context.Int64Type(), uint64(i), false)
+		converter.builder.CreateStore(llvmElement, converter.elementPointer(layout, array, position))
+	}
+
+	converter.valueConverter.RegisterTemporary(temporary, array)
+	return nil
+}
+
+// convertConstantIntArray emits a non-empty int literal whose elements are all constants
+// as a private constant global passed to naviary_array_init_int
+// It reports false for any other literal, which is then built element by element
+func (converter *InstructionConverter) convertConstantIntArray(temporary *nirvalue.Temporary, elements []nirvalue.Value) (llvm.Value, bool, error) {
+	if temporary.Type().(*types.ArrayType).Element != types.Int || len(elements) == 0 {
+		return llvm.Value{}, false, nil
+	}
+
+	values := make([]llvm.Value, len(elements))
+	for i, element := range elements {
+		constant, ok := element.(*nirvalue.Constant)
+		if !ok {
+			return llvm.Value{}, false, nil
+		}
+
+		value, err := converter.valueConverter.Convert(constant)
+		if err != nil {
+			return llvm.Value{}, false, fmt.Errorf("failed to convert array element %d: %w", i, err)
+		}
+		values[i] = value
+	}
+
+	context := converter.typeConverter.context.GetRawContext()
+	initializer := llvm.ConstArray(context.Int64Type(), values)
+	module := converter.builder.GetInsertBlock().Parent().GlobalParent()
+	table := llvm.AddGlobal(module, initializer.Type(), ".array")
+	table.SetInitializer(initializer)
+	table.SetGlobalConstant(true)
+	table.SetLinkage(llvm.PrivateLinkage)
+	table.SetUnnamedAddr(true)
+
+	length := llvm.ConstInt(context.Int64Type(), uint64(len(elements)), false)
+	arrayInit := converter.runtimeFunction("naviary_array_init_int")
+	array := converter.builder.CreateCall(arrayInit.GlobalValueType(), arrayInit, []llvm.Value{table, length}, "")
+	return array, true, nil
+}
+
+// ConvertElement reads an array element after the runtime has checked the index
+func (converter *InstructionConverter) ConvertElement(element *instruction.ElementInstruction) error {
+	temporary, ok := element.GetResult().(*nirvalue.Temporary)
+	if !ok {
+		return fmt.Errorf("element result must be a temporary, got %T", element.GetResult())
+	}
+
+	array, err := converter.valueConverter.Convert(element.GetArray())
+	if err != nil {
+		return fmt.Errorf("failed to convert indexed array: %w", err)
+	}
+	index, err := converter.valueConverter.Convert(element.GetIndex())
+	if err != nil {
+		return fmt.Errorf("failed to convert array index: %w", err)
+	}
+
+	elementType, err := converter.typeConverter.Convert(temporary.Type())
+	if err != nil {
+		return fmt.Errorf("failed to convert element type: %w", err)
+	}
+
+	checkIndex := converter.runtimeFunction("naviary_array_check_index")
+	converter.builder.CreateCall(checkIndex.GlobalValueType(), checkIndex, []llvm.Value{array, index}, "")
+
+	layout := converter.typeConverter.ArrayLayout(elementType)
+	loaded := converter.builder.CreateLoad(elementType, converter.elementPointer(layout, array, index), "")
+
+	converter.valueConverter.RegisterTemporary(temporary, loaded)
+	return nil
+}
+
+// ConvertSetElement stores an array element after the runtime has checked the index
+func (converter *InstructionConverter) ConvertSetElement(setElement *instruction.SetElementInstruction) error {
+	array, err := converter.valueConverter.Convert(setElement.GetArray())
+	if err != nil {
+		return fmt.Errorf("failed to convert indexed array: %w", err)
+	}
+	index, err := converter.valueConverter.Convert(setElement.GetIndex())
+	if err != nil {
+		return fmt.Errorf("failed to convert array index: %w", err)
+	}
+	stored, err := converter.valueConverter.Convert(setElement.GetValue())
+	if err != nil {
+		return fmt.Errorf("failed to convert stored element: %w", err)
+	}
+
+	elementType, err := converter.typeConverter.Convert(setElement.GetArray().Type().(*types.ArrayType).Element)
+	if err != nil {
+		return fmt.Errorf("failed to convert element type: %w", err)
+	}
+
+	checkIndex := converter.runtimeFunction("naviary_array_check_index")
+	converter.builder.CreateCall(checkIndex.GlobalValueType(), checkIndex, []llvm.Value{array, index}, "")
+
+	layout := converter.typeConverter.ArrayLayout(elementType)
+	converter.builder.CreateStore(stored, converter.elementPointer(layout, array, index))
+	return nil
+}
+
+// elementPointer addresses element index of the array laid out as layout
+func (converter *InstructionConverter) elementPointer(layout llvm.Type, array llvm.Value, index llvm.Value) llvm.Value {
+	int32Type := converter.typeConverter.context.GetRawContext().Int32Type()
+	indices := []llvm.Value{
+		llvm.ConstInt(int32Type, 0, false), // the array itself
+		llvm.ConstInt(int32Type, 1, false), // its elements, after the length
+		index,
+	}
+
+	return converter.builder.CreateInBoundsGEP(layout, array, indices, "")
+}
+
+// runtimeFunction looks up a runtime function declared in the current module
+func (converter *InstructionConverter) runtimeFunction(name string) llvm.Value {
+	return converter.builder.GetInsertBlock().Parent().GlobalParent().NamedFunction(name)
+}
+
+// runtimeFunctionNames maps builtins to the runtime functions that implement them
+var runtimeFunctionNames = map[string]string{
+	"panic":     "naviary_panic",
+	"char_code": "naviary_char_code",
+	"char_from": "naviary_char_from",
+	"char_at":   "naviary_string_index",
+
+	"format_pad": "naviary_format_pad",
+	"to_hex":     "naviary_to_hex",
+	"to_string":  "naviary_float_to_string",
+
+	"repeat": "naviary_string_repeat",
+	"join":   "naviary_string_array_join",
+
+	"sum":    "naviary_array_sum_int",
+	"min_of": "naviary_array_min_int",
+	"max_of": "naviary_array_max_int",
+	"find":   "naviary_array_find_int",
+	"len":    "naviary_array_length",
+
+	"clear":    "naviary_array_clear",
+	"truncate": "naviary_array_truncate",
+}
+
 func (converter *InstructionConverter) ConvertCall(callInstruction *instruction.CallInstruction) error {
 	functionName := callInstruction.GetFunctionName()
 	if functionName == "" {
@@ -175,7 +502,7 @@ func (converter *InstructionConverter) ConvertCall(callInstruction *instruction.
 
 	if functionName == "print" {
 		if len(arguments) != 1 {
-			return fmt.Errorf("print function expects 1 argument for now. got %d", len(arguments))
+			return converter.convertPrintValues(arguments)
 		}
 		argument := arguments[0]
 		argumentType := argument.Type()
@@ -184,11 +511,21 @@ func (converter *InstructionConverter) ConvertCall(callInstruction *instruction.
 			functionName = "print_int"
 		} else if argumentType == types.String {
 			functionName = "print_string"
+		} else if argumentType == types.Bool {
+			functionName = "print_bool"
+		} else if argumentType == types.Char {
+			functionName = "print_char"
+		} else if argumentType == types.Float {
+			functionName = "print_float"
 		} else {
 			return fmt.Errorf("print function does not support type: %s", argumentType.String())
 		}
 	}
 
+	if runtimeName, ok := runtimeFunctionNames[functionName]; ok {
+		functionName = runtimeName
+	}
+
 	llvmArguments := make([]llvm.Value, len(arguments))
 	for i, arg := range arguments {
 		llvmArg, err := converter.valueConverter.Convert(arg)
@@ -216,6 +553,7 @@ func (converter *InstructionConverter) ConvertCall(callInstruction *instruction.
 		}
 
 		llvmResult := converter.builder.CreateCall(functionType, calleeFunction, llvmArguments, "")
+		llvmResult.SetTailCall(converter.options.TailCalls && callInstruction.IsTail())
 		converter.valueConverter.RegisterTemporary(temporary, llvmResult)
 	} else {
 		converter.builder.CreateCall(functionType, calleeFunction, llvmArguments, "")
@@ -224,6 +562,86 @@ func (converter *InstructionConverter) ConvertCall(callInstruction *instruction.
 	return nil
 }
 
+// convertPrintValues prints any number of values, separated by spaces, with one runtime call
+// The descriptor string holds one letter per value (i int, f float, s string, b bool, c char)
+// so the runtime knows how to read each vararg; bools are widened to i32
+// because C varargs promote anything narrower than int
+// The arguments are values NIR lowering has already computed, so converting one
+// again here never repeats its side effects
+func (converter *InstructionConverter) convertPrintValues(arguments []nirvalue.Value) error {
+	descriptor := make([]byte, len(arguments))
+	llvmArguments := make([]llvm.Value, len(arguments)+1)
+
+	for i, argument := range arguments {
+		llvmArgument, err := converter.valueConverter.Convert(argument)
+		if err != nil {
+			return fmt.Errorf("failed to convert print argument %d: %w", i, err)
+		}
+
+		switch argument.Type() {
+		case types.Int:
+			descriptor[i] = 'i'
+		case types.Float:
+			descriptor[i] = 'f'
+		case types.String:
+			descriptor[i] = 's'
+		case types.Bool:
+			descriptor[i] = 'b'
+			llvmArgument = converter.builder.CreateZExt(llvmArgument, llvmArgument.Type().Context().Int32Type(), "")
+		case types.Char:
+			descriptor[i] = 'c'
+		default:
+			return fmt.Errorf("print function does not support type: %s", argument.Type().String())
+		}
+
+		llvmArguments[i+1] = llvmArgument
+	}
+
+	llvmArguments[0] = converter.builder.CreateGlobalStringPtr(string(descriptor), "print_descriptor")
+
+	module := converter.builder.GetInsertBlock().Parent().GlobalParent()
+	printValues := module.NamedFunction("print_values")
+	if printValues.IsNil() {
+		return fmt.Errorf("function print_values not found in module")
+	}
+
+	converter.builder.CreateCall(printValues.GlobalValueType(), printValues, llvmArguments, "")
+	return nil
+}
+
+func (converter *InstructionConverter) ConvertUnreachable() {
+	converter.builder.CreateUnreachable()
+}
+
+func (converter *InstructionConverter) ConvertBranch(branch *instruction.BranchInstruction) error {
+	target, ok := converter.blocks[branch.GetTarget()]
+	if !ok {
+		return fmt.Errorf("branch to unknown block %s", branch.GetTarget())
+	}
+
+	converter.builder.CreateBr(target)
+	return nil
+}
+
+func (converter *InstructionConverter) ConvertConditionalBranch(branch *instruction.ConditionalBranchInstruction) error {
+	condition, err := converter.valueConverter.Convert(branch.GetCondition())
+	if err != nil {
+		return fmt.Errorf("failed to convert branch condition: %w", err)
+	}
+
+	trueTarget, ok := converter.blocks[branch.GetTrueTarget()]
+	if !ok {
+		return fmt.Errorf("branch to unknown block %s", branch.GetTrueTarget())
+	}
+	falseTarget, ok := converter.blocks[branch.GetFalseTarget()]
+	if !ok {
+		return fmt.Errorf("branch to unknown block %s", branch.GetFalseTarget())
+	}
+
+	converter.builder.CreateCondBr(condition, trueTarget, falseTarget)
+	return nil
+}
+
 func (converter *InstructionConverter) ConvertReturn(returnInstruction *instruction.ReturnInstruction) error {
 	returnValue := returnInstruction.GetValue()
 
@@ -4,6 +4,7 @@ import (
 	llvmvalue "compiler/codegen/llvm/value"
 	"compiler/nir"
 	"fmt"
+	"strings"
 
 	"tinygo.org/x/go-llvm"
 )
@@ -16,12 +17,12 @@ type ModuleConverter struct {
 	functionConverter *FunctionConverter
 }
 
-func NewModuleConverter(context *Context, moduleName string) *ModuleConverter {
+func NewModuleConverter(context *Context, moduleName string, options Options) *ModuleConverter {
 	module := context.GetRawContext().NewModule(moduleName)
 
 	typeConverter := NewTypeConverter(context)
-	valueConverter := llvmvalue.NewConverter(typeConverter)
-	functionConverter := NewFunctionConverter(context, module, typeConverter, valueConverter)
+	valueConverter := llvmvalue.NewConverter(typeConverter, module)
+	functionConverter := NewFunctionConverter(context, module, typeConverter, valueConverter, options)
 
 	return &ModuleConverter{
 		context:           context,
@@ -38,13 +39,18 @@ func (converter *ModuleConverter) Convert(nirModule *nir.Module) (string, error)
 		return "", fmt.Errorf("failed to declare runtime functions: %w", err)
 	}
 
+	err = converter.convertGlobals(nirModule.Globals)
+	if err != nil {
+		return "", fmt.Errorf("failed to convert globals: %w", err)
+	}
+
 	err = converter.convertFunctions(nirModule.Functions)
 	if err != nil {
 		return "", fmt.Errorf("failed to convert functions: %w", err)
 	}
 
 	if err := llvm.VerifyModule(converter.module, llvm.ReturnStatusAction); err != nil {
-		return "", fmt.Errorf("failed to verify module: %w", err)
+		return "", converter.newVerificationError(nirModule, err)
 	}
 
 	llvmIR := converter.module.String()
@@ -52,6 +58,24 @@ func (converter *ModuleConverter) Convert(nirModule *nir.Module) (string, error)
 	return llvmIR, nil
 }
 
+// newVerificationError captures the unverified IR along with the function the verifier complained about
+func (converter *ModuleConverter) newVerificationError(nirModule *nir.Module, err error) *VerificationError {
+	verificationError := &VerificationError{
+		Message: err.Error(),
+		IR:      converter.module.String(),
+	}
+
+	for _, nirFunction := range nirModule.Functions {
+		if strings.Contains(verificationError.Message, "@"+nirFunction.Name) {
+			verificationError.Function = nirFunction.Name
+			verificationError.FunctionIR = converter.module.NamedFunction(nirFunction.Name).String()
+			break
+		}
+	}
+
+	return verificationError
+}
+
 func (converter *ModuleConverter) declareRuntimeFunctions() error {
 	context := converter.context.GetRawContext()
 
@@ -63,10 +87,145 @@ func (converter *ModuleConverter) declareRuntimeFunctions() error {
 	printStringFuncType := llvm.FunctionType(context.VoidType(), printStringParamTypes, false)
 	llvm.AddFunction(converter.module, "print_string", printStringFuncType)
 
+	printBoolParamTypes := []llvm.Type{context.Int1Type()}
+	printBoolFuncType := llvm.FunctionType(context.VoidType(), printBoolParamTypes, false)
+	llvm.AddFunction(converter.module, "print_bool", printBoolFuncType)
+
+	printFloatParamTypes := []llvm.Type{context.DoubleType()}
+	printFloatFuncType := llvm.FunctionType(context.VoidType(), printFloatParamTypes, false)
+	llvm.AddFunction(converter.module, "print_float", printFloatFuncType)
+
+	printCharParamTypes := []llvm.Type{context.Int32Type()}
+	printCharFuncType := llvm.FunctionType(context.VoidType(), printCharParamTypes, false)
+	llvm.AddFunction(converter.module, "print_char", printCharFuncType)
+
+	// print_values(descriptor, ...) prints several values on one line; see convertPrintValues
+	printValuesParamTypes := []llvm.Type{llvm.PointerType(context.Int8Type(), 0)}
+	printValuesFuncType := llvm.FunctionType(context.VoidType(), printValuesParamTypes, true)
+	llvm.AddFunction(converter.module, "print_values", printValuesFuncType)
+
+	charCodeFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{context.Int32Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_char_code", charCodeFuncType)
+
+	charFromFuncType := llvm.FunctionType(context.Int32Type(), []llvm.Type{context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_char_from", charFromFuncType)
+
+	stringType := llvm.PointerType(context.Int8Type(), 0)
+
+	formatPadFuncType := llvm.FunctionType(stringType, []llvm.Type{context.Int64Type(), context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_format_pad", formatPadFuncType)
+
+	toHexFuncType := llvm.FunctionType(stringType, []llvm.Type{context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_to_hex", toHexFuncType)
+
+	floatToStringFuncType := llvm.FunctionType(stringType, []llvm.Type{context.DoubleType()}, false)
+	llvm.AddFunction(converter.module, "naviary_float_to_string", floatToStringFuncType)
+
+	// naviary_string_length(string) bounds a for loop over a string; see Lowerer.lowerForStatement
+	stringLengthFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{stringType}, false)
+	llvm.AddFunction(converter.module, "naviary_string_length", stringLengthFuncType)
+
+	stringIndexFuncType := llvm.FunctionType(context.Int32Type(), []llvm.Type{stringType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_string_index", stringIndexFuncType)
+
+	// A for loop over a string decodes a character and skips its width at each step
+	decodeCharFuncType := llvm.FunctionType(context.Int32Type(), []llvm.Type{stringType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_string_decode_char", decodeCharFuncType)
+
+	charWidthFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{stringType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_string_char_width", charWidthFuncType)
+
+	repeatFuncType := llvm.FunctionType(stringType, []llvm.Type{stringType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_string_repeat", repeatFuncType)
+
+	// naviary_array_new(length, element_size) allocates an array; see InstructionConverter.ConvertMakeArray
+	arrayType := llvm.PointerType(context.Int8Type(), 0)
+	arrayNewFuncType := llvm.FunctionType(arrayType, []llvm.Type{context.Int64Type(), context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_array_new", arrayNewFuncType)
+
+	// naviary_array_init_int(values, count) copies a constant int literal into a new array
+	arrayInitIntFuncType := llvm.FunctionType(arrayType, []llvm.Type{llvm.PointerType(context.Int64Type(), 0), context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_array_init_int", arrayInitIntFuncType)
+
+	arrayCheckIndexFuncType := llvm.FunctionType(context.VoidType(), []llvm.Type{arrayType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_array_check_index", arrayCheckIndexFuncType)
+
+	arrayReduceFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{arrayType}, false)
+	for _, name := range []string{"naviary_array_sum_int", "naviary_array_min_int", "naviary_array_max_int"} {
+		llvm.AddFunction(converter.module, name, arrayReduceFuncType)
+	}
+
+	arrayReduceFloatFuncType := llvm.FunctionType(context.DoubleType(), []llvm.Type{arrayType}, false)
+	for _, name := range []string{"naviary_array_sum_float", "naviary_array_min_float", "naviary_array_max_float"} {
+		llvm.AddFunction(converter.module, name, arrayReduceFloatFuncType)
+	}
+
+	joinFuncType := llvm.FunctionType(stringType, []llvm.Type{arrayType, stringType}, false)
+	llvm.AddFunction(converter.module, "naviary_string_array_join", joinFuncType)
+
+	arrayLengthFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{arrayType}, false)
+	llvm.AddFunction(converter.module, "naviary_array_length", arrayLengthFuncType)
+
+	arrayClearFuncType := llvm.FunctionType(context.VoidType(), []llvm.Type{arrayType}, false)
+	llvm.AddFunction(converter.module, "naviary_array_clear", arrayClearFuncType)
+
+	arrayTruncateFuncType := llvm.FunctionType(context.VoidType(), []llvm.Type{arrayType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_array_truncate", arrayTruncateFuncType)
+
+	arrayFindFuncType := llvm.FunctionType(context.Int64Type(), []llvm.Type{arrayType, context.Int64Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_array_find_int", arrayFindFuncType)
+
+	arrayContainsStringFuncType := llvm.FunctionType(context.Int1Type(), []llvm.Type{arrayType, stringType}, false)
+	llvm.AddFunction(converter.module, "naviary_array_contains_string", arrayContainsStringFuncType)
+
+	// naviary_runtime_check_abi(version) is called first thing in main; see FunctionConverter.emitAbiCheck
+	checkAbiFuncType := llvm.FunctionType(context.VoidType(), []llvm.Type{context.Int32Type()}, false)
+	llvm.AddFunction(converter.module, "naviary_runtime_check_abi", checkAbiFuncType)
+
+	panicParamTypes := []llvm.Type{llvm.PointerType(context.Int8Type(), 0)}
+	panicFuncType := llvm.FunctionType(context.VoidType(), panicParamTypes, false)
+	panicFunction := llvm.AddFunction(converter.module, "naviary_panic", panicFuncType)
+	noReturn := context.CreateEnumAttribute(llvm.AttributeKindID("noreturn"), 0)
+	panicFunction.AddFunctionAttr(noReturn)
+
+	return nil
+}
+
+func (converter *ModuleConverter) convertGlobals(nirGlobals []*nir.Global) error {
+	for _, nirGlobal := range nirGlobals {
+		llvmType, err := converter.typeConverter.Convert(nirGlobal.Variable.Type())
+		if err != nil {
+			return fmt.Errorf("failed to convert global %s type: %w", nirGlobal.Variable.Name(), err)
+		}
+
+		llvmGlobal := llvm.AddGlobal(converter.module, llvmType, nirGlobal.Variable.Name())
+
+		if nirGlobal.Initializer == nil {
+			// Zero until main stores the real value
+			llvmGlobal.SetInitializer(llvm.ConstNull(llvmType))
+		} else {
+			initializer, err := converter.valueConverter.Convert(nirGlobal.Initializer)
+			if err != nil {
+				return fmt.Errorf("failed to convert global %s initializer: %w", nirGlobal.Variable.Name(), err)
+			}
+			llvmGlobal.SetInitializer(initializer)
+			llvmGlobal.SetGlobalConstant(!nirGlobal.Mutable)
+		}
+
+		converter.valueConverter.RegisterGlobal(nirGlobal.Variable, llvmGlobal)
+	}
+
 	return nil
 }
 
 func (converter *ModuleConverter) convertFunctions(nirFunctions []*nir.Function) error {
+	for _, nirFunction := range nirFunctions {
+		err := converter.functionConverter.Declare(nirFunction)
+		if err != nil {
+			return fmt.Errorf("failed to declare function %s: %w", nirFunction.Name, err)
+		}
+	}
+
 	for _, nirFunction := range nirFunctions {
 		err := converter.functionConverter.Convert(nirFunction)
 		if err != nil {
@@ -0,0 +1,9 @@
+define ptr @pair(i64 %x) {
+entry:
+  %_ = call ptr @naviary_array_new(i64 2, i64 ptrtoint (ptr getelementptr (i64, ptr null, i32 1) to i64))
+  %_ = getelementptr inbounds { i64, [0 x i64] }, ptr %_, i32 0, i32 1, i64 0
+  store i64 %x, ptr %_
+  %_ = getelementptr inbounds { i64, [0 x i64] }, ptr %_, i32 0, i32 1, i64 1
+  store i64 2, ptr %_
+  ret ptr %_
+}
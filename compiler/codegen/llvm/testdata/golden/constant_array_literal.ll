@@ -0,0 +1,8 @@
+define i64 @second() {
+entry:
+  %_ = call ptr @naviary_array_init_int(ptr @.array, i64 3)
+  call void @naviary_array_check_index(ptr %_, i64 1)
+  %_ = getelementptr inbounds { i64, [0 x i64] }, ptr %_, i32 0, i32 1, i64 1
+  %_ = load i64, ptr %_
+  ret i64 %_
+}
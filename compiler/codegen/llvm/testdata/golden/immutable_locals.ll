@@ -0,0 +1,6 @@
+define i64 @scale(i64 %x, i64 %factor) {
+entry:
+  %_ = mul i64 %x, %factor
+  %_ = add i64 %_, 1
+  ret i64 %_
+}
@@ -0,0 +1,6 @@
+define i64 @main() {
+entry:
+  call void @naviary_runtime_check_abi(i32 11)
+  call void @print_int(i64 1)
+  ret i64 0
+}
@@ -0,0 +1,27 @@
+define i64 @total(i64 %n) {
+entry:
+  %sum = alloca i64
+  %i = alloca i64
+  store i64 0, ptr %sum
+  store i64 0, ptr %i
+  br label %for.cond.0
+
+for.cond.0:                                       ; preds = %for.body.0, %entry
+  %_ = load i64, ptr %i
+  %_ = icmp slt i64 %_, %n
+  br i1 %_, label %for.body.0, label %for.end.0
+
+for.body.0:                                       ; preds = %for.cond.0
+  %_ = load i64, ptr %sum
+  %_ = load i64, ptr %i
+  %_ = add i64 %_, %_
+  store i64 %_, ptr %sum
+  %_ = load i64, ptr %i
+  %_ = add i64 %_, 1
+  store i64 %_, ptr %i
+  br label %for.cond.0
+
+for.end.0:                                        ; preds = %for.cond.0
+  %_ = load i64, ptr %sum
+  ret i64 %_
+}
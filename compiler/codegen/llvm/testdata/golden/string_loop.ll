@@ -0,0 +1,28 @@
+define void @spell(ptr %s) {
+entry:
+  %ch.index = alloca i64
+  %ch = alloca i32
+  %_ = call i64 @naviary_string_length(ptr %s)
+  store i64 0, ptr %ch.index
+  br label %for.cond.0
+
+for.cond.0:                                       ; preds = %for.body.0, %entry
+  %_ = load i64, ptr %ch.index
+  %_ = icmp slt i64 %_, %_
+  br i1 %_, label %for.body.0, label %for.end.0
+
+for.body.0:                                       ; preds = %for.cond.0
+  %_ = load i64, ptr %ch.index
+  %_ = call i32 @naviary_string_decode_char(ptr %s, i64 %_)
+  store i32 %_, ptr %ch
+  %_ = call i64 @naviary_string_char_width(ptr %s, i64 %_)
+  %_ = load i32, ptr %ch
+  call void @print_char(i32 %_)
+  %_ = load i64, ptr %ch.index
+  %_ = add i64 %_, %_
+  store i64 %_, ptr %ch.index
+  br label %for.cond.0
+
+for.end.0:                                        ; preds = %for.cond.0
+  ret void
+}
@@ -29,11 +29,20 @@ func (converter *TypeConverter) Convert(naviaryType types.Type) (llvm.Type, erro
 		return converter.convertNilType(t)
 	case *types.FunctionType:
 		return converter.convertFunctionType(t)
+	case *types.ArrayType:
+		// A pointer to the runtime allocation: an i64 length followed by the elements
+		return llvm.PointerType(converter.context.GetRawContext().Int8Type(), 0), nil
 	default:
 		return llvm.Type{}, fmt.Errorf("unsupported type: %s", naviaryType.String())
 	}
 }
 
+// ArrayLayout is the layout an array pointer points to, for addressing elements of elementType
+func (converter *TypeConverter) ArrayLayout(elementType llvm.Type) llvm.Type {
+	context := converter.context.GetRawContext()
+	return context.StructType([]llvm.Type{context.Int64Type(), llvm.ArrayType(elementType, 0)}, false)
+}
+
 func (converter *TypeConverter) convertPrimitiveType(primitiveType *types.PrimitiveType) (llvm.Type, error) {
 	context := converter.context.GetRawContext()
 	switch primitiveType.Name {
@@ -45,6 +54,9 @@ func (converter *TypeConverter) convertPrimitiveType(primitiveType *types.Primit
 		return llvm.PointerType(context.Int8Type(), 0), nil
 	case "bool":
 		return context.Int1Type(), nil
+	case "char":
+		// A Unicode scalar value
+		return context.Int32Type(), nil
 	default:
 		return llvm.Type{}, fmt.Errorf("unknown primitive type: %s", primitiveType.Name)
 	}
@@ -14,11 +14,16 @@ type TypeConverter interface {
 
 type ConstantConverter struct {
 	typeConverter TypeConverter
+	module        llvm.Module
+	// stringLiterals holds one module-level global per distinct literal
+	stringLiterals map[string]llvm.Value
 }
 
-func NewConstantConverter(typeConverter TypeConverter) *ConstantConverter {
+func NewConstantConverter(typeConverter TypeConverter, module llvm.Module) *ConstantConverter {
 	return &ConstantConverter{
-		typeConverter: typeConverter,
+		typeConverter:  typeConverter,
+		module:         module,
+		stringLiterals: map[string]llvm.Value{},
 	}
 }
 
@@ -36,6 +41,8 @@ func (converter *ConstantConverter) Convert(naviaryConstant *nirvalue.Constant)
 		return converter.convertFloatConstant(naviaryConstant)
 	case types.Bool:
 		return converter.convertBoolConstant(naviaryConstant)
+	case types.Char:
+		return converter.convertCharConstant(naviaryConstant)
 	default:
 		return llvm.Value{}, fmt.Errorf("unsupported constant type: %s", naviaryConstant.Type().String())
 
@@ -76,27 +83,43 @@ func (converter *ConstantConverter) convertFloatConstant(naviaryConstant *nirval
 	return llvm.ConstFloat(llvmType, value), nil
 }
 
+// convertStringConstant returns a pointer to the literal's module-level global
+// Each distinct literal is emitted once, however many times it is used,
+// so executing a literal never allocates
 func (converter *ConstantConverter) convertStringConstant(naviaryConstant *nirvalue.Constant) (llvm.Value, error) {
-	constantString := naviaryConstant.String()
+	value, ok := naviaryConstant.Value().(string)
+	if !ok {
+		return llvm.Value{}, fmt.Errorf("string constant holds %T", naviaryConstant.Value())
+	}
 
-	var value string
-	_, err := fmt.Sscanf(constantString, "Constant(\"%s\")", &value)
-	if err != nil {
-		value = converter.extractStringValue(constantString)
+	if literal, ok := converter.stringLiterals[value]; ok {
+		return literal, nil
 	}
 
-	return llvm.ConstString(value, false), nil
+	initializer := converter.module.Context().ConstString(value, false)
+	literal := llvm.AddGlobal(converter.module, initializer.Type(), fmt.Sprintf(".str.%d", len(converter.stringLiterals)))
+	literal.SetInitializer(initializer)
+	literal.SetGlobalConstant(true)
+	literal.SetLinkage(llvm.PrivateLinkage)
+	literal.SetUnnamedAddr(true)
+
+	converter.stringLiterals[value] = literal
+	return literal, nil
 }
 
-func (converter *ConstantConverter) extractStringValue(constantString string) string {
-	startIndex := len("Constant(\"")
-	endIndex := len(constantString) - 2 // Remove "))
+func (converter *ConstantConverter) convertCharConstant(naviaryConstant *nirvalue.Constant) (llvm.Value, error) {
+	llvmType, err := converter.typeConverter.Convert(types.Char)
 
-	if startIndex >= len(constantString) || endIndex <= startIndex {
-		return ""
+	if err != nil {
+		return llvm.Value{}, fmt.Errorf("failed to convert char type: %w", err)
+	}
+
+	value, ok := naviaryConstant.Value().(rune)
+	if !ok {
+		return llvm.Value{}, fmt.Errorf("failed to parse char constant: %s", naviaryConstant.String())
 	}
 
-	return constantString[startIndex:endIndex]
+	return llvm.ConstInt(llvmType, uint64(value), false), nil
 }
 
 func (converter *ConstantConverter) convertBoolConstant(naviaryConstant *nirvalue.Constant) (llvm.Value, error) {
@@ -12,9 +12,9 @@ type Converter struct {
 	registry          *Registry
 }
 
-func NewConverter(typeConverter TypeConverter) *Converter {
+func NewConverter(typeConverter TypeConverter, module llvm.Module) *Converter {
 	return &Converter{
-		constantConverter: NewConstantConverter(typeConverter),
+		constantConverter: NewConstantConverter(typeConverter, module),
 		registry:          NewRegistry(),
 	}
 }
@@ -35,6 +35,30 @@ func (converter *Converter) GetVariable(variable *nirvalue.Variable) (llvm.Value
 	return converter.registry.GetVariable(variable)
 }
 
+func (converter *Converter) RegisterImmutable(variable *nirvalue.Variable) {
+	converter.registry.RegisterImmutable(variable)
+}
+
+func (converter *Converter) IsImmutable(variable *nirvalue.Variable) bool {
+	return converter.registry.IsImmutable(variable)
+}
+
+func (converter *Converter) BindImmutable(variable *nirvalue.Variable, llvmValue llvm.Value) {
+	converter.registry.BindImmutable(variable, llvmValue)
+}
+
+func (converter *Converter) GetImmutable(variable *nirvalue.Variable) (llvm.Value, error) {
+	return converter.registry.GetImmutable(variable)
+}
+
+func (converter *Converter) RegisterGlobal(global *nirvalue.Global, llvmValue llvm.Value) {
+	converter.registry.RegisterGlobal(global, llvmValue)
+}
+
+func (converter *Converter) GetGlobal(global *nirvalue.Global) (llvm.Value, error) {
+	return converter.registry.GetGlobal(global)
+}
+
 func (converter *Converter) Convert(nirVal nirvalue.Value) (llvm.Value, error) {
 	if nirVal == nil {
 		return llvm.Value{}, fmt.Errorf("cannot convert nil value")
@@ -47,6 +71,8 @@ func (converter *Converter) Convert(nirVal nirvalue.Value) (llvm.Value, error) {
 		return converter.GetTemporary(val)
 	case *nirvalue.Variable:
 		return converter.GetVariable(val)
+	case *nirvalue.Global:
+		return converter.GetGlobal(val)
 	default:
 		return llvm.Value{}, fmt.Errorf("unsupported NIR value type: %T", nirVal)
 	}
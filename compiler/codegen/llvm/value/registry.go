@@ -9,13 +9,17 @@ import (
 
 type Registry struct {
 	temporaryMap map[int]llvm.Value
-	variableMap  map[string]llvm.Value
+	variableMap  map[string]llvm.Value // stack slots of mutable variables
+	immutableMap map[string]llvm.Value // values of immutable variables; nil until bound
+	globalMap    map[string]llvm.Value // module scoped, kept across Reset
 }
 
 func NewRegistry() *Registry {
 	return &Registry{
 		temporaryMap: make(map[int]llvm.Value),
 		variableMap:  make(map[string]llvm.Value),
+		immutableMap: make(map[string]llvm.Value),
+		globalMap:    make(map[string]llvm.Value),
 	}
 }
 
@@ -33,7 +37,10 @@ func (registry *Registry) GetTemporary(naviaryTemporary *nirvalue.Temporary) (ll
 	return llvmValue, nil
 }
 
+// RegisterVariable records the stack slot of a mutable variable
+// A name declared again in a later scope, such as a second loop, replaces the earlier variable
 func (registry *Registry) RegisterVariable(naviaryVariable *nirvalue.Variable, llvmValue llvm.Value) {
+	delete(registry.immutableMap, naviaryVariable.String())
 	registry.variableMap[naviaryVariable.String()] = llvmValue
 }
 
@@ -47,7 +54,48 @@ func (registry *Registry) GetVariable(naviaryVariable *nirvalue.Variable) (llvm.
 	return llvmValue, nil
 }
 
+// RegisterImmutable marks a variable as bound to a value rather than stored in memory
+func (registry *Registry) RegisterImmutable(naviaryVariable *nirvalue.Variable) {
+	delete(registry.variableMap, naviaryVariable.String())
+	registry.immutableMap[naviaryVariable.String()] = llvm.Value{}
+}
+
+func (registry *Registry) IsImmutable(naviaryVariable *nirvalue.Variable) bool {
+	_, exists := registry.immutableMap[naviaryVariable.String()]
+	return exists
+}
+
+// BindImmutable sets the value an immutable variable stands for
+func (registry *Registry) BindImmutable(naviaryVariable *nirvalue.Variable, llvmValue llvm.Value) {
+	registry.immutableMap[naviaryVariable.String()] = llvmValue
+}
+
+func (registry *Registry) GetImmutable(naviaryVariable *nirvalue.Variable) (llvm.Value, error) {
+	llvmValue, exists := registry.immutableMap[naviaryVariable.String()]
+
+	if !exists || llvmValue.IsNil() {
+		return llvm.Value{}, fmt.Errorf("immutable variable %s is not bound", naviaryVariable.String())
+	}
+
+	return llvmValue, nil
+}
+
+func (registry *Registry) RegisterGlobal(naviaryGlobal *nirvalue.Global, llvmValue llvm.Value) {
+	registry.globalMap[naviaryGlobal.Name()] = llvmValue
+}
+
+func (registry *Registry) GetGlobal(naviaryGlobal *nirvalue.Global) (llvm.Value, error) {
+	llvmValue, exists := registry.globalMap[naviaryGlobal.Name()]
+
+	if !exists {
+		return llvm.Value{}, fmt.Errorf("global %s not found in registry", naviaryGlobal.String())
+	}
+
+	return llvmValue, nil
+}
+
 func (registry *Registry) Reset() {
 	registry.temporaryMap = make(map[int]llvm.Value)
 	registry.variableMap = make(map[string]llvm.Value)
+	registry.immutableMap = make(map[string]llvm.Value)
 }
@@ -0,0 +1,41 @@
+package main
+
+import (
+	"compiler/completion"
+	"compiler/lexer"
+	"compiler/parser"
+	"compiler/prelude"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+)
+
+// CompleteAt prints the variables and functions in scope at a file:line:column location as JSON
+// Syntax and type errors are not reported: the file is usually being edited
+func CompleteAt(locationText string, options CompileOptions, writer io.Writer) error {
+	location, err := completion.ParseLocation(locationText)
+	if err != nil {
+		return err
+	}
+
+	sourceCode, err := os.ReadFile(location.Path)
+	if err != nil {
+		return fmt.Errorf("failed to read file %s: %v", location.Path, err)
+	}
+
+	fileName := filepath.Base(location.Path)
+	errorCollector := options.newErrorCollector(string(sourceCode), fileName)
+	program := parser.New(lexer.New(string(sourceCode), fileName, errorCollector), errorCollector).ParseProgram()
+	if !options.NoPrelude {
+		program = prelude.Merge(program, errorCollector)
+	}
+
+	encoded, err := json.Marshal(completion.Complete(program, location.Line, location.Column))
+	if err != nil {
+		return fmt.Errorf("failed to encode completions: %w", err)
+	}
+	fmt.Fprintln(writer, string(encoded))
+	return nil
+}
@@ -0,0 +1,160 @@
+package completion
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/nir"
+	"compiler/token"
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// Location is a cursor position in a source file; lines and columns start at 1
+type Location struct {
+	Path   string
+	Line   int
+	Column int
+}
+
+// ParseLocation reads a location written as path:line:column
+func ParseLocation(text string) (Location, error) {
+	parts := strings.Split(text, ":")
+	if len(parts) < 3 {
+		return Location{}, fmt.Errorf("expected <file>:<line>:<column>, got '%s'", text)
+	}
+
+	line, lineErr := strconv.Atoi(parts[len(parts)-2])
+	column, columnErr := strconv.Atoi(parts[len(parts)-1])
+	if lineErr != nil || columnErr != nil || line < 1 || column < 1 {
+		return Location{}, fmt.Errorf("expected a positive line and column, got '%s'", text)
+	}
+
+	return Location{
+		Path:   strings.Join(parts[:len(parts)-2], ":"),
+		Line:   line,
+		Column: column,
+	}, nil
+}
+
+// Variable is an identifier in scope at the cursor
+type Variable struct {
+	Name string `json:"name"`
+	Type string `json:"type"`
+}
+
+// Result lists what may be referred to at the cursor
+type Result struct {
+	Variables []Variable `json:"variables"` // sorted by name
+	Functions []string   `json:"functions"` // user functions in declaration order, then builtins
+}
+
+// Complete returns the variables and functions visible at line:column of program
+// Only declarations that end before the cursor are in scope
+func Complete(program *ast.Program, line int, column int) Result {
+	cursor := token.Token{Line: line, Column: column}
+
+	var enclosing *ast.FunctionStatement
+	others := []ast.Statement{}
+	functions := []string{}
+
+	for _, statement := range program.Statements {
+		function, ok := statement.(*ast.FunctionStatement)
+		if !ok {
+			continue
+		}
+		functions = append(functions, signature(function))
+
+		if !function.Prelude && function.Body != nil && before(function.Body.Token, cursor) && !before(function.Body.End, cursor) {
+			enclosing = function
+		} else {
+			others = append(others, function)
+		}
+	}
+
+	// The lowerer's scope after the last function is the scope at the cursor,
+	// so the enclosing function goes last, cut off at the cursor
+	// Outside any function only the globals are lowered
+	scope := &ast.Program{Globals: program.Globals}
+	if enclosing != nil {
+		scope.Statements = append(others, truncate(enclosing, cursor))
+	}
+
+	// Code being edited is often incomplete; completion works with whatever lowers
+	lowerer := nir.NewLowerer(errors.New("", ""))
+	lowerer.Lower(scope)
+	visible := lowerer.VisibleVariables()
+
+	variables := make([]Variable, 0, len(visible))
+	for name, variableType := range visible {
+		variables = append(variables, Variable{Name: name, Type: variableType.String()})
+	}
+	sort.Slice(variables, func(i, j int) bool { return variables[i].Name < variables[j].Name })
+
+	return Result{
+		Variables: variables,
+		Functions: append(functions, nir.BuiltinSignatures()...),
+	}
+}
+
+// truncate returns a copy of function keeping only the statements whose declarations are in scope at the cursor
+// A statement is complete once the cursor is on a later line or a following statement has started
+func truncate(function *ast.FunctionStatement, cursor token.Token) *ast.FunctionStatement {
+	statements := function.Body.Statements
+	kept := []ast.Statement{}
+
+	for i, statement := range statements {
+		start := startOf(statement)
+		endsBeforeCursor := start.Line < cursor.Line ||
+			(i+1 < len(statements) && before(startOf(statements[i+1]), cursor))
+		if !endsBeforeCursor {
+			break
+		}
+		kept = append(kept, statement)
+	}
+
+	body := *function.Body
+	body.Statements = kept
+	truncated := *function
+	truncated.Body = &body
+	return &truncated
+}
+
+// signature renders a user function the way it is declared
+func signature(function *ast.FunctionStatement) string {
+	parameters := make([]string, len(function.Parameters))
+	for i, parameter := range function.Parameters {
+		parameters[i] = parameter.Name.Value + ": " + parameter.Type.Value
+	}
+
+	result := fmt.Sprintf("%s(%s)", function.Name.Value, strings.Join(parameters, ", "))
+	if function.ReturnType != nil {
+		result += " -> " + function.ReturnType.Value
+	}
+	return result
+}
+
+func startOf(statement ast.Statement) token.Token {
+	switch statement := statement.(type) {
+	case *ast.LetStatement:
+		return statement.Token
+	case *ast.ReturnStatement:
+		return statement.Token
+	case *ast.AssignmentStatement:
+		return statement.Name.Token
+	case *ast.IndexAssignmentStatement:
+		return statement.Target.Token // its '[' is on the line the statement starts
+	case *ast.ForStatement:
+		return statement.Token
+	case *ast.ExpressionStatement:
+		return statement.Token
+	default:
+		return token.Token{}
+	}
+}
+
+// before reports whether position comes strictly before other
+func before(position token.Token, other token.Token) bool {
+	return position.Line < other.Line || (position.Line == other.Line && position.Column < other.Column)
+}
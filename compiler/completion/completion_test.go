@@ -0,0 +1,86 @@
+package completion
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/parser"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func parseSource(input string) *ast.Program {
+	errorCollector := errors.New(input, "test.navi")
+	lexerInstance := lexer.New(input, "test.navi", errorCollector)
+
+	return parser.New(lexerInstance, errorCollector).ParseProgram()
+}
+
+const source = `let limit = 10
+
+func helper(x: int) -> string {
+  let label = "x"
+  return label
+}
+
+func main() {
+  let a = 1
+  let b = helper(a)
+  print(b)
+  let later = true; print(later)
+}
+`
+
+func TestComplete(t *testing.T) {
+	program := parseSource(source)
+
+	t.Run("Scoping", func(t *testing.T) {
+		tests := []struct {
+			name      string
+			line      int
+			column    int
+			variables []Variable
+		}{
+			{"Outside any function only globals are visible", 2, 1,
+				[]Variable{{"limit", "int"}}},
+			{"Parameters and earlier locals are visible", 5, 3,
+				[]Variable{{"label", "string"}, {"limit", "int"}, {"x", "int"}}},
+			{"Locals of other functions are not visible", 11, 3,
+				[]Variable{{"a", "int"}, {"b", "string"}, {"limit", "int"}}},
+			{"A variable is not visible in its own initializer", 9, 11,
+				[]Variable{{"limit", "int"}}},
+			{"A statement ended by a semicolon is visible on the same line", 12, 30,
+				[]Variable{{"a", "int"}, {"b", "string"}, {"later", "bool"}, {"limit", "int"}}},
+			{"The closing brace is inside the function", 13, 1,
+				[]Variable{{"a", "int"}, {"b", "string"}, {"later", "bool"}, {"limit", "int"}}},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				result := Complete(program, testCase.line, testCase.column)
+
+				assert.Equal(t, testCase.variables, result.Variables)
+			})
+		}
+	})
+
+	t.Run("Functions list user signatures then builtins", func(t *testing.T) {
+		functions := Complete(program, 11, 3).Functions
+
+		assert.Equal(t, []string{"helper(x: int) -> string", "main()"}, functions[:2])
+		assert.Contains(t, functions, "repeat(string, int) -> string")
+		assert.Contains(t, functions, "print(...)")
+	})
+}
+
+func TestParseLocation(t *testing.T) {
+	location, err := ParseLocation("dir/app.navi:12:5")
+	assert.NoError(t, err)
+	assert.Equal(t, Location{Path: "dir/app.navi", Line: 12, Column: 5}, location)
+
+	for _, invalid := range []string{"app.navi", "app.navi:12", "app.navi:0:5", "app.navi:x:5"} {
+		_, err := ParseLocation(invalid)
+		assert.Error(t, err, "%s should be rejected", invalid)
+	}
+}
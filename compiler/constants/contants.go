@@ -2,8 +2,33 @@ package constants
 
 const (
 	NAVIARY_EXTENSION = ".navi"
+	COMPILER_VERSION  = "0.0.1"
+)
+
+const (
+	// CACHE_DIRECTORY is created next to the compiled source file
+	CACHE_DIRECTORY = ".naviary-cache"
+
+	// DEFAULT_RUNTIME_LIBRARY is where `make runtime` places the runtime archive
+	// Override with the NAVIARY_RUNTIME environment variable
+	DEFAULT_RUNTIME_LIBRARY = "build/runtime/libnaviary_runtime.a"
+
+	// RUNTIME_ABI_VERSION is checked by the runtime when main starts, so a program
+	// linked against a runtime with different FFI signatures aborts with a clear message
+	// Bump it together with ABI_VERSION in runtime/src/abi.zig whenever an export changes
+	RUNTIME_ABI_VERSION = 11
 )
 
 const (
 	MAX_LEXER_ERRORS = 20
+
+	// MAX_DISPLAYED_ERRORS is how many diagnostics are shown before the rest are
+	// summarized; --max-errors=N overrides it
+	MAX_DISPLAYED_ERRORS = 20
+)
+
+const (
+	// MAX_EXPRESSION_DEPTH bounds how deeply expressions may nest before the
+	// parser and lowerer give up with a diagnostic instead of exhausting the stack
+	MAX_EXPRESSION_DEPTH = 2000
 )
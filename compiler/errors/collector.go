@@ -1,24 +1,45 @@
 package errors
 
 import (
+	"compiler/constants"
 	"fmt"
+	"io"
 	"os"
+	"sort"
 	"strings"
 )
 
 type ErrorCollector struct {
-	errors    []CompileError
-	source    string
-	filename  string
-	maxErrors int // prevent spamming errors
+	errors       []CompileError
+	source       string
+	filename     string
+	maxErrors    int // prevent spamming errors
+	displayLimit int // errors shown by Display before the rest are summarized
+	style        Style
 }
 
 func New(source, filename string) *ErrorCollector {
 	return &ErrorCollector{
-		errors:    make([]CompileError, 0),
-		source:    source,
-		filename:  filename,
-		maxErrors: 100,
+		errors:       make([]CompileError, 0),
+		source:       source,
+		filename:     filename,
+		maxErrors:    100,
+		displayLimit: constants.MAX_DISPLAYED_ERRORS,
+		style:        ColorStyle,
+	}
+}
+
+// SetDisplayLimit sets how many errors Display shows before summarizing the rest
+func (collector *ErrorCollector) SetDisplayLimit(limit int) {
+	collector.displayLimit = limit
+}
+
+// SetColor chooses between ColorStyle and plain text for Display
+func (collector *ErrorCollector) SetColor(enabled bool) {
+	if enabled {
+		collector.style = ColorStyle
+	} else {
+		collector.style = Style{}
 	}
 }
 
@@ -34,19 +55,58 @@ func (collector *ErrorCollector) HasErrors() bool {
 }
 
 func (collector *ErrorCollector) Display() {
-	for _, err := range collector.errors {
-		fmt.Fprint(os.Stderr, err.Display())
-		fmt.Fprintln(os.Stderr)
+	collector.DisplayTo(os.Stderr)
+}
+
+// DisplayTo writes the errors in source order, whichever phase found them first
+// Only the first displayLimit are shown in full; the rest are counted in a trailer
+func (collector *ErrorCollector) DisplayTo(writer io.Writer) {
+	sorted := collector.Sorted()
+
+	shown := sorted
+	if collector.displayLimit > 0 && len(sorted) > collector.displayLimit {
+		shown = sorted[:collector.displayLimit]
+	}
+
+	for _, err := range shown {
+		fmt.Fprint(writer, err.Render(collector.style))
+		fmt.Fprintln(writer)
+	}
+
+	if hidden := len(sorted) - len(shown); hidden == 1 {
+		fmt.Fprintf(writer, "... and 1 more error\n")
+	} else if hidden > 1 {
+		fmt.Fprintf(writer, "... and %d more errors\n", hidden)
 	}
 
 	if len(collector.errors) == 1 {
-		fmt.Fprintf(os.Stderr, "\033[1;31merror\033[0m: aborting due to previous error\n")
+		fmt.Fprintf(writer, "%serror%s: aborting due to previous error\n",
+			collector.style.Error, collector.style.Reset)
 	} else if len(collector.errors) > 1 {
-		fmt.Fprintf(os.Stderr, "\033[1;31merror\033[0m: aborting due to %d previous errors\n",
-			len(collector.errors))
+		fmt.Fprintf(writer, "%serror%s: aborting due to %d previous errors\n",
+			collector.style.Error, collector.style.Reset, len(collector.errors))
 	}
 }
 
+// Sorted returns the errors ordered by line and column
+// Errors without a position keep their relative order after all the others
+func (collector *ErrorCollector) Sorted() []CompileError {
+	sorted := append([]CompileError{}, collector.errors...)
+
+	sort.SliceStable(sorted, func(i, j int) bool {
+		left, right := sorted[i], sorted[j]
+		if (left.Line == 0) != (right.Line == 0) {
+			return right.Line == 0
+		}
+		if left.Line != right.Line {
+			return left.Line < right.Line
+		}
+		return left.Column < right.Column
+	})
+
+	return sorted
+}
+
 func (collector *ErrorCollector) Add(
 	errorType ErrorType,
 	line,
@@ -74,6 +134,26 @@ func (collector *ErrorCollector) Add(
 	}
 }
 
+// AddNote attaches a secondary location to the most recently added error
+func (collector *ErrorCollector) AddNote(line, column, length int, format string, args ...interface{}) {
+	if len(collector.errors) == 0 {
+		return
+	}
+
+	last := &collector.errors[len(collector.errors)-1]
+	last.Notes = append(last.Notes, Note{
+		Message: fmt.Sprintf(format, args...),
+		Line:    line,
+		Column:  column,
+		Length:  length,
+		Source:  collector.getSourceLine(line),
+	})
+}
+
+func (collector *ErrorCollector) Errors() []CompileError {
+	return collector.errors
+}
+
 func (collector *ErrorCollector) Clear() {
 	collector.errors = collector.errors[:0]
 }
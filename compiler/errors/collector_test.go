@@ -0,0 +1,72 @@
+package errors
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestDisplay(t *testing.T) {
+	source := "let a = 1\nlet b = 2\nlet c = 3\nlet d = 4\n"
+
+	t.Run("Errors are sorted by position", func(t *testing.T) {
+		collector := New(source, "test.navi")
+		collector.Add(TypeError, 3, 5, 1, "third")
+		collector.Add(TypeError, 0, 0, 0, "no position")
+		collector.Add(SyntaxError, 1, 9, 1, "second")
+		collector.Add(LexicalError, 1, 5, 1, "first")
+
+		messages := []string{}
+		for _, err := range collector.Sorted() {
+			messages = append(messages, err.Message)
+		}
+		assert.Equal(t, []string{"first", "second", "third", "no position"}, messages)
+		assert.Equal(t, "third", collector.Errors()[0].Message, "Errors keeps discovery order")
+	})
+
+	t.Run("Errors beyond the limit are summarized", func(t *testing.T) {
+		collector := New(source, "test.navi")
+		collector.SetDisplayLimit(2)
+		for line := 4; line >= 1; line-- {
+			collector.Add(TypeError, line, 1, 3, "error on line %d", line)
+		}
+
+		var output bytes.Buffer
+		collector.DisplayTo(&output)
+
+		assert.Less(t, strings.Index(output.String(), "error on line 1"), strings.Index(output.String(), "error on line 2"))
+		assert.NotContains(t, output.String(), "error on line 3")
+		assert.Contains(t, output.String(), "... and 2 more errors\n")
+		assert.Contains(t, output.String(), "aborting due to 4 previous errors")
+	})
+
+	t.Run("One hidden error is singular", func(t *testing.T) {
+		collector := New(source, "test.navi")
+		collector.SetDisplayLimit(1)
+		collector.Add(TypeError, 1, 1, 3, "shown")
+		collector.Add(TypeError, 2, 1, 3, "hidden")
+
+		var output bytes.Buffer
+		collector.DisplayTo(&output)
+
+		assert.Contains(t, output.String(), "... and 1 more error\n")
+	})
+
+	t.Run("Plain output has no escape sequences", func(t *testing.T) {
+		collector := New(source, "test.navi")
+		collector.SetColor(false)
+		collector.Add(TypeError, 2, 5, 1, "bad b")
+		collector.AddNote(1, 5, 1, "a is here")
+
+		var output bytes.Buffer
+		collector.DisplayTo(&output)
+
+		assert.NotContains(t, output.String(), "\033")
+		assert.Contains(t, output.String(), "error: bad b\n  --> test.navi:2:5\n")
+		assert.Contains(t, output.String(), " 2 | let b = 2\n")
+		assert.Contains(t, output.String(), "note: a is here\n")
+		assert.Contains(t, output.String(), "error: aborting due to previous error\n")
+	})
+}
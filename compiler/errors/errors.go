@@ -33,6 +33,16 @@ type CompileError struct {
 	Column  int
 	Length  int
 	Source  string
+	Notes   []Note
+}
+
+// Note points at a second location that explains an error, such as an earlier declaration
+type Note struct {
+	Message string
+	Line    int
+	Column  int
+	Length  int
+	Source  string
 }
 
 func (e CompileError) Error() string {
@@ -40,27 +50,64 @@ func (e CompileError) Error() string {
 		e.File, e.Line, e.Column, e.Type, e.Message)
 }
 
+// Style holds the ANSI sequences diagnostics are rendered with
+// The zero Style renders plain text for logs, pipes and NO_COLOR
+type Style struct {
+	Error  string
+	Note   string
+	Gutter string
+	Reset  string
+}
+
+// ColorStyle is the terminal style: red errors, cyan notes and a blue gutter
+var ColorStyle = Style{
+	Error:  "\033[1;31m",
+	Note:   "\033[1;36m",
+	Gutter: "\033[1;34m",
+	Reset:  "\033[0m",
+}
+
+// Display renders the error with ColorStyle
 func (e CompileError) Display() string {
+	return e.Render(ColorStyle)
+}
+
+// Render renders the error, its source line and its notes in the given style
+func (e CompileError) Render(style Style) string {
 	var builder strings.Builder
 
-	builder.WriteString(fmt.Sprintf("\033[1;31merror\033[0m: %s\n", e.Message))
-	builder.WriteString(fmt.Sprintf("  \033[1;34m-->\033[0m %s:%d:%d\n",
-		e.File, e.Line, e.Column))
-
-	if e.Source != "" {
-		lineNum := fmt.Sprintf("%d", e.Line)
-		builder.WriteString("   \033[1;34m|\033[0m\n")
-		builder.WriteString(fmt.Sprintf(" \033[1;34m%s |\033[0m %s\n",
-			lineNum, e.Source))
-
-		spaces := e.Column - 1
-		underline := strings.Repeat("^", e.Length)
-		if e.Length == 0 {
-			underline = "^"
-		}
-		builder.WriteString(fmt.Sprintf("   \033[1;34m|\033[0m %*s\033[1;31m%s\033[0m\n",
-			spaces+len(lineNum)+1, "", underline))
+	builder.WriteString(fmt.Sprintf("%serror%s: %s\n", style.Error, style.Reset, e.Message))
+	builder.WriteString(fmt.Sprintf("  %s-->%s %s:%d:%d\n",
+		style.Gutter, style.Reset, e.File, e.Line, e.Column))
+
+	writeSourceLine(&builder, style, e.Line, e.Column, e.Length, e.Source, "^", style.Error)
+
+	for _, note := range e.Notes {
+		builder.WriteString(fmt.Sprintf("%snote%s: %s\n", style.Note, style.Reset, note.Message))
+		builder.WriteString(fmt.Sprintf("  %s-->%s %s:%d:%d\n",
+			style.Gutter, style.Reset, e.File, note.Line, note.Column))
+		writeSourceLine(&builder, style, note.Line, note.Column, note.Length, note.Source, "-", style.Note)
 	}
 
 	return builder.String()
 }
+
+// writeSourceLine prints a source line with the given span underlined by marker
+func writeSourceLine(builder *strings.Builder, style Style, line, column, length int, source, marker, color string) {
+	if source == "" {
+		return
+	}
+
+	lineNum := fmt.Sprintf("%d", line)
+	builder.WriteString(fmt.Sprintf("   %s|%s\n", style.Gutter, style.Reset))
+	builder.WriteString(fmt.Sprintf(" %s%s |%s %s\n",
+		style.Gutter, lineNum, style.Reset, source))
+
+	spaces := column - 1
+	underline := strings.Repeat(marker, length)
+	if length == 0 {
+		underline = marker
+	}
+	builder.WriteString(fmt.Sprintf("   %s|%s %*s%s%s%s\n",
+		style.Gutter, style.Reset, spaces+len(lineNum)+1, "", color, underline, style.Reset))
+}
@@ -0,0 +1,137 @@
+package main
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/parser"
+	goast "go/ast"
+	goparser "go/parser"
+	gotoken "go/token"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+// examplesDirectory holds one program per feature, each with a .expected output file
+var examplesDirectory = filepath.Join("..", "examples")
+
+func examplePaths(t *testing.T) []string {
+	paths, err := filepath.Glob(filepath.Join(examplesDirectory, "*.navi"))
+	assert.NoError(t, err)
+	assert.NotEmpty(t, paths)
+	return paths
+}
+
+// TestExamples runs every example and compares its output with the .expected file beside it
+func TestExamples(t *testing.T) {
+	requireToolchain(t)
+
+	for _, path := range examplePaths(t) {
+		t.Run(filepath.Base(path), func(t *testing.T) {
+			source, err := os.ReadFile(path)
+			assert.NoError(t, err)
+			expected, err := os.ReadFile(strings.TrimSuffix(path, ".navi") + ".expected")
+			if !assert.NoError(t, err, "every example needs a .expected file") {
+				return
+			}
+
+			result, diagnostics, err := RunSnippet(string(source))
+
+			assert.NoError(t, err)
+			assert.Empty(t, diagnostics)
+			assert.Equal(t, string(expected), result.Output)
+		})
+	}
+}
+
+// TestExamplesCoverSyntax checks that the examples use every statement and expression node,
+// so a language feature cannot land without an example showing it
+func TestExamplesCoverSyntax(t *testing.T) {
+	used := map[string]bool{}
+	for _, path := range examplePaths(t) {
+		source, err := os.ReadFile(path)
+		assert.NoError(t, err)
+
+		errorCollector := errors.New(string(source), path)
+		program := parser.New(lexer.New(string(source), path, errorCollector), errorCollector).ParseProgram()
+		assert.False(t, errorCollector.HasErrors(), "%s should parse", path)
+
+		collectNodeTypes(reflect.ValueOf(program), used)
+	}
+
+	for _, name := range astNodeTypes(t) {
+		assert.True(t, used[name], "no example uses ast.%s; add one to examples/", name)
+	}
+}
+
+var (
+	statementType  = reflect.TypeOf((*ast.Statement)(nil)).Elem()
+	expressionType = reflect.TypeOf((*ast.Expression)(nil)).Elem()
+)
+
+// collectNodeTypes records the name of every statement and expression type reachable from value
+func collectNodeTypes(value reflect.Value, used map[string]bool) {
+	switch value.Kind() {
+	case reflect.Pointer:
+		if value.IsNil() {
+			return
+		}
+		if value.Type().Implements(statementType) || value.Type().Implements(expressionType) {
+			used[value.Type().Elem().Name()] = true
+		}
+		collectNodeTypes(value.Elem(), used)
+	case reflect.Interface:
+		if !value.IsNil() {
+			collectNodeTypes(value.Elem(), used)
+		}
+	case reflect.Slice:
+		for i := 0; i < value.Len(); i++ {
+			collectNodeTypes(value.Index(i), used)
+		}
+	case reflect.Struct:
+		for i := 0; i < value.NumField(); i++ {
+			collectNodeTypes(value.Field(i), used)
+		}
+	}
+}
+
+// astNodeTypes lists the types in the ast package that are statements or expressions,
+// found by their statementNode and expressionNode marker methods
+func astNodeTypes(t *testing.T) []string {
+	paths, err := filepath.Glob(filepath.Join("ast", "*.go"))
+	assert.NoError(t, err)
+
+	names := []string{}
+	fileSet := gotoken.NewFileSet()
+	for _, path := range paths {
+		if strings.HasSuffix(path, "_test.go") {
+			continue
+		}
+
+		file, err := goparser.ParseFile(fileSet, path, nil, 0)
+		if !assert.NoError(t, err) {
+			continue
+		}
+
+		for _, declaration := range file.Decls {
+			function, ok := declaration.(*goast.FuncDecl)
+			if !ok || function.Recv == nil {
+				continue
+			}
+			if function.Name.Name != "statementNode" && function.Name.Name != "expressionNode" {
+				continue
+			}
+			if receiver, ok := function.Recv.List[0].Type.(*goast.StarExpr); ok {
+				names = append(names, receiver.X.(*goast.Ident).Name)
+			}
+		}
+	}
+
+	assert.NotEmpty(t, names)
+	return names
+}
@@ -3,6 +3,10 @@ package lexer
 import (
 	"compiler/errors"
 	"compiler/token"
+	"math"
+	"strconv"
+	"strings"
+	"unicode/utf8"
 )
 
 // Lexer tokenizes the input source code
@@ -37,94 +41,167 @@ func (lexer *Lexer) NextToken() token.Token {
 	lexer.skipWhitespace()
 
 	// Save current position for token
+	start := lexer.position
 	t.Line = lexer.line
 	t.Column = lexer.column
 
 	switch lexer.currentChar {
 	case '=':
-		t = token.New(token.ASSIGN, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		if lexer.peek() == '=' {
+			t = lexer.readTwoCharToken(token.EQUAL)
+		} else {
+			t = lexer.readSingleCharToken(token.ASSIGN)
+		}
+	case '!':
+		if lexer.peek() == '=' {
+			t = lexer.readTwoCharToken(token.NOT_EQUAL)
+		} else {
+			t = lexer.illegalCharacter()
+		}
+	case '<':
+		if lexer.peek() == '=' {
+			t = lexer.readTwoCharToken(token.LESS_THAN_EQUAL)
+		} else {
+			t = lexer.readSingleCharToken(token.LESS_THAN)
+		}
+	case '>':
+		if lexer.peek() == '=' {
+			t = lexer.readTwoCharToken(token.GREATER_THAN_EQUAL)
+		} else {
+			t = lexer.readSingleCharToken(token.GREATER_THAN)
+		}
 	case '+':
-		t = token.New(token.PLUS, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.PLUS)
 	case '-':
 		if lexer.peek() == '>' {
-			startColumn := lexer.column
-			lexer.advance() // consume '-'
-			lexer.advance() // consume '>'
-			t = token.New(token.ARROW, "->", lexer.line, startColumn)
+			t = lexer.readTwoCharToken(token.ARROW)
 		} else {
-			t = token.New(token.MINUS, string(lexer.currentChar), lexer.line, lexer.column)
-			lexer.advance()
+			t = lexer.readSingleCharToken(token.MINUS)
 		}
 	case '*':
-		t = token.New(token.ASTERISK, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.ASTERISK)
 	case '/':
-		t = token.New(token.SLASH, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.SLASH)
 	case '(':
-		t = token.New(token.LEFT_PAREN, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.LEFT_PAREN)
 	case ')':
-		t = token.New(token.RIGHT_PAREN, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.RIGHT_PAREN)
 	case '{':
-		t = token.New(token.LEFT_BRACE, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.LEFT_BRACE)
 	case '}':
-		t = token.New(token.RIGHT_BRACE, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.RIGHT_BRACE)
+	case '[':
+		t = lexer.readSingleCharToken(token.LEFT_BRACKET)
+	case ']':
+		t = lexer.readSingleCharToken(token.RIGHT_BRACKET)
 	case ',':
-		t = token.New(token.COMMA, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.COMMA)
 	case ';':
-		t = token.New(token.SEMICOLON, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.SEMICOLON)
+	case '@':
+		t = lexer.readSingleCharToken(token.AT)
+	case '.':
+		if strings.HasPrefix(lexer.input[lexer.position:], "..=") {
+			t = lexer.readThreeCharToken(token.DOT_DOT_EQUAL)
+		} else if lexer.peek() == '.' {
+			t = lexer.readTwoCharToken(token.DOT_DOT)
+		} else {
+			t = lexer.readSingleCharToken(token.DOT)
+		}
 	case ':':
 		// Check for := (colon assign)
 		if lexer.peek() == '=' {
-			startColumn := lexer.column
-			lexer.advance() // consume ':'
-			lexer.advance() // consume '='
-			t = token.New(token.COLON_ASSIGN, ":=", lexer.line, startColumn)
+			t = lexer.readTwoCharToken(token.COLON_ASSIGN)
 		} else {
-			t = token.New(token.COLON, string(lexer.currentChar), lexer.line, lexer.column)
-			lexer.advance()
+			t = lexer.readSingleCharToken(token.COLON)
 		}
 	case '\n':
-		t = token.New(token.NEW_LINE, string(lexer.currentChar), lexer.line, lexer.column)
-		lexer.advance()
+		t = lexer.readSingleCharToken(token.NEW_LINE)
 	case '"':
-		t.Value = lexer.readString()
+		if strings.HasPrefix(lexer.input[lexer.position:], `"""`) {
+			t.Value = lexer.readTripleQuotedString()
+		} else {
+			t.Value = lexer.readString()
+		}
 		t.Type = token.STRING_LITERAL
-		return t // readString already advanced position
+	case '\'':
+		t.Value = lexer.readChar()
+		t.Type = token.CHAR_LITERAL
 	case 0:
 		t.Type = token.EOF
 		t.Value = ""
 	default:
-		if isLetter(lexer.currentChar) {
+		if lexer.currentChar == 'r' && lexer.startsRawString() {
+			t.Value = lexer.readRawString()
+			t.Type = token.STRING_LITERAL
+		} else if isLetter(lexer.currentChar) {
 			t.Value = lexer.readIdentifier()
 			t.Type = token.LookupIdentifier(t.Value)
-			return t // readIdentifier already advanced position
 		} else if isDigit(lexer.currentChar) {
 			t.Value = lexer.readNumber()
 			t.Type = token.INT_LITERAL
-			return t // readNumber already advanced position
 		} else {
-			t = token.New(token.ILLEGAL, string(lexer.currentChar), lexer.line, lexer.column)
-			lexer.errors.Add(
-				errors.LexicalError,
-				lexer.line,
-				lexer.column,
-				len(string(lexer.currentChar)),
-				"Unexpected character: %s",
-				string(lexer.currentChar),
-			)
-			lexer.advance()
+			t = lexer.illegalCharacter()
 		}
 	}
 
+	// Every branch has consumed exactly the token's characters, escapes included
+	t.Offset = start
+	t.End = min(lexer.position, len(lexer.input))
+
+	return t
+}
+
+// Token values are slices of the input, so punctuation and operators never allocate
+
+// readSingleCharToken consumes a one character token such as + or (
+func (lexer *Lexer) readSingleCharToken(tokenType token.TokenType) token.Token {
+	t := token.New(tokenType, lexer.input[lexer.position:lexer.readPosition], lexer.line, lexer.column)
+	lexer.advance()
+
+	return t
+}
+
+// readTwoCharToken consumes a two character operator such as == or <=
+func (lexer *Lexer) readTwoCharToken(tokenType token.TokenType) token.Token {
+	startPosition := lexer.position
+	startColumn := lexer.column
+	lexer.advance() // consume first character
+	lexer.advance() // consume second character
+
+	return token.New(tokenType, lexer.input[startPosition:lexer.position], lexer.line, startColumn)
+}
+
+// readThreeCharToken consumes a three character operator such as ..=
+func (lexer *Lexer) readThreeCharToken(tokenType token.TokenType) token.Token {
+	startPosition := lexer.position
+	startColumn := lexer.column
+	for i := 0; i < 3; i++ {
+		lexer.advance()
+	}
+
+	return token.New(tokenType, lexer.input[startPosition:lexer.position], lexer.line, startColumn)
+}
+
+// illegalCharacter reports the current character as unexpected and skips it
+// A multi-byte UTF-8 character is one error and one ILLEGAL token whose value is the whole character
+func (lexer *Lexer) illegalCharacter() token.Token {
+	_, size := utf8.DecodeRuneInString(lexer.input[lexer.position:])
+	character := lexer.input[lexer.position : lexer.position+size]
+
+	t := token.New(token.ILLEGAL, character, lexer.line, lexer.column)
+	lexer.errors.Add(
+		errors.LexicalError,
+		lexer.line,
+		lexer.column,
+		1,
+		"unexpected character '%s'",
+		character,
+	)
+	for i := 0; i < size; i++ {
+		lexer.advance()
+	}
+
 	return t
 }
 
@@ -142,7 +219,7 @@ func (lexer *Lexer) Tokenize() []token.Token {
 
 		// Stop if too many errors
 		if lexer.errors.HasErrors() {
-			tokens = append(tokens, token.Token{Type: token.EOF, Line: lexer.line, Column: lexer.column})
+			tokens = append(tokens, token.Token{Type: token.EOF, Line: lexer.line, Column: lexer.column, Offset: len(lexer.input), End: len(lexer.input)})
 			break
 		}
 	}
@@ -169,34 +246,89 @@ func (lexer *Lexer) advance() {
 	lexer.column++
 }
 
-// skipWhitespace skips spaces, tabs, and newlines
+// skipWhitespace skips spaces, tabs, carriage returns and comments
+// Newlines are tokens, so a line comment stops before its newline
 func (lexer *Lexer) skipWhitespace() {
-	for lexer.currentChar == ' ' || lexer.currentChar == '\t' || lexer.currentChar == '\r' {
-		lexer.advance()
+	for {
+		switch {
+		case lexer.currentChar == ' ' || lexer.currentChar == '\t' || lexer.currentChar == '\r':
+			lexer.advance()
+		case lexer.currentChar == '/' && lexer.peek() == '/':
+			for lexer.currentChar != '\n' && lexer.currentChar != 0 {
+				lexer.advance()
+			}
+		case lexer.currentChar == '/' && lexer.peek() == '*':
+			lexer.skipBlockComment()
+		default:
+			return
+		}
+	}
+}
+
+// skipBlockComment skips a /* */ comment, which may span lines
+// Block comments nest, so code that already contains one can be commented out
+func (lexer *Lexer) skipBlockComment() {
+	startLine := lexer.line
+	startColumn := lexer.column
+	depth := 0
+
+	for {
+		switch {
+		case lexer.currentChar == 0:
+			lexer.errors.Add(
+				errors.LexicalError,
+				startLine,
+				startColumn,
+				2,
+				"unterminated block comment starting at line %d",
+				startLine,
+			)
+			return
+		case lexer.currentChar == '/' && lexer.peek() == '*':
+			depth++
+			lexer.advance()
+			lexer.advance()
+		case lexer.currentChar == '*' && lexer.peek() == '/':
+			depth--
+			lexer.advance()
+			lexer.advance()
+			if depth == 0 {
+				return
+			}
+		default:
+			lexer.advance()
+		}
 	}
 }
 
 func (lexer *Lexer) readString() string {
+	startLine := lexer.line
+	startColumn := lexer.column
+	quotePosition := lexer.position
 	lexer.advance() //consume opening quote
 
+	// A string ends on the line it starts, so a missing quote is reported
+	// there instead of swallowing the rest of the file
 	startPosition := lexer.position
-	for lexer.currentChar != '"' && lexer.currentChar != 0 {
+	for lexer.currentChar != '"' && lexer.currentChar != '\n' && lexer.currentChar != 0 {
 		if lexer.currentChar == '\\' {
 			lexer.advance() // consume backslash
-			if lexer.currentChar == 0 {
+			if lexer.currentChar == '\n' || lexer.currentChar == 0 {
 				break
 			}
 		}
 		lexer.advance()
 	}
 
-	if lexer.currentChar == 0 {
+	if lexer.currentChar != '"' {
 		lexer.errors.Add(
 			errors.LexicalError,
-			lexer.line,
-			lexer.column,
-			1,
-			"Unterminated string literal",
+			startLine,
+			startColumn,
+			lexer.position-quotePosition,
+			"unterminated string literal starting at line %d, column %d",
+			startLine,
+			startColumn,
 		)
 		return lexer.input[startPosition:lexer.position]
 	}
@@ -207,7 +339,148 @@ func (lexer *Lexer) readString() string {
 	return lexer.processEscapeSequences(result)
 }
 
+// readTripleQuotedString reads a """...""" string, the one string form that may span lines
+// Newlines are kept and quotes need no escape; the first """ ends the string
+// Escapes are decoded as in other strings, so \""" puts three quotes in one
+func (lexer *Lexer) readTripleQuotedString() string {
+	startLine := lexer.line
+	startColumn := lexer.column
+	for i := 0; i < 3; i++ {
+		lexer.advance() // consume opening quotes
+	}
+
+	startPosition := lexer.position
+	for lexer.currentChar != 0 {
+		if strings.HasPrefix(lexer.input[lexer.position:], `"""`) {
+			result := lexer.input[startPosition:lexer.position]
+			for i := 0; i < 3; i++ {
+				lexer.advance() // consume closing quotes
+			}
+			return lexer.processEscapeSequences(result)
+		}
+
+		if lexer.currentChar == '\\' {
+			lexer.advance() // consume backslash
+			if lexer.currentChar == 0 {
+				break
+			}
+		}
+		lexer.advance()
+	}
+
+	lexer.errors.Add(
+		errors.LexicalError,
+		startLine,
+		startColumn,
+		3,
+		"unterminated triple-quoted string starting at line %d, column %d",
+		startLine,
+		startColumn,
+	)
+	return lexer.input[startPosition:lexer.position]
+}
+
+// startsRawString checks if the current 'r' opens a raw string: r"..." or r#"..."#
+func (lexer *Lexer) startsRawString() bool {
+	position := lexer.readPosition
+	for position < len(lexer.input) && lexer.input[position] == '#' {
+		position++
+	}
+
+	return position < len(lexer.input) && lexer.input[position] == '"'
+}
+
+// readRawString reads a raw string, whose body is taken as written: backslashes are not escapes
+// The closing quote must be followed by as many '#' as the opening one is preceded by,
+// so r#"say "hi""# can hold quotes; like other strings it ends on the line it starts
+func (lexer *Lexer) readRawString() string {
+	startLine := lexer.line
+	startColumn := lexer.column
+	openPosition := lexer.position
+	lexer.advance() // consume 'r'
+
+	closing := "\""
+	for lexer.currentChar == '#' {
+		closing += "#"
+		lexer.advance()
+	}
+	lexer.advance() // consume opening quote
+
+	startPosition := lexer.position
+	for lexer.currentChar != '\n' && lexer.currentChar != 0 {
+		if strings.HasPrefix(lexer.input[lexer.position:], closing) {
+			result := lexer.input[startPosition:lexer.position]
+			for i := 0; i < len(closing); i++ {
+				lexer.advance()
+			}
+			return result
+		}
+		lexer.advance()
+	}
+
+	lexer.errors.Add(
+		errors.LexicalError,
+		startLine,
+		startColumn,
+		lexer.position-openPosition,
+		"unterminated raw string literal starting at line %d, column %d",
+		startLine,
+		startColumn,
+	)
+	return lexer.input[startPosition:lexer.position]
+}
+
+// readChar reads a character literal and returns the character it denotes
+func (lexer *Lexer) readChar() string {
+	startLine := lexer.line
+	startColumn := lexer.column
+	lexer.advance() // consume opening quote
+
+	startPosition := lexer.position
+	for lexer.currentChar != '\'' && lexer.currentChar != '\n' && lexer.currentChar != 0 {
+		if lexer.currentChar == '\\' {
+			lexer.advance() // consume backslash
+			if lexer.currentChar == 0 {
+				break
+			}
+		}
+		lexer.advance()
+	}
+
+	if lexer.currentChar != '\'' {
+		lexer.errors.Add(
+			errors.LexicalError,
+			startLine,
+			startColumn,
+			lexer.position-startPosition+1,
+			"Unterminated character literal",
+		)
+		return lexer.input[startPosition:lexer.position]
+	}
+
+	result := lexer.processEscapeSequences(lexer.input[startPosition:lexer.position])
+	lexer.advance() // consume closing quote
+
+	if utf8.RuneCountInString(result) != 1 {
+		lexer.errors.Add(
+			errors.LexicalError,
+			startLine,
+			startColumn,
+			lexer.position-startPosition+1,
+			"Character literal must contain exactly one character",
+		)
+	}
+
+	return result
+}
+
+// processEscapeSequences decodes escapes in a literal's body
+// Literals without a backslash are returned as is, sharing the input's memory
 func (lexer *Lexer) processEscapeSequences(str string) string {
+	if strings.IndexByte(str, '\\') < 0 {
+		return str
+	}
+
 	var result []byte
 	i := 0
 
@@ -229,6 +502,12 @@ func (lexer *Lexer) processEscapeSequences(str string) string {
 			case '"':
 				result = append(result, '"')
 				i += 2
+			case '\'':
+				result = append(result, '\'')
+				i += 2
+			case '0':
+				result = append(result, 0)
+				i += 2
 			default:
 				// Unknown escape sequence, keep as is
 				result = append(result, str[i])
@@ -246,9 +525,16 @@ func (lexer *Lexer) processEscapeSequences(str string) string {
 // readNumber reads a number from the input
 func (lexer *Lexer) readNumber() string {
 	startPosition := lexer.position
+	startColumn := lexer.column
+
+	if lexer.currentChar == '0' {
+		if base, ok := numberBases[lexer.peek()]; ok {
+			return lexer.readPrefixedNumber(base, startPosition, startColumn)
+		}
+	}
 
-	// Read all consecutive digits
-	for isDigit(lexer.currentChar) {
+	// Read all consecutive digits and the underscores grouping them
+	for isDigit(lexer.currentChar) || lexer.currentChar == '_' {
 		lexer.advance()
 	}
 
@@ -270,7 +556,127 @@ func (lexer *Lexer) readNumber() string {
 		return invalidToken
 	}
 
-	return lexer.input[startPosition:lexer.position]
+	literal := lexer.input[startPosition:lexer.position]
+	digits, ok := lexer.removeSeparators(literal, literal, startColumn)
+	if !ok {
+		return literal
+	}
+
+	if !fitsInt(digits) {
+		lexer.reportIntOverflow(literal, startColumn)
+	}
+
+	return digits
+}
+
+// removeSeparators strips the underscores that group digits, as in 1_000_000
+// An underscore must sit between two digits, so 100_, 1__0 and 0x_FF are reported
+// Only literals with underscores allocate; the rest are returned as is
+func (lexer *Lexer) removeSeparators(literal string, digits string, startColumn int) (string, bool) {
+	if !strings.Contains(digits, "_") {
+		return digits, true
+	}
+
+	if strings.HasPrefix(digits, "_") || strings.HasSuffix(digits, "_") || strings.Contains(digits, "__") {
+		lexer.errors.Add(
+			errors.LexicalError,
+			lexer.line,
+			startColumn,
+			len(literal),
+			"misplaced '_' in number literal %s: underscores must separate digits",
+			literal,
+		)
+		return literal, false
+	}
+
+	return strings.ReplaceAll(digits, "_", ""), true
+}
+
+// numberBases maps the second character of a 0x, 0o or 0b prefix to its base
+var numberBases = map[byte]int{
+	'x': 16, 'X': 16,
+	'o': 8, 'O': 8,
+	'b': 2, 'B': 2,
+}
+
+// readPrefixedNumber reads a hexadecimal, octal or binary literal and returns
+// its value in decimal, so later phases only ever see decimal literals
+// The token's byte range still covers the literal as written
+func (lexer *Lexer) readPrefixedNumber(base int, startPosition int, startColumn int) string {
+	lexer.advance() // consume '0'
+	lexer.advance() // consume the base letter
+
+	digitsStart := lexer.position
+	for isLetter(lexer.currentChar) || isDigit(lexer.currentChar) {
+		lexer.advance()
+	}
+
+	literal := lexer.input[startPosition:lexer.position]
+	digits := lexer.input[digitsStart:lexer.position]
+	if digits == "" {
+		lexer.errors.Add(
+			errors.LexicalError,
+			lexer.line,
+			startColumn,
+			len(literal),
+			"integer literal %s has no digits",
+			literal,
+		)
+		return literal
+	}
+
+	digits, ok := lexer.removeSeparators(literal, digits, startColumn)
+	if !ok {
+		return literal
+	}
+
+	value, err := strconv.ParseUint(digits, base, 64)
+	if numberError, ok := err.(*strconv.NumError); ok && numberError.Err != strconv.ErrRange {
+		lexer.errors.Add(
+			errors.LexicalError,
+			lexer.line,
+			startColumn,
+			len(literal),
+			"Invalid number format: %s",
+			literal,
+		)
+		return literal
+	}
+
+	if err != nil || value > maxIntLiteral {
+		lexer.reportIntOverflow(literal, startColumn)
+		return literal
+	}
+
+	return strconv.FormatUint(value, 10)
+}
+
+// maxIntLiteral is the largest integer literal the lexer accepts
+// It is one more than the maximum int so -9223372036854775808 can be written;
+// the parser reports the value when it is not negated
+const maxIntLiteral = uint64(math.MaxInt64) + 1
+
+// fitsInt reports whether a decimal literal fits in int (64 bits), or is the minimum int's magnitude
+// Once sized int types exist the limit should come from the literal's context
+func fitsInt(literal string) bool {
+	value, err := strconv.ParseUint(literal, 10, 64)
+	if err != nil {
+		return false
+	}
+
+	return value <= maxIntLiteral
+}
+
+// reportIntOverflow reports a literal whose value does not fit in int
+func (lexer *Lexer) reportIntOverflow(literal string, startColumn int) {
+	lexer.errors.Add(
+		errors.LexicalError,
+		lexer.line,
+		startColumn,
+		len(literal),
+		"integer literal out of range for int (max %d)",
+		int64(math.MaxInt64),
+	)
 }
 
 // readIdentifier reads an identifier or keyword from the input
@@ -3,6 +3,8 @@ package lexer
 import (
 	"compiler/errors"
 	"compiler/token"
+	"fmt"
+	"strings"
 	"testing"
 
 	"github.com/stretchr/testify/assert"
@@ -81,6 +83,18 @@ func TestLexer(t *testing.T) {
 				expectedType:  token.RIGHT_BRACE,
 				expectedValue: "}",
 			},
+			{
+				name:          "Left Bracket",
+				input:         "[",
+				expectedType:  token.LEFT_BRACKET,
+				expectedValue: "[",
+			},
+			{
+				name:          "Right Bracket",
+				input:         "]",
+				expectedType:  token.RIGHT_BRACKET,
+				expectedValue: "]",
+			},
 			{
 				name:          "Comma",
 				input:         ",",
@@ -93,6 +107,12 @@ func TestLexer(t *testing.T) {
 				expectedType:  token.SEMICOLON,
 				expectedValue: ";",
 			},
+			{
+				name:          "At sign",
+				input:         "@",
+				expectedType:  token.AT,
+				expectedValue: "@",
+			},
 			{
 				name:          "Colon",
 				input:         ":",
@@ -118,13 +138,43 @@ func TestLexer(t *testing.T) {
 				expectedType:  token.INT_LITERAL,
 				expectedValue: "123",
 			},
-			// TODO:
-			// {
-			// 	name:          "Integer with underscore",
-			// 	input:         "1_000",
-			// 	expectedType:  token.INT_LITERAL,
-			// 	expectedValue: "1_000",
-			// },
+			{
+				name:          "Integer with underscore",
+				input:         "1_000",
+				expectedType:  token.INT_LITERAL,
+				expectedValue: "1000",
+			},
+			{
+				name:          "Leading underscore is an identifier",
+				input:         "_100",
+				expectedType:  token.IDENTIFIER,
+				expectedValue: "_100",
+			},
+			// CHAR_LITERAL
+			{
+				name:          "Char literal",
+				input:         "'a'",
+				expectedType:  token.CHAR_LITERAL,
+				expectedValue: "a",
+			},
+			{
+				name:          "Escaped char literal",
+				input:         "'\\n'",
+				expectedType:  token.CHAR_LITERAL,
+				expectedValue: "\n",
+			},
+			{
+				name:          "Escaped quote char literal",
+				input:         "'\\''",
+				expectedType:  token.CHAR_LITERAL,
+				expectedValue: "'",
+			},
+			{
+				name:          "Non-ASCII char literal",
+				input:         "'é'",
+				expectedType:  token.CHAR_LITERAL,
+				expectedValue: "é",
+			},
 			// IDENTIFIER
 			{
 				name:          "Simple identifier",
@@ -197,6 +247,36 @@ func TestLexer(t *testing.T) {
 				expectedType:  token.MUT,
 				expectedValue: "mut",
 			},
+			{
+				name:          "true keyword",
+				input:         "true",
+				expectedType:  token.TRUE,
+				expectedValue: "true",
+			},
+			{
+				name:          "false keyword",
+				input:         "false",
+				expectedType:  token.FALSE,
+				expectedValue: "false",
+			},
+			{
+				name:          "in keyword",
+				input:         "in",
+				expectedType:  token.IN,
+				expectedValue: "in",
+			},
+			{
+				name:          "for keyword",
+				input:         "for",
+				expectedType:  token.FOR,
+				expectedValue: "for",
+			},
+			{
+				name:          "char type keyword",
+				input:         "char",
+				expectedType:  token.CHAR,
+				expectedValue: "char",
+			},
 		}
 
 		for _, testCase := range tests {
@@ -232,6 +312,60 @@ func TestLexer(t *testing.T) {
 				expectedType:  token.COLON_ASSIGN,
 				expectedValue: ":=",
 			},
+			{
+				name:          "Equal token",
+				input:         "==",
+				expectedType:  token.EQUAL,
+				expectedValue: "==",
+			},
+			{
+				name:          "Not equal token",
+				input:         "!=",
+				expectedType:  token.NOT_EQUAL,
+				expectedValue: "!=",
+			},
+			{
+				name:          "Less than token",
+				input:         "<",
+				expectedType:  token.LESS_THAN,
+				expectedValue: "<",
+			},
+			{
+				name:          "Less than or equal token",
+				input:         "<=",
+				expectedType:  token.LESS_THAN_EQUAL,
+				expectedValue: "<=",
+			},
+			{
+				name:          "Greater than token",
+				input:         ">",
+				expectedType:  token.GREATER_THAN,
+				expectedValue: ">",
+			},
+			{
+				name:          "Greater than or equal token",
+				input:         ">=",
+				expectedType:  token.GREATER_THAN_EQUAL,
+				expectedValue: ">=",
+			},
+			{
+				name:          "Dot token",
+				input:         ".",
+				expectedType:  token.DOT,
+				expectedValue: ".",
+			},
+			{
+				name:          "Exclusive range token",
+				input:         "..",
+				expectedType:  token.DOT_DOT,
+				expectedValue: "..",
+			},
+			{
+				name:          "Inclusive range token",
+				input:         "..=",
+				expectedType:  token.DOT_DOT_EQUAL,
+				expectedValue: "..=",
+			},
 		}
 
 		for _, testCase := range tests {
@@ -280,6 +414,80 @@ func TestLexer(t *testing.T) {
 					{tokenType: token.INT_LITERAL, tokenValue: "3"},
 				},
 			},
+			{
+				name:  "Subtraction without spaces",
+				input: "x-1",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.IDENTIFIER, tokenValue: "x"},
+					{tokenType: token.MINUS, tokenValue: "-"},
+					{tokenType: token.INT_LITERAL, tokenValue: "1"},
+				},
+			},
+			{
+				name:  "Range without spaces",
+				input: "for i in 0..=n",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.FOR, tokenValue: "for"},
+					{tokenType: token.IDENTIFIER, tokenValue: "i"},
+					{tokenType: token.IN, tokenValue: "in"},
+					{tokenType: token.INT_LITERAL, tokenValue: "0"},
+					{tokenType: token.DOT_DOT_EQUAL, tokenValue: "..="},
+					{tokenType: token.IDENTIFIER, tokenValue: "n"},
+				},
+			},
+			{
+				name:  "Minus attached to the right operand",
+				input: "x -1",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.IDENTIFIER, tokenValue: "x"},
+					{tokenType: token.MINUS, tokenValue: "-"},
+					{tokenType: token.INT_LITERAL, tokenValue: "1"},
+				},
+			},
+			{
+				name:  "Minus attached to the left operand",
+				input: "x- 1",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.IDENTIFIER, tokenValue: "x"},
+					{tokenType: token.MINUS, tokenValue: "-"},
+					{tokenType: token.INT_LITERAL, tokenValue: "1"},
+				},
+			},
+			{
+				name:  "Subtraction of literals",
+				input: "1-2",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.INT_LITERAL, tokenValue: "1"},
+					{tokenType: token.MINUS, tokenValue: "-"},
+					{tokenType: token.INT_LITERAL, tokenValue: "2"},
+				},
+			},
+			{
+				name:  "Minus is never part of a number",
+				input: "-5",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{tokenType: token.MINUS, tokenValue: "-"},
+					{tokenType: token.INT_LITERAL, tokenValue: "5"},
+				},
+			},
 			{
 				name:  "Function declaration",
 				input: "func main() {}",
@@ -390,22 +598,22 @@ func TestLexer(t *testing.T) {
 			shouldContainError string
 		}{
 			{
-				name:               "Invalid character @",
-				input:              "@",
+				name:               "Invalid character ~",
+				input:              "~",
 				expectedErrorCount: 1,
-				shouldContainError: "Unexpected character",
+				shouldContainError: "unexpected character",
 			},
 			{
 				name:               "Invalid character #",
 				input:              "#",
 				expectedErrorCount: 1,
-				shouldContainError: "Unexpected character",
+				shouldContainError: "unexpected character",
 			},
 			{
 				name:               "Invalid character $",
 				input:              "$",
 				expectedErrorCount: 1,
-				shouldContainError: "Unexpected character",
+				shouldContainError: "unexpected character",
 			},
 			{
 				name:               "Invalid number format",
@@ -415,9 +623,9 @@ func TestLexer(t *testing.T) {
 			},
 			{
 				name:               "Multiple invalid characters",
-				input:              "let x = @ + #",
+				input:              "let x = ~ + #",
 				expectedErrorCount: 2,
-				shouldContainError: "Unexpected character",
+				shouldContainError: "unexpected character",
 			},
 			{
 				name:               "Invalid number in expression",
@@ -425,6 +633,48 @@ func TestLexer(t *testing.T) {
 				expectedErrorCount: 1,
 				shouldContainError: "Invalid number format",
 			},
+			{
+				name:               "Empty char literal",
+				input:              "''",
+				expectedErrorCount: 1,
+				shouldContainError: "exactly one character",
+			},
+			{
+				name:               "Char literal with two characters",
+				input:              "'ab'",
+				expectedErrorCount: 1,
+				shouldContainError: "exactly one character",
+			},
+			{
+				name:               "Unterminated char literal",
+				input:              "'a",
+				expectedErrorCount: 1,
+				shouldContainError: "Unterminated character literal",
+			},
+			{
+				name:               "Lone bang",
+				input:              "!",
+				expectedErrorCount: 1,
+				shouldContainError: "unexpected character",
+			},
+			{
+				name:               "Integer past the minimum's magnitude",
+				input:              "let x = 9223372036854775809",
+				expectedErrorCount: 1,
+				shouldContainError: "integer literal out of range for int (max 9223372036854775807)",
+			},
+			{
+				name:               "Integer beyond 64 bits",
+				input:              "99999999999999999999",
+				expectedErrorCount: 1,
+				shouldContainError: "integer literal out of range for int",
+			},
+			{
+				name:               "Negative integer one past the minimum",
+				input:              "-9223372036854775809",
+				expectedErrorCount: 1,
+				shouldContainError: "integer literal out of range for int",
+			},
 		}
 
 		for _, testCase := range tests {
@@ -445,6 +695,310 @@ func TestLexer(t *testing.T) {
 		}
 	})
 
+	t.Run("Test every invalid character is reported", func(t *testing.T) {
+		input := "let € = 1 $ 2\n§"
+		errorCollector := errors.New(input, "test.navi")
+		lexerInstance := New(input, "test.navi", errorCollector)
+
+		illegal := []token.Token{}
+		for {
+			tok := lexerInstance.NextToken()
+			if tok.Type == token.EOF {
+				break
+			}
+			if tok.Type == token.ILLEGAL {
+				illegal = append(illegal, tok)
+			}
+		}
+
+		// A multi-byte character is one token and one error, not one per byte
+		if assert.Len(t, illegal, 3) {
+			assert.Equal(t, "€", illegal[0].Value)
+			assert.Equal(t, "$", illegal[1].Value)
+			assert.Equal(t, "§", illegal[2].Value)
+			assert.Equal(t, "€", input[illegal[0].Offset:illegal[0].End])
+		}
+
+		if assert.Len(t, errorCollector.Errors(), 3) {
+			first := errorCollector.Errors()[0]
+			assert.Equal(t, "unexpected character '€'", first.Message)
+			assert.Equal(t, 1, first.Line)
+			assert.Equal(t, 5, first.Column)
+
+			last := errorCollector.Errors()[2]
+			assert.Equal(t, "unexpected character '§'", last.Message)
+			assert.Equal(t, 2, last.Line)
+			assert.Equal(t, 1, last.Column)
+		}
+	})
+
+	t.Run("Test integer literal range", func(t *testing.T) {
+		t.Run("Boundaries are accepted", func(t *testing.T) {
+			// 9223372036854775808 only fits after a minus, which the parser checks
+			for _, input := range []string{"9223372036854775807", "-9223372036854775808", "9223372036854775808", "0"} {
+				errorCollector := errors.New(input, "test.navi")
+				lexerInstance := New(input, "test.navi", errorCollector)
+				for lexerInstance.NextToken().Type != token.EOF {
+				}
+
+				assert.False(t, errorCollector.HasErrors(), "Lexer should accept %s", input)
+			}
+		})
+
+		t.Run("Overflow is reported on the literal", func(t *testing.T) {
+			input := "let x = 9223372036854775809"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				overflow := errorCollector.Errors()[0]
+				assert.Equal(t, "integer literal out of range for int (max 9223372036854775807)", overflow.Message)
+				assert.Equal(t, 9, overflow.Column)
+				assert.Equal(t, 19, overflow.Length)
+			}
+		})
+	})
+
+	t.Run("Test prefixed integer literals", func(t *testing.T) {
+		t.Run("Values are converted to decimal", func(t *testing.T) {
+			tests := []struct {
+				input    string
+				expected string
+			}{
+				{"0xFF", "255"},
+				{"0xff", "255"},
+				{"0XfF", "255"},
+				{"0o755", "493"},
+				{"0O17", "15"},
+				{"0b1010", "10"},
+				{"0B1", "1"},
+				{"0x0", "0"},
+				{"0x7FFFFFFFFFFFFFFF", "9223372036854775807"},
+				{"0b111111111111111111111111111111111111111111111111111111111111111", "9223372036854775807"},
+				{"0xFF_FF", "65535"},
+				{"0b1010_1010", "170"},
+				{"9_223_372_036_854_775_807", "9223372036854775807"},
+			}
+
+			for _, testCase := range tests {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				tok := New(testCase.input, "test.navi", errorCollector).NextToken()
+
+				assert.False(t, errorCollector.HasErrors(), "Lexer should accept %s", testCase.input)
+				assert.Equal(t, token.INT_LITERAL, tok.Type)
+				assert.Equal(t, testCase.expected, tok.Value, "value of %s", testCase.input)
+				assert.Equal(t, testCase.input, testCase.input[tok.Offset:tok.End], "range of %s", testCase.input)
+			}
+		})
+
+		t.Run("Minimum int after a minus", func(t *testing.T) {
+			input := "-0x8000000000000000"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+
+			assert.False(t, errorCollector.HasErrors())
+		})
+
+		t.Run("Invalid literals are reported", func(t *testing.T) {
+			tests := []struct {
+				input    string
+				expected string
+			}{
+				{"0x;", "integer literal 0x has no digits"},
+				{"0b", "integer literal 0b has no digits"},
+				{"0b102", "Invalid number format: 0b102"},
+				{"0o8", "Invalid number format: 0o8"},
+				{"0xFG", "Invalid number format: 0xFG"},
+				{"0x8000000000000001", "integer literal out of range for int (max 9223372036854775807)"},
+				{"0x10000000000000000", "integer literal out of range for int (max 9223372036854775807)"},
+				{"100_", "misplaced '_' in number literal 100_: underscores must separate digits"},
+				{"1__0", "misplaced '_' in number literal 1__0: underscores must separate digits"},
+				{"0x_FF", "misplaced '_' in number literal 0x_FF: underscores must separate digits"},
+				{"0b1_", "misplaced '_' in number literal 0b1_: underscores must separate digits"},
+				{"9_223_372_036_854_775_809", "integer literal out of range for int (max 9223372036854775807)"},
+			}
+
+			for _, testCase := range tests {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+				for lexerInstance.NextToken().Type != token.EOF {
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1, "errors for %s", testCase.input) {
+					assert.Equal(t, testCase.expected, errorCollector.Errors()[0].Message)
+					assert.Equal(t, 1, errorCollector.Errors()[0].Column)
+				}
+			}
+		})
+	})
+
+	t.Run("Test unterminated string literals", func(t *testing.T) {
+		tests := []struct {
+			name           string
+			input          string
+			expectedLine   int
+			expectedColumn int
+		}{
+			{"At end of file", "print(\"hello)", 1, 7},
+			{"Before a newline", "func main() {\n  print(\"hello)\n  print(1)\n}", 2, 9},
+			{"After an escaped quote", "let s = \"say \\\"hi\\\"", 1, 9},
+			{"Backslash at end of line", "let s = \"a\\\nlet t = 1", 1, 9},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+				for lexerInstance.NextToken().Type != token.EOF {
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					unterminated := errorCollector.Errors()[0]
+					assert.Equal(t, fmt.Sprintf("unterminated string literal starting at line %d, column %d",
+						testCase.expectedLine, testCase.expectedColumn), unterminated.Message)
+					assert.Equal(t, testCase.expectedLine, unterminated.Line)
+					assert.Equal(t, testCase.expectedColumn, unterminated.Column)
+				}
+			})
+		}
+
+		t.Run("The next line is lexed normally", func(t *testing.T) {
+			input := "\"open\nlet x = 1"
+			lexerInstance := New(input, "test.navi", errors.New(input, "test.navi"))
+
+			assert.Equal(t, token.STRING_LITERAL, lexerInstance.NextToken().Type)
+			assert.Equal(t, token.NEW_LINE, lexerInstance.NextToken().Type)
+			assert.Equal(t, token.LET, lexerInstance.NextToken().Type)
+		})
+
+		t.Run("Escaped quotes terminate properly", func(t *testing.T) {
+			input := "\"say \\\"hi\\\"\" \"\\\\\""
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+
+			assert.Equal(t, "say \"hi\"", lexerInstance.NextToken().Value)
+			assert.Equal(t, "\\", lexerInstance.NextToken().Value)
+			assert.False(t, errorCollector.HasErrors())
+		})
+	})
+
+	t.Run("Test triple-quoted strings", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Spans lines", "\"\"\"first\n  second\nthird\"\"\"", "first\n  second\nthird"},
+			{"Single quote inside", "\"\"\"say \"hi\" and 'bye'\"\"\"", "say \"hi\" and 'bye'"},
+			{"Two quotes inside", "\"\"\"a\"\"b\"\"\"", "a\"\"b"},
+			{"Escapes are decoded", "\"\"\"a\\tb \\\"\"\" c\"\"\"", "a\tb \"\"\" c"},
+			{"Empty", "\"\"\"\"\"\"", ""},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+
+				tok := lexerInstance.NextToken()
+				assert.Equal(t, token.STRING_LITERAL, tok.Type)
+				assert.Equal(t, testCase.expected, tok.Value)
+				assert.Equal(t, testCase.input, testCase.input[tok.Offset:tok.End])
+				assert.Equal(t, token.EOF, lexerInstance.NextToken().Type)
+				assert.False(t, errorCollector.HasErrors())
+			})
+		}
+
+		t.Run("Lines after the literal are counted", func(t *testing.T) {
+			input := "let s = \"\"\"a\nb\"\"\"\nlet t = 1"
+			lexerInstance := New(input, "test.navi", errors.New(input, "test.navi"))
+
+			tokens := []token.Token{}
+			for tok := lexerInstance.NextToken(); tok.Type != token.EOF; tok = lexerInstance.NextToken() {
+				tokens = append(tokens, tok)
+			}
+
+			assert.Equal(t, 1, tokens[3].Line)
+			assert.Equal(t, token.NEW_LINE, tokens[4].Type)
+			assert.Equal(t, token.LET, tokens[5].Type)
+			assert.Equal(t, 3, tokens[5].Line)
+		})
+
+		t.Run("Unterminated triple-quoted strings are reported where they start", func(t *testing.T) {
+			input := "func main() {\n  print(\"\"\"open\n\"\")\n}"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				unterminated := errorCollector.Errors()[0]
+				assert.Equal(t, "unterminated triple-quoted string starting at line 2, column 9", unterminated.Message)
+				assert.Equal(t, 2, unterminated.Line)
+				assert.Equal(t, 9, unterminated.Column)
+			}
+		})
+	})
+
+	t.Run("Test raw string literals", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Backslashes are literal", `r"C:\temp\x"`, `C:\temp\x`},
+			{"Escape sequences are not translated", `r"a\nb\t"`, `a\nb\t`},
+			{"Empty", `r""`, ""},
+			{"Quotes inside hashes", `r#"say "hi""#`, `say "hi"`},
+			{"A shorter closer stays in the body", `r##"a"#b"##`, `a"#b`},
+			{"Trailing backslash", `r"dir\"`, `dir\`},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+
+				tok := lexerInstance.NextToken()
+				assert.Equal(t, token.STRING_LITERAL, tok.Type)
+				assert.Equal(t, testCase.expected, tok.Value)
+				assert.Equal(t, testCase.input, testCase.input[tok.Offset:tok.End])
+				assert.Equal(t, token.EOF, lexerInstance.NextToken().Type)
+				assert.False(t, errorCollector.HasErrors())
+			})
+		}
+
+		t.Run("An r not followed by a quote is an identifier", func(t *testing.T) {
+			input := "r rx r(1)"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+
+			for _, expected := range []token.TokenType{token.IDENTIFIER, token.IDENTIFIER, token.IDENTIFIER, token.LEFT_PAREN} {
+				assert.Equal(t, expected, lexerInstance.NextToken().Type)
+			}
+			assert.False(t, errorCollector.HasErrors())
+		})
+
+		t.Run("Unterminated raw strings are reported at the r", func(t *testing.T) {
+			input := "let p = r#\"a\"\nlet q = 1"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				unterminated := errorCollector.Errors()[0]
+				assert.Equal(t, "unterminated raw string literal starting at line 1, column 9", unterminated.Message)
+				assert.Equal(t, 1, unterminated.Line)
+				assert.Equal(t, 9, unterminated.Column)
+			}
+		})
+	})
+
 	t.Run("Test whitespace handling", func(t *testing.T) {
 		tests := []multipleTokenTestCase{
 			{
@@ -565,6 +1119,146 @@ func TestLexer(t *testing.T) {
 			})
 		}
 	})
+	t.Run("Test comments", func(t *testing.T) {
+		tests := []multipleTokenTestCase{
+			{
+				name:  "Line comment keeps its newline",
+				input: "let x = 1 // one\nx",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.LET, "let"},
+					{token.IDENTIFIER, "x"},
+					{token.ASSIGN, "="},
+					{token.INT_LITERAL, "1"},
+					{token.NEW_LINE, "\n"},
+					{token.IDENTIFIER, "x"},
+					{token.EOF, ""},
+				},
+			},
+			{
+				name:  "Block comment between tokens",
+				input: "let /* hi */ x = 1",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.LET, "let"},
+					{token.IDENTIFIER, "x"},
+					{token.ASSIGN, "="},
+					{token.INT_LITERAL, "1"},
+					{token.EOF, ""},
+				},
+			},
+			{
+				name:  "Block comment spanning lines",
+				input: "func main() {\n  /* first\n  second */\n  print(1)\n}",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.FUNC, "func"},
+					{token.IDENTIFIER, "main"},
+					{token.LEFT_PAREN, "("},
+					{token.RIGHT_PAREN, ")"},
+					{token.LEFT_BRACE, "{"},
+					{token.NEW_LINE, "\n"},
+					{token.NEW_LINE, "\n"},
+					{token.IDENTIFIER, "print"},
+					{token.LEFT_PAREN, "("},
+					{token.INT_LITERAL, "1"},
+					{token.RIGHT_PAREN, ")"},
+					{token.NEW_LINE, "\n"},
+					{token.RIGHT_BRACE, "}"},
+					{token.EOF, ""},
+				},
+			},
+			{
+				name:  "Nested block comments",
+				input: "1 /* a /* b */ c */ 2",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.INT_LITERAL, "1"},
+					{token.INT_LITERAL, "2"},
+					{token.EOF, ""},
+				},
+			},
+			{
+				name:  "Division is not a comment",
+				input: "a / b /c",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.IDENTIFIER, "a"},
+					{token.SLASH, "/"},
+					{token.IDENTIFIER, "b"},
+					{token.SLASH, "/"},
+					{token.IDENTIFIER, "c"},
+					{token.EOF, ""},
+				},
+			},
+			{
+				name:  "Comment markers inside strings",
+				input: "\"// /* x\" 1",
+				expectedTokens: []struct {
+					tokenType  token.TokenType
+					tokenValue string
+				}{
+					{token.STRING_LITERAL, "// /* x"},
+					{token.INT_LITERAL, "1"},
+					{token.EOF, ""},
+				},
+			},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+
+				for index, expected := range testCase.expectedTokens {
+					tok := lexerInstance.NextToken()
+
+					assert.Equal(t, expected.tokenType, tok.Type,
+						"Token %d: type mismatch", index)
+					assert.Equal(t, expected.tokenValue, tok.Value,
+						"Token %d: value mismatch", index)
+				}
+
+				assert.False(t, errorCollector.HasErrors())
+			})
+		}
+
+		t.Run("Positions after a multi-line comment", func(t *testing.T) {
+			input := "/* a\nb */ x"
+			lexerInstance := New(input, "test.navi", errors.New(input, "test.navi"))
+
+			tok := lexerInstance.NextToken()
+			assert.Equal(t, token.IDENTIFIER, tok.Type)
+			assert.Equal(t, 2, tok.Line)
+			assert.Equal(t, 6, tok.Column)
+		})
+
+		t.Run("Unterminated block comment", func(t *testing.T) {
+			input := "let x = 1\n  /* open /* nested */\nlet y = 2"
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := New(input, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				unterminated := errorCollector.Errors()[0]
+				assert.Equal(t, "unterminated block comment starting at line 2", unterminated.Message)
+				assert.Equal(t, 2, unterminated.Line)
+				assert.Equal(t, 3, unterminated.Column)
+			}
+		})
+	})
+
 	t.Run("Test token position", func(t *testing.T) {
 		tests := []struct {
 			name           string
@@ -692,4 +1386,88 @@ func TestLexer(t *testing.T) {
 			})
 		}
 	})
+
+	t.Run("Test token byte ranges", func(t *testing.T) {
+		input := "func f() {\n\tlet s = \"a\\n\" -> 'b'\n}"
+		tests := []struct {
+			tokenType token.TokenType
+			text      string
+			line      int
+			column    int
+		}{
+			{token.FUNC, "func", 1, 1},
+			{token.IDENTIFIER, "f", 1, 6},
+			{token.LEFT_PAREN, "(", 1, 7},
+			{token.RIGHT_PAREN, ")", 1, 8},
+			{token.LEFT_BRACE, "{", 1, 10},
+			{token.NEW_LINE, "\n", 1, 11},
+			{token.LET, "let", 2, 2},
+			{token.IDENTIFIER, "s", 2, 6},
+			{token.ASSIGN, "=", 2, 8},
+			{token.STRING_LITERAL, "\"a\\n\"", 2, 10},
+			{token.ARROW, "->", 2, 16},
+			{token.CHAR_LITERAL, "'b'", 2, 19},
+			{token.NEW_LINE, "\n", 2, 22},
+			{token.RIGHT_BRACE, "}", 3, 1},
+			{token.EOF, "", 3, 2},
+		}
+
+		errorCollector := errors.New(input, "test.navi")
+		lexerInstance := New(input, "test.navi", errorCollector)
+
+		for index, expected := range tests {
+			tok := lexerInstance.NextToken()
+
+			assert.Equal(t, expected.tokenType, tok.Type, "Token %d: type mismatch", index)
+			assert.Equal(t, expected.text, input[tok.Offset:tok.End], "Token %d: source range mismatch", index)
+			assert.Equal(t, expected.line, tok.Line, "Token %d: line mismatch", index)
+			assert.Equal(t, expected.column, tok.Column, "Token %d: column mismatch", index)
+		}
+		assert.False(t, errorCollector.HasErrors())
+	})
+
+	t.Run("Test lexing without escapes does not allocate per token", func(t *testing.T) {
+		source := generateSource(50)
+		errorCollector := errors.New(source, "test.navi")
+
+		allocations := testing.AllocsPerRun(10, func() {
+			lexerInstance := New(source, "test.navi", errorCollector)
+			for lexerInstance.NextToken().Type != token.EOF {
+			}
+		})
+
+		// The only allocation is the lexer itself; token values slice the source
+		assert.LessOrEqual(t, allocations, 1.0)
+		assert.False(t, errorCollector.HasErrors())
+	})
+}
+
+func BenchmarkLexer(b *testing.B) {
+	source := generateSource(2000)
+	errorCollector := errors.New(source, "bench.navi")
+
+	b.ReportAllocs()
+	b.SetBytes(int64(len(source)))
+	b.ResetTimer()
+
+	for i := 0; i < b.N; i++ {
+		lexerInstance := New(source, "bench.navi", errorCollector)
+		for lexerInstance.NextToken().Type != token.EOF {
+		}
+	}
+}
+
+// generateSource builds a program with the given number of functions
+// that exercises every token kind without escape sequences
+func generateSource(functions int) string {
+	var builder strings.Builder
+	for i := 0; i < functions; i++ {
+		fmt.Fprintf(&builder, "@inline\nfunc compute_%d(value: int, flag: bool) -> int {\n", i)
+		builder.WriteString("  let total: int = value * 2 + 10 / 5 - 1\n")
+		builder.WriteString("  let same = total == value != flag <= total >= value < 3 > 4\n")
+		builder.WriteString("  let name := \"naviary\"\n")
+		builder.WriteString("  let letter = 'n' as char\n")
+		fmt.Fprintf(&builder, "  print(name, letter, true, false);\n  return compute_%d(total, flag)\n}\n", i)
+	}
+	return builder.String()
 }
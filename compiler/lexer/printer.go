@@ -0,0 +1,112 @@
+package lexer
+
+import (
+	"compiler/token"
+	"strings"
+)
+
+// Print writes tokens back as source text that lexes to the same tokens
+// Literals are written in canonical form: integers in decimal without separators,
+// strings and chars with the escapes the lexer decodes, so 0x1F prints as 31
+// A space is placed only between tokens that would otherwise lex as one
+func Print(tokens []token.Token) string {
+	var builder strings.Builder
+	previous := ""
+
+	for _, t := range tokens {
+		if t.Type == token.EOF {
+			break
+		}
+
+		text := TokenSource(t)
+		if needsSeparator(previous, text) {
+			builder.WriteByte(' ')
+		}
+		builder.WriteString(text)
+		previous = text
+	}
+
+	return builder.String()
+}
+
+// TokenSource returns the canonical source text of one token
+func TokenSource(t token.Token) string {
+	switch t.Type {
+	case token.STRING_LITERAL:
+		return quoteLiteral(t.Value, '"')
+	case token.CHAR_LITERAL:
+		return quoteLiteral(t.Value, '\'')
+	case token.INT_LITERAL, token.IDENTIFIER, token.ILLEGAL:
+		return t.Value
+	case token.NEW_LINE:
+		return "\n"
+	case token.EOF:
+		return ""
+	default:
+		return t.Type.String()
+	}
+}
+
+// quoteLiteral wraps a decoded literal value in quote, escaping what the lexer would not read back
+func quoteLiteral(value string, quote byte) string {
+	var builder strings.Builder
+	builder.WriteByte(quote)
+
+	for i := 0; i < len(value); i++ {
+		switch value[i] {
+		case '\n':
+			builder.WriteString(`\n`)
+		case '\t':
+			builder.WriteString(`\t`)
+		case '\r':
+			builder.WriteString(`\r`)
+		case 0:
+			builder.WriteString(`\0`)
+		case '\\':
+			builder.WriteString(`\\`)
+		case quote:
+			builder.WriteByte('\\')
+			builder.WriteByte(quote)
+		default:
+			builder.WriteByte(value[i])
+		}
+	}
+
+	builder.WriteByte(quote)
+	return builder.String()
+}
+
+// mergingPairs are the character pairs the lexer reads as one token or as a comment start
+var mergingPairs = map[string]bool{
+	"==": true, "!=": true, "<=": true, ">=": true,
+	"->": true, ":=": true, "//": true, "/*": true,
+	"..": true,
+}
+
+// needsSeparator reports whether next written right after previous would lex differently
+func needsSeparator(previous, next string) bool {
+	if previous == "" || next == "" {
+		return false
+	}
+
+	last, first := previous[len(previous)-1], next[0]
+	if isWordCharacter(last) && isWordCharacter(first) {
+		return true
+	}
+	if previous == "r" && first == '"' {
+		return true // r"..." is a raw string
+	}
+	if last == '"' && first == '"' {
+		return true // "" followed by a string would open """
+	}
+	if previous == ".." && first == '=' {
+		return true // ..= is the inclusive range
+	}
+
+	return mergingPairs[string([]byte{last, first})]
+}
+
+// isWordCharacter checks if a character can continue an identifier or a number
+func isWordCharacter(char byte) bool {
+	return isLetter(char) || isDigit(char)
+}
@@ -0,0 +1,204 @@
+package lexer
+
+import (
+	"compiler/errors"
+	"compiler/token"
+	"math"
+	"math/rand"
+	"reflect"
+	"strconv"
+	"testing"
+	"testing/quick"
+
+	"github.com/stretchr/testify/assert"
+)
+
+// lexedToken is the part of a token a roundtrip must preserve
+type lexedToken struct {
+	Type  token.TokenType
+	Value string
+}
+
+// lexAll lexes input up to EOF and reports whether it produced any errors
+func lexAll(input string) ([]lexedToken, bool) {
+	errorCollector := errors.New(input, "test.navi")
+	lexerInstance := New(input, "test.navi", errorCollector)
+
+	tokens := []lexedToken{}
+	for {
+		t := lexerInstance.NextToken()
+		if t.Type == token.EOF {
+			break
+		}
+		tokens = append(tokens, lexedToken{t.Type, t.Value})
+	}
+
+	return tokens, !errorCollector.HasErrors()
+}
+
+// tokenSequence is a random list of valid tokens for testing/quick
+type tokenSequence []token.Token
+
+// fixedTokens are every keyword, operator and delimiter, whose value is their text
+var fixedTokens = func() []token.Token {
+	tokens := []token.Token{}
+	for tokenType := token.LET; tokenType <= token.NEW_LINE; tokenType++ {
+		text := TokenSource(token.Token{Type: tokenType})
+		if text == "" {
+			continue // markers such as the operator range bounds
+		}
+		tokens = append(tokens, token.Token{Type: tokenType, Value: text})
+	}
+	return tokens
+}()
+
+// literalRunes are the characters string and char values are drawn from,
+// weighted towards the ones that need escaping or look like other tokens
+var literalRunes = []rune("ab Z09_\"'\\\n\t\r\x00/*-.=é€")
+
+func (sequence tokenSequence) Generate(random *rand.Rand, size int) reflect.Value {
+	tokens := make(tokenSequence, random.Intn(size+1))
+	for i := range tokens {
+		tokens[i] = randomToken(random)
+	}
+	return reflect.ValueOf(tokens)
+}
+
+func randomToken(random *rand.Rand) token.Token {
+	switch random.Intn(6) {
+	case 0:
+		return token.Token{Type: token.IDENTIFIER, Value: randomIdentifier(random)}
+	case 1:
+		return token.Token{Type: token.INT_LITERAL, Value: randomInteger(random)}
+	case 2:
+		return token.Token{Type: token.STRING_LITERAL, Value: randomText(random, random.Intn(6))}
+	case 3:
+		return token.Token{Type: token.CHAR_LITERAL, Value: randomText(random, 1)}
+	default:
+		return fixedTokens[random.Intn(len(fixedTokens))]
+	}
+}
+
+func randomIdentifier(random *rand.Rand) string {
+	const first = "abcrxyzXY_"
+	const rest = first + "019"
+
+	for {
+		name := []byte{first[random.Intn(len(first))]}
+		for length := random.Intn(5); length > 0; length-- {
+			name = append(name, rest[random.Intn(len(rest))])
+		}
+		if token.LookupIdentifier(string(name)) == token.IDENTIFIER {
+			return string(name)
+		}
+	}
+}
+
+func randomInteger(random *rand.Rand) string {
+	switch random.Intn(4) {
+	case 0:
+		return strconv.Itoa(random.Intn(10))
+	case 1:
+		return strconv.FormatInt(math.MaxInt64, 10)
+	default:
+		return strconv.FormatInt(random.Int63(), 10)
+	}
+}
+
+func randomText(random *rand.Rand, length int) string {
+	text := make([]rune, length)
+	for i := range text {
+		text[i] = literalRunes[random.Intn(len(literalRunes))]
+	}
+	return string(text)
+}
+
+func TestPrinter(t *testing.T) {
+	t.Run("Printed tokens lex back to the same tokens", func(t *testing.T) {
+		roundtrip := func(sequence tokenSequence) bool {
+			expected := []lexedToken{}
+			for _, item := range sequence {
+				expected = append(expected, lexedToken{item.Type, item.Value})
+			}
+
+			actual, ok := lexAll(Print(sequence))
+			return ok && reflect.DeepEqual(expected, actual)
+		}
+
+		err := quick.Check(roundtrip, &quick.Config{MaxCount: 2000})
+		assert.NoError(t, err)
+	})
+
+	t.Run("Test numeric edge cases", func(t *testing.T) {
+		// There are no float literals yet: 1.5 is three tokens,
+		// so adding them must update these cases deliberately
+		tests := []struct {
+			input     string
+			canonical string
+		}{
+			{"1-2", "1-2"},
+			{"x-1", "x-1"},
+			{"x - -1", "x--1"},
+			{"-9223372036854775807", "-9223372036854775807"},
+			{"0x1F-0b1", "31-1"},
+			{"0o17 0", "15 0"},
+			{"1_000_000->x", "1000000->x"},
+			{"1.5", "1.5"},
+			{"1..2", "1..2"},
+			{"1..=2", "1..=2"},
+			{"1 .. =2", "1.. =2"},
+			{"a . . b", "a. .b"},
+			{"a . ..= b", "a. ..=b"},
+			{"a.b.0", "a.b.0"},
+			{"1 /* c */ / 2", "1/2"},
+			{"a / / b", "a/ /b"},
+			{"a / *b", "a/ *b"},
+			{"x = = 1", "x= =1"},
+			{"x < = 1", "x< =1"},
+			{"a : = 1", "a: =1"},
+			{"r \"a\"", "r \"a\""},
+			{"\"\" \"a\"", "\"\" \"a\""},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.input, func(t *testing.T) {
+				errorCollector := errors.New(testCase.input, "test.navi")
+				lexerInstance := New(testCase.input, "test.navi", errorCollector)
+				tokens := []token.Token{}
+				for {
+					next := lexerInstance.NextToken()
+					tokens = append(tokens, next)
+					if next.Type == token.EOF {
+						break
+					}
+				}
+				assert.False(t, errorCollector.HasErrors())
+
+				printed := Print(tokens)
+				assert.Equal(t, testCase.canonical, printed)
+
+				original, _ := lexAll(testCase.input)
+				reprinted, ok := lexAll(printed)
+				assert.True(t, ok)
+				assert.Equal(t, original, reprinted)
+			})
+		}
+	})
+
+	t.Run("Test literal escapes", func(t *testing.T) {
+		tests := []struct {
+			token    token.Token
+			expected string
+		}{
+			{token.Token{Type: token.STRING_LITERAL, Value: "a\"b\\c"}, `"a\"b\\c"`},
+			{token.Token{Type: token.STRING_LITERAL, Value: "\t'\n\r\x00"}, `"\t'\n\r\0"`},
+			{token.Token{Type: token.CHAR_LITERAL, Value: "'"}, `'\''`},
+			{token.Token{Type: token.CHAR_LITERAL, Value: "\""}, `'"'`},
+			{token.Token{Type: token.CHAR_LITERAL, Value: "€"}, `'€'`},
+		}
+
+		for _, testCase := range tests {
+			assert.Equal(t, testCase.expected, TokenSource(testCase.token))
+		}
+	})
+}
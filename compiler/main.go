@@ -1,78 +1,371 @@
 package main
 
 import (
+	"compiler/ast"
+	"compiler/cache"
 	"compiler/codegen/llvm"
 	"compiler/constants"
 	"compiler/errors"
 	"compiler/lexer"
 	"compiler/nir"
 	"compiler/parser"
+	"compiler/prelude"
+	"compiler/symbols"
+	"compiler/testrunner"
+	"compiler/timing"
+	"compiler/toolchain"
+	goerrors "errors"
 	"fmt"
 	"os"
 	"path/filepath"
 	"strings"
+	"unicode/utf8"
 )
 
-// CompileFile compiles a single Naviary source file
-func CompileFile(inputPath string, runAfterCompile bool) error {
-	sourceCode, err := os.ReadFile(inputPath)
+// CompileOptions controls optional behavior of the compile pipeline
+type CompileOptions struct {
+	Run         bool
+	Timings     string // timing.FormatTable, timing.FormatJSON or "" to disable
+	NoCache     bool
+	TailCalls   bool
+	NoPrelude   bool
+	VerifyTypes bool // debugging aid that checks codegen against the lowerer's types
+	NoPIE       bool
+	CC          string // C compiler used for linking; empty searches for one
+	MaxErrors   int    // diagnostics shown before the rest are summarized; 0 uses the default
+	Report      string // symbols.FormatTable, symbols.FormatJSON or "" to disable
+	Entry       string // function run in place of main; empty runs main
+	Color       string // COLOR_ALWAYS, COLOR_NEVER, or COLOR_AUTO (also when empty)
+}
+
+const (
+	COLOR_AUTO   = "auto"
+	COLOR_ALWAYS = "always"
+	COLOR_NEVER  = "never"
+)
+
+// useColor decides whether diagnostics use ANSI colors
+// An explicit --color wins; otherwise NO_COLOR turns colors off, and they are
+// only used when stderr, where diagnostics go, is a terminal
+func (options CompileOptions) useColor() bool {
+	switch options.Color {
+	case COLOR_ALWAYS:
+		return true
+	case COLOR_NEVER:
+		return false
+	}
+
+	if os.Getenv("NO_COLOR") != "" {
+		return false
+	}
+
+	info, err := os.Stderr.Stat()
+	return err == nil && info.Mode()&os.ModeCharDevice != 0
+}
+
+// cacheFlags lists the options that change the generated IR and must be part of the cache key
+// --verify-types is included so IR cached without verification is not reused when it is asked for
+func (options CompileOptions) cacheFlags() []string {
+	flags := []string{}
+	if options.TailCalls {
+		flags = append(flags, "--tail-calls")
+	}
+	if options.NoPrelude {
+		flags = append(flags, "--no-prelude")
+	}
+	if options.VerifyTypes {
+		flags = append(flags, "--verify-types")
+	}
+	if options.Entry != "" {
+		flags = append(flags, "--entry="+options.Entry)
+	}
+	return flags
+}
+
+// codegenOptions returns the code generation settings selected by the driver options
+func (options CompileOptions) codegenOptions() llvm.Options {
+	return llvm.Options{
+		TailCalls:   options.TailCalls,
+		VerifyTypes: options.VerifyTypes,
+	}
+}
+
+// newErrorCollector creates an error collector that displays as many errors as the options allow
+func (options CompileOptions) newErrorCollector(source string, fileName string) *errors.ErrorCollector {
+	errorCollector := errors.New(source, fileName)
+	errorCollector.SetColor(options.useColor())
+	if options.MaxErrors > 0 {
+		errorCollector.SetDisplayLimit(options.MaxErrors)
+	}
+	return errorCollector
+}
+
+// linkOptions returns the link settings selected by the driver options
+func (options CompileOptions) linkOptions() toolchain.LinkOptions {
+	// Tail calls only reuse the caller's frame in optimized code
+	return toolchain.LinkOptions{
+		NoPIE:    options.NoPIE,
+		CC:       options.CC,
+		Optimize: options.TailCalls,
+	}
+}
+
+// CompileFile compiles a single Naviary source file, and links it when the program is to be run
+func CompileFile(inputPath string, options CompileOptions) (err error) {
+	timings := timing.New()
+	defer reportTimings(timings, options.Timings, &err)
+
+	if err := compile(inputPath, options, timings); err != nil {
+		return err
+	}
+	if options.Run {
+		basePath := strings.TrimSuffix(inputPath, constants.NAVIARY_EXTENSION)
+		return linkProgram(basePath+".ll", basePath, options.linkOptions(), timings)
+	}
+	return nil
+}
+
+// reportTimings writes the timing report to stderr when a format was asked for
+// A report that cannot be written fails the command, unless it has already failed
+func reportTimings(timings *timing.Report, format string, err *error) {
+	if format == "" {
+		return
+	}
+
+	if writeErr := timings.Write(os.Stderr, format); writeErr != nil && *err == nil {
+		*err = writeErr
+	}
+}
+
+func compile(inputPath string, options CompileOptions, timings *timing.Report) error {
+	sourceStr, err := readSource(inputPath)
 	if err != nil {
-		return fmt.Errorf("failed to read file %s: %v", inputPath, err)
+		return err
 	}
 
 	fileName := filepath.Base(inputPath)
-	sourceStr := string(sourceCode)
+	outputPath := strings.TrimSuffix(inputPath, constants.NAVIARY_EXTENSION) + ".ll"
 
-	// Create global error collector with source code
-	errorCollector := errors.New(sourceStr, fileName)
+	compileCache := cache.New(filepath.Join(filepath.Dir(inputPath), constants.CACHE_DIRECTORY))
+	cacheKey := cache.Key(sourceStr, options.cacheFlags()...)
+
+	// The symbol report needs the NIR and LLVM modules, which a cached build never creates
+	if !options.NoCache && options.Report == "" {
+		if llvmIR, ok := compileCache.Lookup(cacheKey); ok {
+			fmt.Printf("%s is unchanged, using cached LLVM IR\n", fileName)
+			return writeOutput(outputPath, llvmIR, timings)
+		}
+	}
 
-	// Step 1: Lexical Analysis
 	fmt.Printf("Compiling %s...\n", fileName)
-	lexerInstance := lexer.New(sourceStr, fileName, errorCollector)
+	program, errorCollector, err := parseSource(fileName, sourceStr, options, timings)
+	if err != nil {
+		return err
+	}
+	if err := requireFunctions(program, inputPath); err != nil {
+		return err
+	}
+
+	program, err = applyEntry(program, options)
+	if err != nil {
+		return err
+	}
+
+	llvmIR, err := generateIR(program, errorCollector, outputPath, options, timings)
+	if err != nil {
+		return err
+	}
+
+	if !options.NoCache {
+		if err := compileCache.Store(cacheKey, llvmIR); err != nil {
+			fmt.Fprintf(os.Stderr, "Warning: %v\n", err)
+		}
+	}
+
+	// Step 5: LLVM IR to file
+	return writeOutput(outputPath, llvmIR, timings)
+}
+
+// readSource reads a source file, rejecting inputs that cannot be a program
+// before they reach the lexer: directories, bytes that are not UTF-8 and files
+// with nothing but whitespace
+func readSource(inputPath string) (string, error) {
+	info, err := os.Stat(inputPath)
+	if err == nil && info.IsDir() {
+		return "", fmt.Errorf("%s is a directory, not a source file", inputPath)
+	}
+
+	sourceCode, err := os.ReadFile(inputPath)
+	if err != nil {
+		return "", fmt.Errorf("failed to read file %s: %v", inputPath, err)
+	}
+
+	if offset := invalidUTF8Offset(sourceCode); offset >= 0 {
+		return "", fmt.Errorf("%s is not valid UTF-8 (first invalid byte at offset %d)", inputPath, offset)
+	}
+
+	if strings.TrimSpace(string(sourceCode)) == "" {
+		return "", fmt.Errorf("no functions found in %s", inputPath)
+	}
+
+	return string(sourceCode), nil
+}
 
-	// Transfer lexer errors to main collector
+// requireFunctions rejects a parsed file that defines no functions, such as one holding only comments
+// It runs before the prelude is merged, whose functions would otherwise let the file through
+// to fail at link time for want of a main
+func requireFunctions(program *ast.Program, inputPath string) error {
+	for _, statement := range program.Statements {
+		if _, ok := statement.(*ast.FunctionStatement); ok {
+			return nil
+		}
+	}
+
+	return fmt.Errorf("no functions found in %s", inputPath)
+}
+
+// invalidUTF8Offset returns the byte offset of the first invalid UTF-8 sequence, or -1
+func invalidUTF8Offset(source []byte) int {
+	for offset := 0; offset < len(source); {
+		character, size := utf8.DecodeRune(source[offset:])
+		if character == utf8.RuneError && size <= 1 {
+			return offset
+		}
+		offset += size
+	}
+	return -1
+}
+
+// parseSource lexes and parses one source file with its own error collector,
+// displaying any errors
+func parseSource(fileName string, sourceStr string, options CompileOptions, timings *timing.Report) (*ast.Program, *errors.ErrorCollector, error) {
+	// Create global error collector with source code
+	errorCollector := options.newErrorCollector(sourceStr, fileName)
+
+	program := parseProgram(fileName, sourceStr, errorCollector, timings)
 	if errorCollector.HasErrors() {
 		errorCollector.Display()
-		return fmt.Errorf("compilation failed")
+		return nil, nil, fmt.Errorf("compilation failed")
+	}
+
+	return program, errorCollector, nil
+}
+
+// parseProgram lexes and parses source, leaving any errors in errorCollector for the caller
+func parseProgram(fileName string, sourceStr string, errorCollector *errors.ErrorCollector, timings *timing.Report) *ast.Program {
+	// Step 1: Lexical Analysis
+	// The lexer is driven lazily by the parser, so both are timed as one phase
+	doneParsing := timings.Start(timing.PhaseParse)
+	defer doneParsing()
+	lexerInstance := lexer.New(sourceStr, fileName, errorCollector)
+	if errorCollector.HasErrors() {
+		return nil
 	}
 
 	// Step 2: Parsing
-	parserInstance := parser.New(lexerInstance, errorCollector)
-	program := parserInstance.ParseProgram()
+	return parser.New(lexerInstance, errorCollector).ParseProgram()
+}
 
-	// Transfer parser errors to main collector
+// applyEntry makes the function named by --entry the program's main, after checking it can be one
+func applyEntry(program *ast.Program, options CompileOptions) (*ast.Program, error) {
+	if options.Entry == "" {
+		return program, nil
+	}
+	if err := testrunner.CheckEntry(program, options.Entry); err != nil {
+		return nil, err
+	}
+	return testrunner.WithEntry(program, options.Entry), nil
+}
+
+// generateIR lowers a parsed program to NIR and generates LLVM IR from it
+func generateIR(program *ast.Program, errorCollector *errors.ErrorCollector, outputPath string, options CompileOptions, timings *timing.Report) (string, error) {
+	_, nirModule, err := lowerProgram(program, errorCollector, true, options, timings)
+	if err != nil {
+		return "", err
+	}
+
+	return emitIR(nirModule, outputPath, options, timings)
+}
+
+// lowerProgram lowers a parsed program to NIR with lowerModule, displaying any errors
+// The lowerer is returned as well, so entry points can be lowered against the same program;
+// a program whose entry points are added that way need not define main itself
+func lowerProgram(program *ast.Program, errorCollector *errors.ErrorCollector, requireMain bool, options CompileOptions, timings *timing.Report) (*nir.Lowerer, *nir.Module, error) {
+	lowerer, nirModule, err := lowerModule(program, errorCollector, requireMain, options, timings)
 	if errorCollector.HasErrors() {
 		errorCollector.Display()
-		return fmt.Errorf("compilation failed")
+		return nil, nil, fmt.Errorf("lowering failed")
 	}
+	if err != nil {
+		return nil, nil, err
+	}
+	fmt.Println("NIR generation successful!")
+
+	return lowerer, nirModule, nil
+}
 
+// lowerModule merges the prelude into a parsed program and lowers it to NIR
+// Type errors are left in errorCollector for the caller; the error is for a module
+// the lowerer accepted but could not finish
+func lowerModule(program *ast.Program, errorCollector *errors.ErrorCollector, requireMain bool, options CompileOptions, timings *timing.Report) (*nir.Lowerer, *nir.Module, error) {
 	//Step 3: Lower AST to NIR
+	doneLowering := timings.Start(timing.PhaseLower)
+	if !options.NoPrelude {
+		program = prelude.Merge(program, errorCollector)
+	}
 	lowerer := nir.NewLowerer(errorCollector)
 	nirModule := lowerer.Lower(program)
-
-	if errorCollector.HasErrors() {
-		errorCollector.Display()
-		return fmt.Errorf("lowering failed")
+	if requireMain {
+		lowerer.RequireMain()
 	}
+	doneLowering()
 
-	if !nirModule.IsComplete() {
-		return fmt.Errorf("generated NIR module is incomplete")
+	if !errorCollector.HasErrors() && !nirModule.IsComplete() {
+		return nil, nil, fmt.Errorf("generated NIR module is incomplete")
 	}
-	fmt.Println("NIR generation successful!")
 
+	return lowerer, nirModule, nil
+}
+
+// emitIR generates LLVM IR from a lowered module
+func emitIR(nirModule *nir.Module, outputPath string, options CompileOptions, timings *timing.Report) (string, error) {
 	// Step 4: Generate LLVM IR
 	fmt.Println("Generating LLVM IR...")
+	return generateModule(nirModule, outputPath, options, timings)
+}
+
+// generateModule generates LLVM IR from a lowered module with the codegen options the driver options select
+// IR that fails verification is written next to outputPath for inspection
+func generateModule(nirModule *nir.Module, outputPath string, options CompileOptions, timings *timing.Report) (string, error) {
+	doneCodegen := timings.Start(timing.PhaseCodegen)
 	generator := llvm.NewGenerator()
 	defer generator.Dispose()
+	generator.SetOptions(options.codegenOptions())
 
 	llvmIR, err := generator.Generate(nirModule)
+	doneCodegen()
 	if err != nil {
-		return fmt.Errorf("failed to generate LLVM IR: %w", err)
+		var verificationError *llvm.VerificationError
+		if goerrors.As(err, &verificationError) {
+			return "", reportInvalidModule(outputPath, verificationError)
+		}
+		return "", fmt.Errorf("failed to generate LLVM IR: %w", err)
 	}
 
-	// Step 5: LLVM IR to file
-	outputPath := strings.TrimSuffix(inputPath, constants.NAVIARY_EXTENSION) + ".ll"
-	err = os.WriteFile(outputPath, []byte(llvmIR), 0644)
+	if options.Report != "" {
+		report := symbols.Collect(nirModule, generator.InstructionCounts())
+		if err := symbols.Write(os.Stdout, report, options.Report); err != nil {
+			return "", err
+		}
+	}
+
+	return llvmIR, nil
+}
+
+func writeOutput(outputPath string, llvmIR string, timings *timing.Report) error {
+	doneWriting := timings.Start(timing.PhaseWrite)
+	err := os.WriteFile(outputPath, []byte(llvmIR), 0644)
+	doneWriting()
 	if err != nil {
 		return fmt.Errorf("failed to write LLVM IR to file: %w", err)
 	}
@@ -80,21 +373,72 @@ func CompileFile(inputPath string, runAfterCompile bool) error {
 	return nil
 }
 
-func main() {
-	// Parse command line arguments
-	runFlag := false
-	args := os.Args[1:]
+// reportInvalidModule writes IR that failed verification next to the intended output
+// so codegen bugs can be inspected, and returns an error naming the dump
+func reportInvalidModule(outputPath string, verificationError *llvm.VerificationError) error {
+	dumpPath := strings.TrimSuffix(outputPath, ".ll") + ".invalid.ll"
 
-	if len(args) > 0 && args[0] == "run" {
-		runFlag = true
-		args = args[1:]
+	if verificationError.FunctionIR != "" {
+		fmt.Fprintf(os.Stderr, "Offending function %s:\n%s\n", verificationError.Function, verificationError.FunctionIR)
 	}
 
-	if len(args) < 1 {
-		fmt.Printf("Usage: naviary [run] <source_file%s>\n", constants.NAVIARY_EXTENSION)
-		fmt.Printf("  naviary hello%s       # Compile only\n", constants.NAVIARY_EXTENSION)
-		fmt.Printf("  naviary run hello%s   # Compile and run\n", constants.NAVIARY_EXTENSION)
-		os.Exit(1)
+	if err := os.WriteFile(dumpPath, []byte(verificationError.IR), 0644); err != nil {
+		return fmt.Errorf("%w (failed to dump invalid module: %v)", verificationError, err)
+	}
+
+	return fmt.Errorf("%w (invalid module written to %s)", verificationError, dumpPath)
+}
+
+func main() {
+	command, err := ParseArguments(os.Args[1:])
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n\n%s", err, Usage())
+		os.Exit(2)
+	}
+	options := command.Options
+	args := command.Arguments
+
+	switch command.Name {
+	case COMMAND_HELP:
+		fmt.Print(Usage())
+		return
+
+	case COMMAND_VERSION:
+		fmt.Printf("naviary %s\n", constants.COMPILER_VERSION)
+		return
+
+	case COMMAND_NEW:
+		if err := NewProject(args[0]); err != nil {
+			fmt.Printf("Error: %v\n", err)
+			os.Exit(1)
+		}
+		return
+
+	case COMMAND_BUILD:
+		directory := "."
+		if len(args) > 0 {
+			directory = args[0]
+		}
+		if err := BuildProject(directory, options); err != nil {
+			fmt.Printf("Build failed: %v\n", err)
+			os.Exit(1)
+		}
+		fmt.Println("Build successful!")
+		return
+
+	case COMMAND_TEST:
+		if err := RunTests(args[0], options); err != nil {
+			fmt.Printf("Error: %v\n", err)
+			os.Exit(1)
+		}
+		return
+
+	case COMMAND_COMPLETE:
+		if err := CompleteAt(args[0], options, os.Stdout); err != nil {
+			fmt.Printf("Error: %v\n", err)
+			os.Exit(1)
+		}
+		return
 	}
 
 	inputFile := args[0]
@@ -112,10 +456,33 @@ func main() {
 	}
 
 	// Compile the file
-	if err := CompileFile(inputFile, runFlag); err != nil {
+	if err := CompileFile(inputFile, options); err != nil {
 		fmt.Printf("Compilation failed: %v\n", err)
 		os.Exit(1)
 	}
 
 	fmt.Println("Compilation successful!")
+
+	if options.Run {
+		exitCode, err := RunProgram(inputFile)
+		if err != nil {
+			fmt.Printf("Error: %v\n", err)
+			os.Exit(1)
+		}
+		os.Exit(exitCode)
+	}
+}
+
+// linkProgram links llvmPath into the executable binaryPath, timing the link
+func linkProgram(llvmPath string, binaryPath string, linkOptions toolchain.LinkOptions, timings *timing.Report) error {
+	doneLinking := timings.Start(timing.PhaseLink)
+	defer doneLinking()
+
+	return toolchain.Link(llvmPath, binaryPath, linkOptions)
+}
+
+// RunProgram runs the executable linked for inputPath, streaming its output,
+// and returns the program's exit code
+func RunProgram(inputPath string) (int, error) {
+	return toolchain.Run(strings.TrimSuffix(inputPath, constants.NAVIARY_EXTENSION))
 }
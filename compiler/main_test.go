@@ -0,0 +1,1496 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"compiler/codegen/llvm"
+	"compiler/constants"
+	"compiler/errors"
+	"compiler/symbols"
+	"compiler/timing"
+	"compiler/toolchain"
+	"encoding/json"
+	goerrors "errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestReportInvalidModule(t *testing.T) {
+	outputPath := filepath.Join(t.TempDir(), "broken.ll")
+	verificationError := &llvm.VerificationError{
+		Message: "Function return type does not match operand type of return inst!",
+		IR:      "define i64 @main() {\nentry:\n  ret void\n}\n",
+	}
+
+	err := reportInvalidModule(outputPath, verificationError)
+
+	dumpPath := filepath.Join(filepath.Dir(outputPath), "broken.invalid.ll")
+	assert.ErrorContains(t, err, dumpPath)
+
+	dumped, readErr := os.ReadFile(dumpPath)
+	assert.NoError(t, readErr)
+	assert.Equal(t, verificationError.IR, string(dumped))
+}
+
+// recordedPhases returns the phase names recorded in a timing report
+func recordedPhases(report *timing.Report) []string {
+	phases := []string{}
+	for _, entry := range report.Entries() {
+		phases = append(phases, entry.Phase)
+	}
+	return phases
+}
+
+func TestCompileCache(t *testing.T) {
+	inputPath := filepath.Join(t.TempDir(), "cached.navi")
+	assert.NoError(t, os.WriteFile(inputPath, []byte("func main() {\n  print(1)\n}\n"), 0644))
+
+	firstRun := timing.New()
+	assert.NoError(t, compile(inputPath, CompileOptions{}, firstRun))
+	assert.Contains(t, recordedPhases(firstRun), timing.PhaseCodegen)
+
+	firstOutput, err := os.ReadFile(filepath.Join(filepath.Dir(inputPath), "cached.ll"))
+	assert.NoError(t, err)
+
+	secondRun := timing.New()
+	assert.NoError(t, compile(inputPath, CompileOptions{}, secondRun))
+	assert.NotContains(t, recordedPhases(secondRun), timing.PhaseCodegen,
+		"an unchanged file should be served from the cache")
+
+	secondOutput, err := os.ReadFile(filepath.Join(filepath.Dir(inputPath), "cached.ll"))
+	assert.NoError(t, err)
+	assert.Equal(t, string(firstOutput), string(secondOutput))
+
+	uncachedRun := timing.New()
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, uncachedRun))
+	assert.Contains(t, recordedPhases(uncachedRun), timing.PhaseCodegen,
+		"--no-cache should always run codegen")
+}
+
+func TestTimings(t *testing.T) {
+	writeProgram := func(t *testing.T) string {
+		inputPath := filepath.Join(t.TempDir(), "timed.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte("func main() {\n  print(1)\n}\n"), 0644))
+		return inputPath
+	}
+
+	t.Run("Compiling records every compile phase", func(t *testing.T) {
+		report := timing.New()
+		assert.NoError(t, compile(writeProgram(t), CompileOptions{NoCache: true}, report))
+
+		encoded, err := report.JSON()
+		assert.NoError(t, err)
+
+		var decoded map[string]float64
+		assert.NoError(t, json.Unmarshal(encoded, &decoded))
+		for _, phase := range []string{timing.PhaseParse, timing.PhaseLower, timing.PhaseCodegen, timing.PhaseWrite, "total"} {
+			assert.Contains(t, decoded, phase, "missing phase %s", phase)
+		}
+		assert.NotContains(t, decoded, timing.PhaseLink, "nothing was linked")
+	})
+
+	t.Run("Linking is timed after the compile phases", func(t *testing.T) {
+		requireToolchain(t)
+
+		inputPath := writeProgram(t)
+		report := timing.New()
+		assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, report))
+
+		basePath := strings.TrimSuffix(inputPath, ".navi")
+		assert.NoError(t, linkProgram(basePath+".ll", basePath, toolchain.LinkOptions{}, report))
+
+		phases := recordedPhases(report)
+		assert.Equal(t, timing.PhaseLink, phases[len(phases)-1])
+	})
+
+	t.Run("A report that cannot be written fails the compile", func(t *testing.T) {
+		err := CompileFile(writeProgram(t), CompileOptions{NoCache: true, Timings: "xml"})
+
+		assert.ErrorContains(t, err, "unknown timings format: xml")
+	})
+}
+
+func TestBuildProject(t *testing.T) {
+	writeProject := func(t *testing.T, files map[string]string) string {
+		root := t.TempDir()
+		assert.NoError(t, os.MkdirAll(filepath.Join(root, "src"), 0755))
+		assert.NoError(t, os.WriteFile(filepath.Join(root, "naviary.toml"), []byte("[package]\nname = \"demo\"\n"), 0644))
+		for name, content := range files {
+			assert.NoError(t, os.WriteFile(filepath.Join(root, "src", name), []byte(content), 0644))
+		}
+		return root
+	}
+
+	t.Run("Sources are compiled as one program", func(t *testing.T) {
+		root := writeProject(t, map[string]string{
+			"main.navi": "func main() {\n  helper()\n}\n",
+			"util.navi": "func helper() {\n  print(1)\n}\n",
+		})
+
+		assert.NoError(t, BuildProject(root, CompileOptions{}))
+
+		llvmIR, err := os.ReadFile(filepath.Join(root, "target", "demo.ll"))
+		assert.NoError(t, err)
+		assert.Contains(t, string(llvmIR), "define void @helper()")
+		assert.Contains(t, string(llvmIR), "define i64 @main()")
+	})
+
+	t.Run("Duplicate functions across files are rejected", func(t *testing.T) {
+		root := writeProject(t, map[string]string{
+			"main.navi":  "func main() {\n  helper()\n}\nfunc helper() {\n  print(1)\n}\n",
+			"other.navi": "func helper() {\n  print(2)\n}\n",
+		})
+
+		assert.ErrorContains(t, BuildProject(root, CompileOptions{}), "function helper is defined in both")
+	})
+
+	t.Run("Missing entry point is rejected", func(t *testing.T) {
+		root := writeProject(t, map[string]string{
+			"main.navi": "func helper() {\n  print(1)\n}\n",
+		})
+
+		assert.ErrorContains(t, BuildProject(root, CompileOptions{}), "does not define main")
+	})
+}
+
+// requireToolchain skips tests that link and run programs when no C compiler or the runtime is missing
+func requireToolchain(t *testing.T) {
+	t.Helper()
+	if _, err := toolchain.FindCompiler(""); err != nil {
+		t.Skip(err)
+	}
+	if _, err := os.Stat(toolchain.RuntimeLibrary()); err != nil {
+		t.Skipf("runtime library %s is not built", toolchain.RuntimeLibrary())
+	}
+}
+
+func TestPanic(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "panics.navi")
+	source := "func main() {\n  print(1)\n  panic(\"invariant broken\")\n  print(2)\n}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	var stderr bytes.Buffer
+	command := exec.Command(basePath)
+	command.Stderr = &stderr
+	err := command.Run()
+
+	assert.Error(t, err, "a panicking program should exit with a nonzero status")
+	assert.Contains(t, stderr.String(), "panic: invariant broken\n")
+	assert.NotContains(t, stderr.String(), "2\n", "code after panic should not run")
+}
+
+func TestFormattingBuiltins(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "table.navi")
+	source := "func main() {\n" +
+		"  print(format_pad(7, 4))\n" +
+		"  print(format_pad(0 - 42, 4))\n" +
+		"  print(format_pad(12345, 3))\n" +
+		"  print(format_pad(1, 0 - 2))\n" +
+		"  print(to_hex(255))\n" +
+		"  print(to_hex(0 - 255))\n" +
+		"}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	output, err := exec.Command(basePath).CombinedOutput()
+	assert.NoError(t, err)
+	assert.Equal(t, "   7\n -42\n12345\n1\nff\n-ff\n", string(output))
+}
+
+func TestRepeat(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "repeat.navi")
+	source := "func main() {\n" +
+		"  print(repeat(\"-\", 5))\n" +
+		"  print(repeat(\"ab\", 3))\n" +
+		"  print(repeat(\"x\", 0))\n" +
+		"  print(repeat(\"x\", 0 - 1))\n" +
+		"}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	output, err := exec.Command(basePath).CombinedOutput()
+	assert.Error(t, err, "negative count should abort")
+	assert.True(t, strings.HasPrefix(string(output), "-----\nababab\n\npanic: repeat count -1 is negative\n"),
+		"unexpected output %q", output)
+}
+
+func TestStringIndexing(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "index.navi")
+	source := "func last(s: string, length: int) -> char {\n  return s[length - 1]\n}\n\n" +
+		"func main() {\n" +
+		"  let word = \"navi\"\n" +
+		"  print(word[0], last(word, 4))\n" +
+		"  print(char_code(word[1]) + 1)\n" +
+		"  print(char_at(word, 2))\n" +
+		"  print(word[4])\n" +
+		"}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	output, err := exec.Command(basePath).CombinedOutput()
+	assert.Error(t, err, "an out-of-range index should abort")
+	assert.True(t, strings.HasPrefix(string(output), "n i\n98\nv\npanic: string index 4 out of range for length 4\n"),
+		"unexpected output %q", output)
+}
+
+func TestStringIndexingNonAscii(t *testing.T) {
+	requireToolchain(t)
+
+	// "aé!" is a, then é in bytes 1 and 2, then !
+	source := "func main() {\n" +
+		"  let s = \"aé!\"\n" +
+		"  print(s[0], s[1], s[3], char_code(char_at(s, 1)))\n" +
+		"  print(s[2])\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "an index inside a character should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "a é ! 233\npanic: string index 2 is inside a multi-byte character\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestRuntimeAbiCheck(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "abi.navi")
+	assert.NoError(t, os.WriteFile(inputPath, []byte("func main() {\n  print(1)\n}\n"), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	// Pretend the program came from a compiler one ABI version ahead of the runtime
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	llvmIR, err := os.ReadFile(basePath + ".ll")
+	assert.NoError(t, err)
+	current := fmt.Sprintf("@naviary_runtime_check_abi(i32 %d)", constants.RUNTIME_ABI_VERSION)
+	newer := fmt.Sprintf("@naviary_runtime_check_abi(i32 %d)", constants.RUNTIME_ABI_VERSION+1)
+	assert.Contains(t, string(llvmIR), current)
+	assert.NoError(t, os.WriteFile(basePath+".ll", []byte(strings.Replace(string(llvmIR), current, newer, 1)), 0644))
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	output, err := exec.Command(basePath).CombinedOutput()
+	assert.Error(t, err, "an ABI mismatch should abort")
+	expected := fmt.Sprintf("program was compiled for runtime ABI %d but this runtime is ABI %d\n",
+		constants.RUNTIME_ABI_VERSION+1, constants.RUNTIME_ABI_VERSION)
+	assert.Equal(t, expected, string(output), "nothing should run before the check")
+}
+
+func TestEntry(t *testing.T) {
+	source := "func greet() {\n  print(\"hello\")\n}\n\n" +
+		"func count() -> int {\n  print(1, 2, 3)\n  return 3\n}\n\n" +
+		"func twice(x: int) -> int {\n  return x * 2\n}\n\n" +
+		"func main() {\n  print(\"main\")\n}\n"
+
+	// runEntry compiles source starting at entry and returns the program's output and exit code
+	runEntry := func(t *testing.T, entry string) (string, int) {
+		inputPath := filepath.Join(t.TempDir(), "entries.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+		assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true, Entry: entry}, timing.New()))
+
+		basePath := strings.TrimSuffix(inputPath, ".navi")
+		assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+		output, err := exec.Command(basePath).CombinedOutput()
+		var exitError *exec.ExitError
+		if goerrors.As(err, &exitError) {
+			return string(output), exitError.ExitCode()
+		}
+		assert.NoError(t, err)
+		return string(output), 0
+	}
+
+	t.Run("Each entry runs in place of main", func(t *testing.T) {
+		requireToolchain(t)
+
+		tests := []struct {
+			entry    string
+			output   string
+			exitCode int
+		}{
+			{"main", "main\n", 0},
+			{"greet", "hello\n", 0},
+			{"count", "1 2 3\n", 3},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.entry, func(t *testing.T) {
+				output, exitCode := runEntry(t, testCase.entry)
+
+				assert.Equal(t, testCase.output, output)
+				assert.Equal(t, testCase.exitCode, exitCode, "an int result is the exit status")
+			})
+		}
+	})
+
+	t.Run("Invalid entries are compile errors", func(t *testing.T) {
+		tests := []struct {
+			entry         string
+			expectedError string
+		}{
+			{"gret", "entry function gret does not exist; candidates: greet, count, main"},
+			{"twice", "entry function twice must take no parameters but takes 1; candidates: greet, count, main"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.entry, func(t *testing.T) {
+				inputPath := filepath.Join(t.TempDir(), "entries.navi")
+				assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+
+				err := compile(inputPath, CompileOptions{NoCache: true, Entry: testCase.entry}, timing.New())
+				assert.EqualError(t, err, testCase.expectedError)
+			})
+		}
+	})
+}
+
+func TestOutputIsStreamed(t *testing.T) {
+	requireToolchain(t)
+
+	// The second print is far larger than a pipe buffer, so the program cannot
+	// exit until the reader drains it: the first line must arrive while it runs
+	inputPath := filepath.Join(t.TempDir(), "progress.navi")
+	source := "func main() {\n  print(\"first\")\n  print(repeat(\"x\", 1048576))\n}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	reader, writer, err := os.Pipe()
+	assert.NoError(t, err)
+	defer reader.Close()
+
+	command := exec.Command(basePath)
+	command.Stdout = writer
+	command.Stderr = writer
+	assert.NoError(t, command.Start())
+	writer.Close()
+
+	buffered := bufio.NewReader(reader)
+	firstLine, err := buffered.ReadString('\n')
+	assert.NoError(t, err)
+	assert.Equal(t, "first\n", firstLine)
+
+	rest, err := io.ReadAll(buffered)
+	assert.NoError(t, err)
+	assert.Equal(t, 1048576+1, len(rest))
+	assert.NoError(t, command.Wait())
+}
+
+func TestPrelude(t *testing.T) {
+	source := "func main() {\n  print(square(7))\n  print(midpoint(2, 10))\n}\n"
+
+	t.Run("Prelude functions are compiled into the program", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "prelude.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+		assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+		llvmIR, err := os.ReadFile(strings.TrimSuffix(inputPath, ".navi") + ".ll")
+		assert.NoError(t, err)
+		assert.Contains(t, string(llvmIR), "define i64 @square(")
+		assert.Contains(t, string(llvmIR), "define i64 @midpoint(")
+	})
+
+	t.Run("--no-prelude leaves the prelude out", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "bare.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte("func main() {\n  print(1)\n}\n"), 0644))
+		assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true, NoPrelude: true}, timing.New()))
+
+		llvmIR, err := os.ReadFile(strings.TrimSuffix(inputPath, ".navi") + ".ll")
+		assert.NoError(t, err)
+		assert.NotContains(t, string(llvmIR), "@square")
+	})
+
+	t.Run("Prelude functions run", func(t *testing.T) {
+		requireToolchain(t)
+
+		inputPath := filepath.Join(t.TempDir(), "prelude.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+		assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+		basePath := strings.TrimSuffix(inputPath, ".navi")
+		assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+		output, err := exec.Command(basePath).CombinedOutput()
+		assert.NoError(t, err)
+		assert.Equal(t, "49\n6\n", string(output))
+	})
+}
+
+func TestPrintValues(t *testing.T) {
+	requireToolchain(t)
+
+	inputPath := filepath.Join(t.TempDir(), "values.navi")
+	source := "func main() {\n  let n = 1\n  print(n, n < 0, 'x', format_pad(3, 2))\n  print(n)\n}\n"
+	assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+	assert.NoError(t, compile(inputPath, CompileOptions{NoCache: true}, timing.New()))
+
+	basePath := strings.TrimSuffix(inputPath, ".navi")
+	assert.NoError(t, toolchain.Link(basePath+".ll", basePath, toolchain.LinkOptions{}))
+
+	output, err := exec.Command(basePath).CombinedOutput()
+	assert.NoError(t, err)
+	assert.Equal(t, "1 false x  3\n1\n", string(output))
+}
+
+func TestPrintSpacing(t *testing.T) {
+	requireToolchain(t)
+
+	tests := []struct {
+		name     string
+		call     string
+		expected string
+	}{
+		{"String then int", `print("name", 42)`, "name 42\n"},
+		{"Int then string", `print(42, "name")`, "42 name\n"},
+		{"Bool then string", `print(true, "name")`, "true name\n"},
+		{"String then string", `print("a", "b")`, "a b\n"},
+		{"Empty string keeps its separator", `print("", 1)`, " 1\n"},
+		{"Single argument", `print("name")`, "name\n"},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			result, diagnostics, err := RunSnippet("func main() {\n  " + testCase.call + "\n}\n")
+
+			assert.NoError(t, err)
+			assert.Empty(t, diagnostics)
+			assert.Equal(t, testCase.expected, result.Output)
+		})
+	}
+}
+
+func TestEvaluationOrder(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func trace(n: int) -> int {\n  print(n)\n  return n\n}\n\n" +
+		"func pair(a: int, b: int) -> int {\n  return a * 10 + b\n}\n\n" +
+		"func main() {\n  print(pair(trace(1), trace(2)))\n  print(trace(3) - trace(4))\n  print(trace(5), trace(6))\n}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "1\n2\n12\n3\n4\n-1\n5\n6\n5 6\n", result.Output)
+}
+
+func TestBoolReturns(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func is_pos(x: int) -> bool {\n  return x > 0;\n}\n\n" +
+		"func is_small(x: int) -> bool {\n  return x * 2 < 10\n}\n\n" +
+		"func main() {\n  print(is_pos(3), is_pos(0 - 3))\n  let small = is_small(4)\n  print(small, is_small(7))\n}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "true false\ntrue false\n", result.Output)
+}
+
+func TestCallResults(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func twice(x: int) -> int {\n  return x * 2\n}\n\n" +
+		"func greeting() -> string {\n  return \"hi\"\n}\n\n" +
+		"func main() {\n" +
+		"  let n = twice(21)\n" +
+		"  let s = greeting()\n" +
+		"  print(n)\n" +
+		"  print(s)\n" +
+		"  print(twice(twice(1)))\n" +
+		"  print(greeting())\n" +
+		"  print(greeting(), twice(3))\n" +
+		"  print(repeat(greeting(), twice(1)))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "42\nhi\n4\nhi\nhi 6\nhihi\n", result.Output)
+}
+
+// Strings built by the runtime are NUL-terminated like literals, and print writes
+// their bytes without treating them as a format, so % and escapes come out as is
+func TestRuntimeStrings(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func banner(text: string) -> string {\n  return repeat(text, 2)\n}\n\n" +
+		"func main() {\n" +
+		"  let percent = repeat(\"%s%d\", 2)\n" +
+		"  let hex = to_hex(3054)\n" +
+		"  print(percent)\n" +
+		"  print(banner(hex))\n" +
+		"  print(percent, hex, format_pad(5, 3))\n" +
+		"  print(banner(banner(\"\\t|\")))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "%s%d%s%d\nbeebee\n%s%d%s%d bee   5\n\t|\t|\t|\t|\n", result.Output)
+}
+
+// TestRawStrings prints raw strings, whose backslashes must reach the output untouched
+func TestRawStrings(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  print(r\"C:\\temp\\x\")\n" +
+		"  print(r\"a\\nb\\t\", r#\"say \"hi\"\"#)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "C:\\temp\\x\na\\nb\\t say \"hi\"\n", result.Output)
+}
+
+// TestTripleQuotedStrings prints multi-line literals with their newlines and quotes intact
+func TestTripleQuotedStrings(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  print(\"\"\"first\n  second\nthird\"\"\")\n" +
+		"  print(\"\"\"say \"hi\" now\"\"\")\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "first\n  second\nthird\nsay \"hi\" now\n", result.Output)
+}
+
+// TestMutableLocals runs mutable locals, which live in stack storage rather than being bound to their value
+func TestMutableLocals(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let mut count = 1\n" +
+		"  let total := count + 2\n" +
+		"  let mut label: string = \"count\"\n" +
+		"  print(label, count, total)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "count 1 3\n", result.Output)
+}
+
+// TestAssignment updates mutable locals and a mutable global, including from a call's result
+func TestAssignment(t *testing.T) {
+	requireToolchain(t)
+
+	source := "let mut calls = 0\n\n" +
+		"func next() -> int {\n  calls = calls + 1\n  return calls * 10\n}\n\n" +
+		"func main() {\n" +
+		"  let mut x = 0\n" +
+		"  x = 5\n" +
+		"  print(x)\n" +
+		"  x = next()\n" +
+		"  x = x + next()\n" +
+		"  print(x, calls)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "5\n30 2\n", result.Output)
+}
+
+func TestForLoops(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func first(start: int) -> int {\n  for i in start..start + 10 {\n    return i\n  }\n  return -1\n}\n\n" +
+		"func main() {\n" +
+		"  let mut sum = 0\n" +
+		"  for i in 0..10 {\n" +
+		"    sum = sum + i\n" +
+		"  }\n" +
+		"  print(sum)\n" +
+		"  let mut pairs = 0\n" +
+		"  for i in 1..=3 {\n" +
+		"    for j in 0..i {\n" +
+		"      pairs = pairs + 1\n" +
+		"    }\n" +
+		"  }\n" +
+		"  print(pairs)\n" +
+		"  for i in 3..3 {\n" +
+		"    print(i)\n" +
+		"  }\n" +
+		"  print(first(7), first(0))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "45\n6\n7 0\n", result.Output)
+}
+
+func TestLoopShadowing(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let x = 1\n" +
+		"  let mut total = 0\n" +
+		"  for i in 0..2 {\n" +
+		"    let mut x = x + 10\n" +
+		"    x = x + i\n" +
+		"    total = total + x\n" +
+		"  }\n" +
+		"  print(x, total)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "1 23\n", result.Output)
+}
+
+func TestArrayLengthBounds(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let xs = [4, 5, 6]\n" +
+		"  for i in 0..len(xs) {\n" +
+		"    print(i, xs[i])\n" +
+		"  }\n" +
+		"  let none: string[] = []\n" +
+		"  print(len(none), len([[1], [2]]))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "0 4\n1 5\n2 6\n0 2\n", result.Output)
+}
+
+func TestForLoopBoundsEvaluatedOnce(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func start() -> int {\n  print(\"start\")\n  return 1\n}\n\n" +
+		"func end() -> int {\n  print(\"end\")\n  return 3\n}\n\n" +
+		"func main() {\n" +
+		"  for i in start()..end() {\n" +
+		"    print(i)\n" +
+		"  }\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "start\nend\n1\n2\n", result.Output)
+}
+
+func TestStringLoops(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let name = \"navi\"\n" +
+		"  for ch in name {\n" +
+		"    print(ch)\n" +
+		"  }\n" +
+		"  for ch in \"aé😀\" {\n" +
+		"    print(char_code(ch))\n" +
+		"  }\n" +
+		"  for ch in \"é!\" {\n" +
+		"    print(ch)\n" +
+		"  }\n" +
+		"  for ch in \"\" {\n" +
+		"    print(ch)\n" +
+		"  }\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "n\na\nv\ni\n97\n233\n128512\né\n!\n", result.Output)
+}
+
+func TestArrays(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func total(xs: int[], n: int) -> int {\n" +
+		"  let mut sum = 0\n" +
+		"  for i in 0..n {\n" +
+		"    sum = sum + xs[i]\n" +
+		"  }\n" +
+		"  return sum\n" +
+		"}\n\n" +
+		"func main() {\n" +
+		"  let xs = [10, 20, 30]\n" +
+		"  print(xs[1], total(xs, 3))\n" +
+		"  let none: int[] = []\n" +
+		"  print(total(none, 0))\n" +
+		"  let grid = [[\"a\", \"b\"], [\"c\"]]\n" +
+		"  print(grid[1][0], [true, false][1])\n" +
+		"  print(xs[3])\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "an out-of-range index should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "20 60\n0\nc false\npanic: array index 3 out of range for length 3\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestArrayReferences(t *testing.T) {
+	t.Run("Element writes are visible to every holder", func(t *testing.T) {
+		requireToolchain(t)
+
+		source := "func fill(xs: int[]) {\n  xs[0] = 10\n}\n\n" +
+			"func main() {\n" +
+			"  let numbers = [1, 2, 3]\n" +
+			"  fill(numbers)\n" +
+			"  print(numbers[0], numbers[1], numbers[2])\n" +
+			"  let grid = [[1, 2], [3, 4]]\n" +
+			"  let row = grid[1]\n" +
+			"  row[0] = 30\n" +
+			"  fill(grid[0])\n" +
+			"  print(grid[0][0], grid[1][0])\n" +
+			"  numbers[3] = 4\n" +
+			"}\n"
+
+		result, diagnostics, err := RunSnippet(source)
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.NotZero(t, result.ExitCode, "an out-of-range store should abort")
+		assert.True(t, strings.HasPrefix(result.Output, "10 2 3\n10 30\npanic: array index 3 out of range for length 3\n"),
+			"unexpected output %q", result.Output)
+	})
+
+	t.Run("Each evaluation of a literal is a new array", func(t *testing.T) {
+		requireToolchain(t)
+
+		source := "func five() -> int {\n  return 5\n}\n\n" +
+			"func main() {\n" +
+			"  for i in 0..2 {\n" +
+			"    let constant = [1, 2, 3]\n" +
+			"    let mixed = [4, five(), 6]\n" +
+			"    print(constant[0], mixed[0], mixed[1], mixed[2])\n" +
+			"    constant[0] = 10\n" +
+			"    mixed[0] = 40\n" +
+			"  }\n" +
+			"}\n"
+
+		result, diagnostics, err := RunSnippet(source)
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.Zero(t, result.ExitCode)
+		assert.Equal(t, "1 4 5 6\n1 4 5 6\n", result.Output)
+	})
+
+	t.Run("Rebinding a parameter is rejected", func(t *testing.T) {
+		result, diagnostics, err := RunSnippet("func fill(xs: int[]) {\n  xs = [7, 8]\n}\n\nfunc main() {\n}\n")
+
+		assert.NoError(t, err)
+		assert.Equal(t, RunResult{}, result)
+		if assert.Len(t, diagnostics, 1) {
+			assert.Equal(t, errors.TypeError, diagnostics[0].Type)
+			assert.Equal(t, "Cannot assign to parameter xs; copy it into a let mut variable first", diagnostics[0].Message)
+			assert.Equal(t, 2, diagnostics[0].Line)
+		}
+	})
+}
+
+func TestSwap(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let xs = [1, 2, 3]\n" +
+		"  swap(xs, 0, 2)\n" +
+		"  swap(xs, 1, 1)\n" +
+		"  print(xs[0], xs[1], xs[2])\n" +
+		"  swap(xs, 0, 3)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "an out-of-range index should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "3 2 1\npanic: array index 3 out of range for length 3\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestClearAndTruncate(t *testing.T) {
+	requireToolchain(t)
+
+	// One buffer reused across iterations: each round truncates what the last one left
+	source := "func main() {\n" +
+		"  let xs = [1, 2, 3, 4, 5]\n" +
+		"  for round in 0..3 {\n" +
+		"    truncate(xs, 4 - round)\n" +
+		"    print(len(xs), sum(xs))\n" +
+		"  }\n" +
+		"  let names = [\"a\", \"b\"]\n" +
+		"  truncate(names, 1)\n" +
+		"  print(join(names, \",\"))\n" +
+		"  clear(xs)\n" +
+		"  clear(xs)\n" +
+		"  print(len(xs), sum(xs))\n" +
+		"  truncate(xs, 1)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "growing an array with truncate should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "4 10\n3 6\n2 3\na\n0 0\npanic: cannot truncate an array of length 0 to 1\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestArrayReductions(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let none: int[] = []\n" +
+		"  print(sum(none))\n" +
+		"  let one = [7]\n" +
+		"  print(sum(one), min_of(one), max_of(one))\n" +
+		"  let xs = [3, -8, 5, -1]\n" +
+		"  print(sum(xs), min_of(xs), max_of(xs))\n" +
+		"  print(min_of(none))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "min_of of an empty array should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "0\n7 7 7\n-1 -8 5\npanic: min_of of an empty array\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestFloatArrayReductions(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let none: float[] = []\n" +
+		"  print(sum(none))\n" +
+		"  print(max_of(none))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.NotZero(t, result.ExitCode, "max_of of an empty array should abort")
+	assert.True(t, strings.HasPrefix(result.Output, "0\npanic: max_of of an empty array\n"),
+		"unexpected output %q", result.Output)
+}
+
+func TestJoin(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let none: string[] = []\n" +
+		"  print(\"[\", join(none, \", \"), \"]\")\n" +
+		"  print(join([\"solo\"], \", \"))\n" +
+		"  print(join([\"a\", \"bc\", \"\", \"d\"], \", \"))\n" +
+		"  print(join([\"x\", \"y\"], \"\"))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "[  ]\nsolo\na, bc, , d\nxy\n", result.Output)
+}
+
+func TestFind(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let xs = [4, -2, 9, -2]\n" +
+		"  print(find(xs, -2), find(xs, 4), find(xs, 7))\n" +
+		"  let none: int[] = []\n" +
+		"  print(find(none, 0))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "1 0 -1\n-1\n", result.Output)
+}
+
+func TestMembership(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let xs = [4, -2, 9]\n" +
+		"  print(9 in xs, 7 in xs)\n" +
+		"  let none: int[] = []\n" +
+		"  print(0 in none)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "true false\nfalse\n", result.Output)
+}
+
+func TestStringMembership(t *testing.T) {
+	requireToolchain(t)
+
+	// repeat builds a new string, so a match cannot come from comparing pointers
+	source := "func main() {\n" +
+		"  print(\"b\" in [\"a\", \"b\"], \"c\" in [\"a\", \"b\"])\n" +
+		"  let names = [\"ab\", \"b\"]\n" +
+		"  print(repeat(\"b\", 1) in names, \"a\" in names)\n" +
+		"  let none: string[] = []\n" +
+		"  print(\"\" in none)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "true false\ntrue false\nfalse\n", result.Output)
+}
+
+func TestArrayIndexing(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let matrix = [[1, 2], [3, 4]]\n" +
+		"  print(matrix[1][0] + matrix[0][1] * 10)\n" +
+		"  let i = 1\n" +
+		"  print([10, 20, 30][i + 1], matrix[i][i])\n" +
+		"  print(char_code(repeat(\"ab\", 2)[3]) - char_code([\"a\"][0][0]))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "23\n30 4\n1\n", result.Output)
+}
+
+// TestPrintArgumentsEvaluatedOnce checks that each print argument runs exactly once,
+// left to right, before anything is printed for the call itself
+func TestPrintArgumentsEvaluatedOnce(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func mark(n: int) -> int {\n  print(n)\n  return n\n}\n\n" +
+		"func positive(n: int) -> bool {\n  print(n)\n  return n > 0\n}\n\n" +
+		"func main() {\n" +
+		"  print(mark(1) + mark(2))\n" +
+		"  print(positive(3))\n" +
+		"  print(mark(4), positive(5), mark(6) == 6)\n" +
+		"  print(mark(7) < mark(8))\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "1\n2\n3\n3\ntrue\n4\n5\n6\n4 true true\n7\n8\ntrue\n", result.Output)
+}
+
+// TestStatementSequences runs every ordered pair of body statements in one program
+// Statements after a return never run and must not emit code past the terminator
+func TestStatementSequences(t *testing.T) {
+	requireToolchain(t)
+
+	// Each statement is written for position 1 or 2 in the body
+	type statement struct {
+		kind   string
+		source func(position int) string
+	}
+	statements := []statement{
+		{"let", func(position int) string { return fmt.Sprintf("let v%d = %d", position, position) }},
+		{"call", func(position int) string { return fmt.Sprintf("print(%d)", position) }},
+		{"return", func(position int) string { return fmt.Sprintf("return %d", position+10) }},
+	}
+
+	source := ""
+	mainBody := "func main() {\n"
+	expected := ""
+	for _, first := range statements {
+		for _, second := range statements {
+			name := fmt.Sprintf("%s_then_%s", first.kind, second.kind)
+			source += fmt.Sprintf("func %s() -> int {\n  %s\n  %s\n  return 0\n}\n\n",
+				name, first.source(1), second.source(2))
+			mainBody += fmt.Sprintf("  print(%s())\n", name)
+
+			// Output is whatever runs before the first return, then the returned value
+			result := "0"
+			for index, current := range []statement{first, second} {
+				position := index + 1
+				if current.kind == "return" {
+					result = fmt.Sprint(position + 10)
+					break
+				}
+				if current.kind == "call" {
+					expected += fmt.Sprintf("%d\n", position)
+				}
+			}
+			expected += result + "\n"
+		}
+	}
+	source += mainBody + "}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, expected, result.Output)
+}
+
+func TestIntegerLiteralSyntax(t *testing.T) {
+	requireToolchain(t)
+
+	source := "func main() {\n" +
+		"  let x = 0xFF\n" +
+		"  print(x)\n" +
+		"  print(0o755, 0b1010, -0x10)\n" +
+		"  print(0x7FFFFFFFFFFFFFFF)\n" +
+		"  print(1_000_000_000, 0xFFFF_FFFF)\n" +
+		"}\n"
+
+	result, diagnostics, err := RunSnippet(source)
+
+	assert.NoError(t, err)
+	assert.Empty(t, diagnostics)
+	assert.Equal(t, "255\n493 10 -16\n9223372036854775807\n1000000000 4294967295\n", result.Output)
+}
+
+func TestCallDiagnostics(t *testing.T) {
+	declarations := "func twice(x: int) -> int {\n  return x * 2\n}\n" +
+		"func total(xs: int[]) -> int {\n  return sum(xs)\n}\n"
+
+	tests := []struct {
+		name          string
+		call          string
+		expectedError string
+		column        int
+	}{
+		{"Undefined function", "  print(thrice(2))", "Undefined function: thrice", 9},
+		{"Undefined function as a statement", "  missing()", "Undefined function: missing", 3},
+		{"Too few arguments", "  print(twice())", "twice expects (int), got ()", 14},
+		{"Too many arguments", "  print(twice(1, 2))", "twice expects (int), got (int, int)", 14},
+		{"Wrong argument type", "  print(twice(\"1\"))", "twice expects (int), got (string)", 14},
+		{"Wrong array type", "  print(total(['a']))", "total expects (int[]), got (char[])", 14},
+		{"Wrong element in an array argument", "  print(total(['a', 1]))", "Array elements must all be int, but element 1 is char", 15},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			source := declarations + "func main() {\n" + testCase.call + "\n}\n"
+
+			result, diagnostics, err := RunSnippet(source)
+
+			assert.NoError(t, err)
+			assert.Equal(t, RunResult{}, result)
+			if assert.Len(t, diagnostics, 1) {
+				assert.Equal(t, errors.TypeError, diagnostics[0].Type)
+				assert.Equal(t, testCase.expectedError, diagnostics[0].Message)
+				assert.Equal(t, 8, diagnostics[0].Line)
+				assert.Equal(t, testCase.column, diagnostics[0].Column)
+			}
+		})
+	}
+}
+
+func TestRunSnippet(t *testing.T) {
+	t.Run("Successful program", func(t *testing.T) {
+		requireToolchain(t)
+
+		result, diagnostics, err := RunSnippet("func main() {\n  print(repeat(\"ab\", 2), 7)\n}\n")
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.Equal(t, RunResult{Output: "abab 7\n", ExitCode: 0}, result)
+	})
+
+	t.Run("Type error is returned as a diagnostic", func(t *testing.T) {
+		result, diagnostics, err := RunSnippet("func main() {\n  print(char_code(97))\n}\n")
+
+		assert.NoError(t, err)
+		assert.Equal(t, RunResult{}, result)
+		if assert.Len(t, diagnostics, 1) {
+			assert.Equal(t, errors.TypeError, diagnostics[0].Type)
+			assert.Equal(t, "char_code expects (char), got (int)", diagnostics[0].Message)
+			assert.Equal(t, 2, diagnostics[0].Line)
+		}
+	})
+
+	t.Run("Exit code comes from main", func(t *testing.T) {
+		requireToolchain(t)
+
+		result, diagnostics, err := RunSnippet("func main() {\n  print(1)\n  return 3\n}\n")
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.Equal(t, RunResult{Output: "1\n", ExitCode: 3}, result)
+	})
+
+	t.Run("Options apply as they do to a compiled file", func(t *testing.T) {
+		source := "func other() {\n  print(square(3))\n}\n\nfunc main() {\n  print(1)\n}\n"
+
+		result, diagnostics, err := RunSnippetWithOptions(source, CompileOptions{NoPrelude: true})
+
+		assert.NoError(t, err)
+		assert.Equal(t, RunResult{}, result)
+		if assert.Len(t, diagnostics, 1) {
+			assert.Equal(t, "Undefined function: square", diagnostics[0].Message)
+		}
+
+		_, _, err = RunSnippetWithOptions(source, CompileOptions{Entry: "missing"})
+		assert.EqualError(t, err, "entry function missing does not exist; candidates: other, main")
+	})
+
+	t.Run("Tail calls run deep recursion in constant stack", func(t *testing.T) {
+		requireToolchain(t)
+
+		// The loop runs its body only while n > 0, so it stands in for an if
+		source := "func countdown(n: int) -> int {\n" +
+			"  for i in 0..n {\n" +
+			"    return countdown(n - 1)\n" +
+			"  }\n" +
+			"  return 0\n" +
+			"}\n\n" +
+			"func main() {\n  print(countdown(10000000))\n}\n"
+
+		result, diagnostics, err := RunSnippetWithOptions(source, CompileOptions{TailCalls: true})
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.Equal(t, RunResult{Output: "0\n", ExitCode: 0}, result)
+	})
+
+	t.Run("Entry replaces main", func(t *testing.T) {
+		requireToolchain(t)
+
+		source := "func other() {\n  print(square(3))\n}\n\nfunc main() {\n  print(1)\n}\n"
+
+		result, diagnostics, err := RunSnippetWithOptions(source, CompileOptions{Entry: "other", VerifyTypes: true})
+
+		assert.NoError(t, err)
+		assert.Empty(t, diagnostics)
+		assert.Equal(t, RunResult{Output: "9\n", ExitCode: 0}, result)
+	})
+}
+
+func TestParseArguments(t *testing.T) {
+	t.Run("Valid command lines", func(t *testing.T) {
+		tests := []struct {
+			name      string
+			arguments []string
+			expected  Command
+		}{
+			{"Compile", []string{"hello.navi"}, Command{Name: COMMAND_COMPILE, Arguments: []string{"hello.navi"}}},
+			{"Run with a flag after the file", []string{"run", "hello.navi", "--no-cache"},
+				Command{Name: COMMAND_RUN, Arguments: []string{"hello.navi"}, Options: CompileOptions{Run: true, NoCache: true}}},
+			{"Build without a directory", []string{"build"}, Command{Name: COMMAND_BUILD, Arguments: []string{}}},
+			{"Build with a directory", []string{"--timings=json", "build", "app"},
+				Command{Name: COMMAND_BUILD, Arguments: []string{"app"}, Options: CompileOptions{Timings: timing.FormatJSON}}},
+			{"New project", []string{"new", "app"}, Command{Name: COMMAND_NEW, Arguments: []string{"app"}}},
+			{"Error limit", []string{"--max-errors=5", "hello.navi"},
+				Command{Name: COMMAND_COMPILE, Arguments: []string{"hello.navi"}, Options: CompileOptions{MaxErrors: 5}}},
+			{"Compiler override", []string{"run", "--cc=clang-18", "hello.navi"},
+				Command{Name: COMMAND_RUN, Arguments: []string{"hello.navi"}, Options: CompileOptions{Run: true, CC: "clang-18"}}},
+			{"Completion", []string{"--complete=app.navi:3:7"}, Command{Name: COMMAND_COMPLETE, Arguments: []string{"app.navi:3:7"}}},
+			{"Entry point", []string{"run", "hello.navi", "--entry=bench_loop"},
+				Command{Name: COMMAND_RUN, Arguments: []string{"hello.navi"}, Options: CompileOptions{Run: true, Entry: "bench_loop"}}},
+			{"Symbol report as JSON", []string{"--report=symbols,json", "hello.navi"},
+				Command{Name: COMMAND_COMPILE, Arguments: []string{"hello.navi"}, Options: CompileOptions{Report: symbols.FormatJSON}}},
+			{"Colors forced on", []string{"--color=always", "hello.navi"},
+				Command{Name: COMMAND_COMPILE, Arguments: []string{"hello.navi"}, Options: CompileOptions{Color: COLOR_ALWAYS}}},
+			{"Colors off", []string{"hello.navi", "--no-color"},
+				Command{Name: COMMAND_COMPILE, Arguments: []string{"hello.navi"}, Options: CompileOptions{Color: COLOR_NEVER}}},
+			{"Help wins over everything else", []string{"run", "--bogus", "--help"}, Command{Name: COMMAND_HELP}},
+			{"Version", []string{"--version"}, Command{Name: COMMAND_VERSION}},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				command, err := ParseArguments(testCase.arguments)
+
+				assert.NoError(t, err)
+				assert.Equal(t, testCase.expected, command)
+			})
+		}
+	})
+
+	t.Run("Invalid command lines", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			arguments     []string
+			expectedError string
+		}{
+			{"No arguments", []string{}, "missing source file"},
+			{"Run without a file", []string{"run"}, "missing source file"},
+			{"Unknown flag", []string{"--fast", "hello.navi"}, "unknown flag '--fast'"},
+			{"Extra file", []string{"a.navi", "b.navi"}, "unexpected argument 'b.navi'"},
+			{"New without a name", []string{"new"}, "missing project name"},
+			{"Build with two directories", []string{"build", "a", "b"}, "unexpected argument 'b'"},
+			{"Test with two files", []string{"test", "a.navi", "b.navi"}, "unexpected argument 'b.navi'"},
+			{"Zero error limit", []string{"--max-errors=0", "a.navi"}, "--max-errors expects a positive number, got '0'"},
+			{"Error limit without a number", []string{"--max-errors=many", "a.navi"}, "--max-errors expects a positive number, got 'many'"},
+			{"Completion with a source file", []string{"--complete=app.navi:3:7", "app.navi"}, "unexpected argument 'app.navi'"},
+			{"Entry without a name", []string{"run", "--entry=", "a.navi"}, "--entry expects a function name"},
+			{"Unknown color mode", []string{"--color=sometimes", "a.navi"}, "--color expects always, auto or never, got 'sometimes'"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, err := ParseArguments(testCase.arguments)
+
+				if assert.Error(t, err) {
+					assert.Equal(t, testCase.expectedError, err.Error())
+				}
+			})
+		}
+	})
+}
+
+// runDriver runs main in a child test process with the given arguments
+// and returns its combined output and exit code
+func runDriver(t *testing.T, arguments ...string) (string, int) {
+	command := exec.Command(os.Args[0], "-test.run=^TestDriverProcess$")
+	command.Env = append(os.Environ(), "NAVIARY_DRIVER_ARGS="+strings.Join(arguments, "\n"))
+
+	output, err := command.CombinedOutput()
+	var exitError *exec.ExitError
+	if goerrors.As(err, &exitError) {
+		return string(output), exitError.ExitCode()
+	}
+	assert.NoError(t, err)
+
+	return string(output), 0
+}
+
+// TestDriverProcess is the child side of runDriver and does nothing in a normal test run
+func TestDriverProcess(t *testing.T) {
+	arguments, ok := os.LookupEnv("NAVIARY_DRIVER_ARGS")
+	if !ok {
+		t.Skip("only runs as the child of runDriver")
+	}
+
+	os.Args = []string{"naviary"}
+	if arguments != "" {
+		os.Args = append(os.Args, strings.Split(arguments, "\n")...)
+	}
+	main()
+	os.Exit(0)
+}
+
+func TestDriverArguments(t *testing.T) {
+	t.Run("No arguments print usage", func(t *testing.T) {
+		output, exitCode := runDriver(t)
+
+		assert.Equal(t, 2, exitCode)
+		assert.Contains(t, output, "Error: missing source file")
+		assert.Contains(t, output, "Usage: naviary")
+	})
+
+	t.Run("Unknown flag is rejected", func(t *testing.T) {
+		output, exitCode := runDriver(t, "--fast", "hello.navi")
+
+		assert.Equal(t, 2, exitCode)
+		assert.Contains(t, output, "Error: unknown flag '--fast'")
+		assert.Contains(t, output, "--no-prelude")
+	})
+
+	t.Run("Errors are limited and shown in source order", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "broken.navi")
+		source := "func main() {\n"
+		for i := 0; i < 5; i++ {
+			source += "  print(char_code(1))\n"
+		}
+		source += "}\n"
+		assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+
+		output, exitCode := runDriver(t, "--no-cache", "--max-errors=2", inputPath)
+
+		assert.Equal(t, 1, exitCode)
+		assert.Equal(t, 2, strings.Count(output, "char_code expects (char), got (int)"))
+		second := strings.Index(output, "broken.navi:2:")
+		third := strings.Index(output, "broken.navi:3:")
+		assert.True(t, second >= 0 && second < third, "errors should be shown in source order")
+		assert.NotContains(t, output, "broken.navi:4:")
+		assert.Contains(t, output, "... and 3 more errors\n")
+		assert.Contains(t, output, "aborting due to 5 previous errors")
+	})
+
+	t.Run("Every invalid character is reported without cascades", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "symbols.navi")
+		source := "func main() {\n  let a = 1 # 2\n  print(a $ 1)\n  let b = €\n}\n"
+		assert.NoError(t, os.WriteFile(inputPath, []byte(source), 0644))
+
+		output, exitCode := runDriver(t, "--no-cache", "--no-color", inputPath)
+
+		assert.Equal(t, 1, exitCode)
+		assert.Contains(t, output, "symbols.navi:2:13")
+		assert.Contains(t, output, "unexpected character '#'")
+		assert.Contains(t, output, "symbols.navi:3:11")
+		assert.Contains(t, output, "unexpected character '$'")
+		assert.Contains(t, output, "symbols.navi:4:11")
+		assert.Contains(t, output, "unexpected character '€'")
+		assert.Contains(t, output, "aborting due to 3 previous errors")
+	})
+
+	t.Run("Unusable source files are rejected before lexing", func(t *testing.T) {
+		directory := t.TempDir()
+		binaryPath := filepath.Join(directory, "binary.navi")
+		assert.NoError(t, os.WriteFile(binaryPath, []byte("func main() {\xff\xfe}\n"), 0644))
+		emptyPath := filepath.Join(directory, "empty.navi")
+		assert.NoError(t, os.WriteFile(emptyPath, []byte(""), 0644))
+		blankPath := filepath.Join(directory, "blank.navi")
+		assert.NoError(t, os.WriteFile(blankPath, []byte("\n  \t\n"), 0644))
+		directoryPath := filepath.Join(directory, "folder.navi")
+		assert.NoError(t, os.Mkdir(directoryPath, 0755))
+
+		tests := []struct {
+			name     string
+			path     string
+			expected string
+		}{
+			{"Invalid UTF-8", binaryPath, binaryPath + " is not valid UTF-8 (first invalid byte at offset 13)"},
+			{"Empty file", emptyPath, "no functions found in " + emptyPath},
+			{"Whitespace only", blankPath, "no functions found in " + blankPath},
+			{"Directory", directoryPath, directoryPath + " is a directory, not a source file"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				output, exitCode := runDriver(t, "--no-cache", testCase.path)
+
+				assert.Equal(t, 1, exitCode)
+				assert.Contains(t, output, "Compilation failed: "+testCase.expected+"\n")
+				assert.NotContains(t, output, "Compiling")
+			})
+
+			t.Run(testCase.name+" under test", func(t *testing.T) {
+				output, exitCode := runDriver(t, "test", testCase.path)
+
+				assert.Equal(t, 1, exitCode)
+				assert.Contains(t, output, "Error: "+testCase.expected+"\n")
+			})
+		}
+	})
+
+	t.Run("Files without functions are rejected before the prelude is merged", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "comments.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte("// nothing yet\n/* still nothing */\n"), 0644))
+
+		output, exitCode := runDriver(t, "--no-cache", inputPath)
+
+		assert.Equal(t, 1, exitCode)
+		assert.Contains(t, output, "Compilation failed: no functions found in "+inputPath+"\n")
+		assert.NotContains(t, output, "undefined reference")
+
+		output, exitCode = runDriver(t, "test", inputPath)
+
+		assert.Equal(t, 1, exitCode)
+		assert.Contains(t, output, "Error: no functions found in "+inputPath+"\n")
+	})
+
+	t.Run("Programs without main are rejected by the lowerer", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "helpers.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte("func helper() -> int {\n  return 1\n}\n"), 0644))
+
+		output, exitCode := runDriver(t, "--no-cache", inputPath)
+
+		assert.Equal(t, 1, exitCode)
+		assert.Contains(t, output, "No main function defined; a program starts by calling func main()")
+		assert.Contains(t, output, "Compilation failed: lowering failed\n")
+		assert.NotContains(t, output, "undefined reference")
+	})
+
+	t.Run("Diagnostics are colored only when asked or on a terminal", func(t *testing.T) {
+		inputPath := filepath.Join(t.TempDir(), "broken.navi")
+		assert.NoError(t, os.WriteFile(inputPath, []byte("func main() {\n  print(char_code(1))\n}\n"), 0644))
+
+		tests := []struct {
+			name      string
+			noColor   string
+			arguments []string
+			colored   bool
+		}{
+			{"Auto is plain when piped", "", []string{}, false},
+			{"Never", "", []string{"--color=never"}, false},
+			{"No color flag", "", []string{"--no-color"}, false},
+			{"Always", "", []string{"--color=always"}, true},
+			{"NO_COLOR", "1", []string{}, false},
+			{"Always overrides NO_COLOR", "1", []string{"--color=always"}, true},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				t.Setenv("NO_COLOR", testCase.noColor)
+				arguments := append([]string{"--no-cache", inputPath}, testCase.arguments...)
+
+				output, exitCode := runDriver(t, arguments...)
+
+				assert.Equal(t, 1, exitCode)
+				assert.Contains(t, output, "char_code expects (char), got (int)")
+				assert.Equal(t, testCase.colored, strings.Contains(output, "\033["), "output %q", output)
+			})
+		}
+	})
+
+	t.Run("Help", func(t *testing.T) {
+		output, exitCode := runDriver(t, "--help")
+
+		assert.Equal(t, 0, exitCode)
+		assert.Equal(t, Usage(), output)
+	})
+
+	t.Run("Version", func(t *testing.T) {
+		output, exitCode := runDriver(t, "--version")
+
+		assert.Equal(t, 0, exitCode)
+		assert.Equal(t, "naviary "+constants.COMPILER_VERSION+"\n", output)
+	})
+}
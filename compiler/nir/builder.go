@@ -8,12 +8,14 @@ import (
 
 type Builder struct {
 	nextTemporaryID int
+	nextBlockID     int
 	currentBlock    *BasicBlock
 }
 
 func NewBuilder() *Builder {
 	return &Builder{
 		nextTemporaryID: 0,
+		nextBlockID:     0,
 		currentBlock:    nil,
 	}
 }
@@ -25,6 +27,14 @@ func (builder *Builder) SetInsertBlock(block *BasicBlock) {
 func (builder *Builder) GetInsertBlock() *BasicBlock {
 	return builder.currentBlock
 }
+
+// NextBlockID returns a number that keeps block names unique within the function
+func (builder *Builder) NextBlockID() int {
+	id := builder.nextBlockID
+	builder.nextBlockID++
+	return id
+}
+
 func (builder *Builder) CreateTemporary(temporaryType types.Type) value.Value {
 	temp := value.NewTemporary(builder.nextTemporaryID, temporaryType)
 	builder.nextTemporaryID++
@@ -35,6 +45,14 @@ func (builder *Builder) CreateConstantInt(val int) value.Value {
 	return value.NewConstant(val, types.Int)
 }
 
+func (builder *Builder) CreateConstantBool(val bool) value.Value {
+	return value.NewConstant(val, types.Bool)
+}
+
+func (builder *Builder) CreateConstantChar(val rune) value.Value {
+	return value.NewConstant(val, types.Char)
+}
+
 func (builder *Builder) CreateConstantString(val string) value.Value {
 	return value.NewConstant(val, types.String)
 }
@@ -43,9 +61,13 @@ func (builder *Builder) CreateVariable(name string, variableType types.Type) val
 	return value.NewVariable(name, variableType)
 }
 
-func (builder *Builder) BuildAlloc(name string, allocateType types.Type) value.Value {
+func (builder *Builder) CreateGlobal(name string, globalType types.Type) *value.Global {
+	return value.NewGlobal(name, globalType)
+}
+
+func (builder *Builder) BuildAlloc(name string, allocateType types.Type, mutable bool) value.Value {
 	variable := builder.CreateVariable(name, allocateType)
-	allocInstruction := instruction.NewAllocInstruction(variable, allocateType)
+	allocInstruction := instruction.NewAllocInstruction(variable, allocateType, mutable)
 
 	if builder.currentBlock != nil {
 		builder.currentBlock.AddInstruction(allocInstruction)
@@ -85,6 +107,18 @@ func (builder *Builder) BuildBinary(left value.Value, right value.Value, operato
 	return temporary
 }
 
+func (builder *Builder) BuildCompare(left value.Value, right value.Value, operator instruction.CompareOperator) value.Value {
+	temporary := builder.CreateTemporary(types.Bool)
+
+	compareInstruction := instruction.NewCompareInstruction(temporary, operator, left, right)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.AddInstruction(compareInstruction)
+	}
+
+	return temporary
+}
+
 func (builder *Builder) BuildCall(functionName string, arguments []value.Value, returnType types.Type) value.Value {
 	var result value.Value = nil
 
@@ -101,6 +135,51 @@ func (builder *Builder) BuildCall(functionName string, arguments []value.Value,
 	return result
 }
 
+func (builder *Builder) BuildMakeArray(elementType types.Type, elements []value.Value) value.Value {
+	temporary := builder.CreateTemporary(&types.ArrayType{Element: elementType})
+
+	makeArrayInstruction := instruction.NewMakeArrayInstruction(temporary, elements)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.AddInstruction(makeArrayInstruction)
+	}
+
+	return temporary
+}
+
+func (builder *Builder) BuildElement(array value.Value, index value.Value) value.Value {
+	temporary := builder.CreateTemporary(array.Type().(*types.ArrayType).Element)
+
+	elementInstruction := instruction.NewElementInstruction(temporary, array, index)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.AddInstruction(elementInstruction)
+	}
+
+	return temporary
+}
+
+func (builder *Builder) BuildSetElement(array value.Value, index value.Value, val value.Value) {
+	setElementInstruction := instruction.NewSetElementInstruction(array, index, val)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.AddInstruction(setElementInstruction)
+	}
+}
+
+// MarkTailCall flags the call that produced result as being in tail position
+// It only applies when that call is the last instruction of the current block
+func (builder *Builder) MarkTailCall(result value.Value) {
+	if builder.currentBlock == nil || len(builder.currentBlock.Instructions) == 0 {
+		return
+	}
+
+	last := builder.currentBlock.Instructions[len(builder.currentBlock.Instructions)-1]
+	if call, ok := last.(*instruction.CallInstruction); ok && call.GetResult() == result {
+		call.SetTail(true)
+	}
+}
+
 func (builder *Builder) BuildReturn(val value.Value) {
 	resultInstruction := instruction.NewReturnInstruction(val)
 
@@ -109,7 +188,32 @@ func (builder *Builder) BuildReturn(val value.Value) {
 	}
 }
 
+func (builder *Builder) BuildBranch(target *BasicBlock) {
+	branchInstruction := instruction.NewBranchInstruction(target.Name)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.Terminator = branchInstruction
+	}
+}
+
+func (builder *Builder) BuildConditionalBranch(condition value.Value, trueTarget *BasicBlock, falseTarget *BasicBlock) {
+	branchInstruction := instruction.NewConditionalBranchInstruction(condition, trueTarget.Name, falseTarget.Name)
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.Terminator = branchInstruction
+	}
+}
+
+func (builder *Builder) BuildUnreachable() {
+	unreachableInstruction := instruction.NewUnreachableInstruction()
+
+	if builder.currentBlock != nil {
+		builder.currentBlock.Terminator = unreachableInstruction
+	}
+}
+
 func (builder *Builder) Reset() {
 	builder.nextTemporaryID = 0
+	builder.nextBlockID = 0
 	builder.currentBlock = nil
 }
@@ -22,10 +22,18 @@ func (param *Parameter) String() string {
 	return fmt.Sprintf("%s: %s", param.Name, param.Type.String())
 }
 
+// FunctionFlags carries per-function code generation hints
+type FunctionFlags int
+
+const (
+	FunctionInline FunctionFlags = 1 << iota // always inline at call sites
+)
+
 type Function struct {
 	Name        string
 	Parameters  []Parameter
 	ReturnType  types.Type
+	Flags       FunctionFlags
 	BasicBlocks []*BasicBlock
 }
 
@@ -63,10 +71,19 @@ func (function *Function) IsComplete() bool {
 	return true
 }
 
+// HasFlag reports whether every bit of flag is set on the function
+func (function *Function) HasFlag(flag FunctionFlags) bool {
+	return function.Flags&flag == flag
+}
+
 func (function *Function) String() string {
 	var builder strings.Builder
 
-	builder.WriteString(fmt.Sprintf("Function: %s(", function.Name))
+	builder.WriteString("Function: ")
+	if function.HasFlag(FunctionInline) {
+		builder.WriteString("@inline ")
+	}
+	builder.WriteString(fmt.Sprintf("%s(", function.Name))
 
 	for i, param := range function.Parameters {
 		if i > 0 {
@@ -0,0 +1,37 @@
+package nir
+
+import (
+	"compiler/nir/value"
+	"fmt"
+)
+
+// Global is a module level variable
+// Scalar globals are initialized statically; globals whose initializer
+// needs runtime support (like strings) are stored at the top of main instead
+type Global struct {
+	Variable    *value.Global
+	Mutable     bool
+	Initializer value.Value // nil when main initializes the global
+}
+
+func NewGlobal(variable *value.Global, mutable bool, initializer value.Value) *Global {
+	return &Global{
+		Variable:    variable,
+		Mutable:     mutable,
+		Initializer: initializer,
+	}
+}
+
+func (global *Global) String() string {
+	keyword := "global"
+	if !global.Mutable {
+		keyword = "constant"
+	}
+
+	initializer := "<initialized in main>"
+	if global.Initializer != nil {
+		initializer = global.Initializer.String()
+	}
+
+	return fmt.Sprintf("%s %s: %s = %s", keyword, global.Variable.String(), global.Variable.Type().String(), initializer)
+}
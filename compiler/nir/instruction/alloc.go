@@ -8,15 +8,20 @@ import (
 
 // AllocInstruction allocates memory for a variable
 // Example: %x = Alloc(int)
+//
+// Variables that are never reassigned are not mutable; codegen binds them
+// straight to their value instead of giving them stack storage
 type AllocInstruction struct {
 	result       value.Value
 	allocateType types.Type
+	mutable      bool
 }
 
-func NewAllocInstruction(result value.Value, allocateType types.Type) *AllocInstruction {
+func NewAllocInstruction(result value.Value, allocateType types.Type, mutable bool) *AllocInstruction {
 	return &AllocInstruction{
 		result:       result,
 		allocateType: allocateType,
+		mutable:      mutable,
 	}
 }
 
@@ -25,9 +30,17 @@ func (alloc *AllocInstruction) GetResult() value.Value {
 }
 
 func (alloc *AllocInstruction) String() string {
-	return fmt.Sprintf("%s = Alloc(%s)", alloc.result.String(), alloc.allocateType.String())
+	kind := "Alloc"
+	if alloc.mutable {
+		kind = "AllocMut"
+	}
+	return fmt.Sprintf("%s = %s(%s)", alloc.result.String(), kind, alloc.allocateType.String())
 }
 
 func (alloc *AllocInstruction) GetAllocateType() types.Type {
 	return alloc.allocateType
 }
+
+func (alloc *AllocInstruction) IsMutable() bool {
+	return alloc.mutable
+}
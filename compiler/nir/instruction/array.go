@@ -0,0 +1,106 @@
+package instruction
+
+import (
+	"compiler/nir/value"
+	"fmt"
+	"strings"
+)
+
+// MakeArrayInstruction builds a new array holding the elements in order
+// Example: %0 = MakeArray([Constant(1), Constant(2)])
+type MakeArrayInstruction struct {
+	result   value.Value
+	elements []value.Value
+}
+
+func NewMakeArrayInstruction(result value.Value, elements []value.Value) *MakeArrayInstruction {
+	return &MakeArrayInstruction{
+		result:   result,
+		elements: elements,
+	}
+}
+
+func (makeArray *MakeArrayInstruction) GetElements() []value.Value {
+	return makeArray.elements
+}
+
+func (makeArray *MakeArrayInstruction) String() string {
+	elements := make([]string, len(makeArray.elements))
+	for i, element := range makeArray.elements {
+		elements[i] = element.String()
+	}
+
+	return fmt.Sprintf("%s = MakeArray([%s])", makeArray.result.String(), strings.Join(elements, ", "))
+}
+
+func (makeArray *MakeArrayInstruction) GetResult() value.Value {
+	return makeArray.result
+}
+
+// ElementInstruction reads one element of an array, aborting when the index is out of range
+// Example: %2 = Element(%0, %1)
+type ElementInstruction struct {
+	result value.Value
+	array  value.Value
+	index  value.Value
+}
+
+func NewElementInstruction(result value.Value, array value.Value, index value.Value) *ElementInstruction {
+	return &ElementInstruction{
+		result: result,
+		array:  array,
+		index:  index,
+	}
+}
+
+func (element *ElementInstruction) GetArray() value.Value {
+	return element.array
+}
+
+func (element *ElementInstruction) GetIndex() value.Value {
+	return element.index
+}
+
+func (element *ElementInstruction) String() string {
+	return fmt.Sprintf("%s = Element(%s, %s)", element.result.String(), element.array.String(), element.index.String())
+}
+
+func (element *ElementInstruction) GetResult() value.Value {
+	return element.result
+}
+
+// SetElementInstruction stores a value in one element of an array, aborting when the index is out of range
+// Example: SetElement(%0, %1, %2)
+type SetElementInstruction struct {
+	array value.Value
+	index value.Value
+	value value.Value
+}
+
+func NewSetElementInstruction(array value.Value, index value.Value, value value.Value) *SetElementInstruction {
+	return &SetElementInstruction{
+		array: array,
+		index: index,
+		value: value,
+	}
+}
+
+func (setElement *SetElementInstruction) GetArray() value.Value {
+	return setElement.array
+}
+
+func (setElement *SetElementInstruction) GetIndex() value.Value {
+	return setElement.index
+}
+
+func (setElement *SetElementInstruction) GetValue() value.Value {
+	return setElement.value
+}
+
+func (setElement *SetElementInstruction) String() string {
+	return fmt.Sprintf("SetElement(%s, %s, %s)", setElement.array.String(), setElement.index.String(), setElement.value.String())
+}
+
+func (setElement *SetElementInstruction) GetResult() value.Value {
+	return nil
+}
@@ -32,6 +32,24 @@ func (operator BinaryOperator) String() string {
 	}
 }
 
+// Symbol returns the operator as it is written in source, for diagnostics
+func (operator BinaryOperator) Symbol() string {
+	switch operator {
+	case BinaryAdd:
+		return "+"
+	case BinarySubtract:
+		return "-"
+	case BinaryMultiply:
+		return "*"
+	case BinaryDivide:
+		return "/"
+	case BinaryModulo:
+		return "%"
+	default:
+		return "?"
+	}
+}
+
 // BinaryInstruction performs binary operations (like +, -, *, /, %)
 // Example: %result = Add(%left, %right)
 type BinaryInstruction struct {
@@ -0,0 +1,68 @@
+package instruction
+
+import (
+	"compiler/nir/value"
+	"fmt"
+)
+
+// Branch targets are block names, since blocks belong to the nir package above this one
+
+// BranchInstruction ends a block by jumping to another block of the same function
+// Example: Branch(for.cond.0)
+type BranchInstruction struct {
+	target string
+}
+
+func NewBranchInstruction(target string) *BranchInstruction {
+	return &BranchInstruction{
+		target: target,
+	}
+}
+
+func (branch *BranchInstruction) GetTarget() string {
+	return branch.target
+}
+
+func (branch *BranchInstruction) String() string {
+	return fmt.Sprintf("Branch(%s)", branch.target)
+}
+
+func (branch *BranchInstruction) GetResult() value.Value {
+	return nil
+}
+
+// ConditionalBranchInstruction ends a block by jumping to one of two blocks on a bool
+// Example: Branch(%3, for.body.0, for.end.0)
+type ConditionalBranchInstruction struct {
+	condition   value.Value
+	trueTarget  string
+	falseTarget string
+}
+
+func NewConditionalBranchInstruction(condition value.Value, trueTarget string, falseTarget string) *ConditionalBranchInstruction {
+	return &ConditionalBranchInstruction{
+		condition:   condition,
+		trueTarget:  trueTarget,
+		falseTarget: falseTarget,
+	}
+}
+
+func (branch *ConditionalBranchInstruction) GetCondition() value.Value {
+	return branch.condition
+}
+
+func (branch *ConditionalBranchInstruction) GetTrueTarget() string {
+	return branch.trueTarget
+}
+
+func (branch *ConditionalBranchInstruction) GetFalseTarget() string {
+	return branch.falseTarget
+}
+
+func (branch *ConditionalBranchInstruction) String() string {
+	return fmt.Sprintf("Branch(%s, %s, %s)", branch.condition.String(), branch.trueTarget, branch.falseTarget)
+}
+
+func (branch *ConditionalBranchInstruction) GetResult() value.Value {
+	return nil
+}
@@ -11,6 +11,7 @@ type CallInstruction struct {
 	result       value.Value
 	functionName string
 	arguments    []value.Value
+	tail         bool // the call's result is returned immediately
 }
 
 func NewCallInstruction(result value.Value, functionName string, arguments []value.Value) *CallInstruction {
@@ -31,10 +32,15 @@ func (call *CallInstruction) String() string {
 	}
 	args += "]"
 
+	kind := "Call"
+	if call.tail {
+		kind = "TailCall"
+	}
+
 	if call.result != nil {
-		return fmt.Sprintf("%s = Call(%s, %s)", call.result.String(), call.functionName, args)
+		return fmt.Sprintf("%s = %s(%s, %s)", call.result.String(), kind, call.functionName, args)
 	}
-	return fmt.Sprintf("Call(%s, %s)", call.functionName, args)
+	return fmt.Sprintf("%s(%s, %s)", kind, call.functionName, args)
 }
 
 func (call *CallInstruction) GetResult() value.Value {
@@ -48,3 +54,11 @@ func (call *CallInstruction) GetFunctionName() string {
 func (call *CallInstruction) GetArguments() []value.Value {
 	return call.arguments
 }
+
+func (call *CallInstruction) IsTail() bool {
+	return call.tail
+}
+
+func (call *CallInstruction) SetTail(tail bool) {
+	call.tail = tail
+}
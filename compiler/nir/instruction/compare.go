@@ -0,0 +1,103 @@
+package instruction
+
+import (
+	"compiler/nir/value"
+	"fmt"
+)
+
+type CompareOperator int
+
+const (
+	CompareEqual CompareOperator = iota
+	CompareNotEqual
+	CompareLess
+	CompareGreater
+	CompareLessEqual
+	CompareGreaterEqual
+)
+
+func (operator CompareOperator) String() string {
+	switch operator {
+	case CompareEqual:
+		return "Equal"
+	case CompareNotEqual:
+		return "NotEqual"
+	case CompareLess:
+		return "Less"
+	case CompareGreater:
+		return "Greater"
+	case CompareLessEqual:
+		return "LessEqual"
+	case CompareGreaterEqual:
+		return "GreaterEqual"
+	default:
+		return "Unknown"
+	}
+}
+
+// Symbol returns the operator as it is written in source, for diagnostics
+func (operator CompareOperator) Symbol() string {
+	switch operator {
+	case CompareEqual:
+		return "=="
+	case CompareNotEqual:
+		return "!="
+	case CompareLess:
+		return "<"
+	case CompareGreater:
+		return ">"
+	case CompareLessEqual:
+		return "<="
+	case CompareGreaterEqual:
+		return ">="
+	default:
+		return "?"
+	}
+}
+
+// IsOrdering reports whether the operator compares magnitude rather than identity
+func (operator CompareOperator) IsOrdering() bool {
+	return operator != CompareEqual && operator != CompareNotEqual
+}
+
+// CompareInstruction compares two operands of the same type and produces a bool
+// Example: %result = Less(%left, %right)
+type CompareInstruction struct {
+	result   value.Value
+	operator CompareOperator
+	left     value.Value
+	right    value.Value
+}
+
+func NewCompareInstruction(result value.Value, operator CompareOperator, left value.Value, right value.Value) *CompareInstruction {
+	return &CompareInstruction{
+		result:   result,
+		operator: operator,
+		left:     left,
+		right:    right,
+	}
+}
+
+func (compare *CompareInstruction) GetResult() value.Value {
+	return compare.result
+}
+
+func (compare *CompareInstruction) String() string {
+	return fmt.Sprintf("%s = %s(%s, %s)",
+		compare.result.String(),
+		compare.operator.String(),
+		compare.left.String(),
+		compare.right.String())
+}
+
+func (compare *CompareInstruction) GetOperator() CompareOperator {
+	return compare.operator
+}
+
+func (compare *CompareInstruction) GetLeft() value.Value {
+	return compare.left
+}
+
+func (compare *CompareInstruction) GetRight() value.Value {
+	return compare.right
+}
@@ -0,0 +1,40 @@
+package instruction
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestOperatorSymbols(t *testing.T) {
+	t.Run("Binary operators", func(t *testing.T) {
+		expected := map[BinaryOperator]string{
+			BinaryAdd:      "+",
+			BinarySubtract: "-",
+			BinaryMultiply: "*",
+			BinaryDivide:   "/",
+			BinaryModulo:   "%",
+		}
+
+		for operator, symbol := range expected {
+			assert.Equal(t, symbol, operator.Symbol(), "symbol of %s", operator)
+		}
+		assert.Equal(t, "?", BinaryOperator(len(expected)).Symbol(), "every operator should be listed above")
+	})
+
+	t.Run("Compare operators", func(t *testing.T) {
+		expected := map[CompareOperator]string{
+			CompareEqual:        "==",
+			CompareNotEqual:     "!=",
+			CompareLess:         "<",
+			CompareGreater:      ">",
+			CompareLessEqual:    "<=",
+			CompareGreaterEqual: ">=",
+		}
+
+		for operator, symbol := range expected {
+			assert.Equal(t, symbol, operator.Symbol(), "symbol of %s", operator)
+		}
+		assert.Equal(t, "?", CompareOperator(len(expected)).Symbol(), "every operator should be listed above")
+	})
+}
@@ -0,0 +1,19 @@
+package instruction
+
+import "compiler/nir/value"
+
+// UnreachableInstruction terminates a block that control never leaves,
+// such as one ending in a call to panic
+type UnreachableInstruction struct{}
+
+func NewUnreachableInstruction() *UnreachableInstruction {
+	return &UnreachableInstruction{}
+}
+
+func (unreachable *UnreachableInstruction) String() string {
+	return "Unreachable"
+}
+
+func (unreachable *UnreachableInstruction) GetResult() value.Value {
+	return nil
+}
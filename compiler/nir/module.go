@@ -7,16 +7,22 @@ import (
 
 type Module struct {
 	Name      string
+	Globals   []*Global
 	Functions []*Function
 }
 
 func NewModule(name string) *Module {
 	return &Module{
 		Name:      name,
+		Globals:   make([]*Global, 0),
 		Functions: make([]*Function, 0),
 	}
 }
 
+func (module *Module) AddGlobal(global *Global) {
+	module.Globals = append(module.Globals, global)
+}
+
 func (module *Module) AddFunction(function *Function) {
 	module.Functions = append(module.Functions, function)
 }
@@ -50,6 +56,10 @@ func (module *Module) String() string {
 
 	builder.WriteString(fmt.Sprintf("Module: %s\n", module.Name))
 
+	for _, global := range module.Globals {
+		builder.WriteString(fmt.Sprintf("  %s\n", global.String()))
+	}
+
 	if len(module.Functions) == 0 {
 		builder.WriteString("  <no functions>\n")
 		return builder.String()
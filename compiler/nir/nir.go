@@ -2,11 +2,16 @@ package nir
 
 import (
 	"compiler/ast"
+	"compiler/constants"
 	"compiler/errors"
 	"compiler/nir/instruction"
 	"compiler/nir/value"
+	"compiler/token"
 	"compiler/types"
 	"fmt"
+	"maps"
+	"sort"
+	"strings"
 )
 
 // Lowerer converts AST to NIR
@@ -15,6 +20,26 @@ type Lowerer struct {
 	builder         *Builder
 	currentFunction *Function
 	errorCollector  *errors.ErrorCollector
+	expressionDepth int
+	variableTypes   map[string]types.Type  // types of the locals visible in the current function
+	declarations    map[string]token.Token // where each local of the current function was declared
+	scopeLocals     map[string]bool        // locals declared in the innermost scope, which may not be declared again
+	storageNames    map[string]string      // NIR variable of each local whose name is shadowed by or shadows another
+	shadowCount     int                    // shadowing locals of the current function, numbering their storage
+	mutableLocals   map[string]bool        // locals of the current function declared with let mut or :=
+	loopVariables   map[string]bool        // counters of the for loops being lowered
+
+	globals            map[string]*value.Global
+	mutableGlobals     map[string]bool
+	globalInitializers []globalInitializer // globals that main stores before running its body
+
+	functionSignatures map[string]*types.FunctionType // parameter and return types of every function in the program
+}
+
+// globalInitializer records a global whose value is stored at the top of main
+type globalInitializer struct {
+	variable *value.Global
+	value    value.Value
 }
 
 func NewLowerer(errorCollector *errors.ErrorCollector) *Lowerer {
@@ -22,15 +47,63 @@ func NewLowerer(errorCollector *errors.ErrorCollector) *Lowerer {
 		builder:         NewBuilder(),
 		currentFunction: nil,
 		errorCollector:  errorCollector,
+		globals:         map[string]*value.Global{},
+		mutableGlobals:  map[string]bool{},
+
+		functionSignatures: map[string]*types.FunctionType{},
 	}
 }
 
+// VisibleVariables returns the type of every global and of every local of the last function lowered
+// Locals shadow globals of the same name; editor tooling lowers a function cut off at the cursor to see its scope
+func (lowerer *Lowerer) VisibleVariables() map[string]types.Type {
+	visible := map[string]types.Type{}
+	for name, global := range lowerer.globals {
+		visible[name] = global.Type()
+	}
+	for name, variableType := range lowerer.variableTypes {
+		visible[name] = variableType
+	}
+	return visible
+}
+
 func (lowerer *Lowerer) Lower(program *ast.Program) *Module {
 	module := NewModule("main")
 
+	for _, global := range program.Globals {
+		lowerer.lowerGlobal(module, global)
+	}
+
+	// Collect signatures first so calls to functions defined later know their result type
+	// Calls to a builtin's name never reach a function of that name, so defining one is an error
+	// A function with an unknown type in its signature is reported once, at the annotation, and not lowered
+	skipped := map[*ast.FunctionStatement]bool{}
+	for _, statement := range program.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok {
+			if IsBuiltin(function.Name.Value) {
+				lowerer.errorCollector.Add(
+					errors.TypeError,
+					function.Name.Token.Line,
+					function.Name.Token.Column,
+					len(function.Name.Value),
+					"Function %s conflicts with builtin function",
+					function.Name.Value,
+				)
+				skipped[function] = true
+				continue
+			}
+			signature := lowerer.functionSignature(function)
+			lowerer.functionSignatures[function.Name.Value] = signature
+			skipped[function] = !signatureResolved(signature)
+		}
+	}
+
 	for _, statement := range program.Statements {
 		switch stmt := statement.(type) {
 		case *ast.FunctionStatement:
+			if skipped[stmt] {
+				continue
+			}
 			function := lowerer.lowerFunction(stmt)
 			if function != nil {
 				module.AddFunction(function)
@@ -47,61 +120,235 @@ func (lowerer *Lowerer) Lower(program *ast.Program) *Module {
 	return module
 }
 
+// RequireMain reports a lowered program that does not define main,
+// which would otherwise only surface as an undefined reference when linking
+// Test files are not checked, since LowerEntry gives each test its own main
+func (lowerer *Lowerer) RequireMain() {
+	if _, defined := lowerer.functionSignatures["main"]; defined {
+		return
+	}
+
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		0, 0, 0,
+		"No main function defined; a program starts by calling func main()",
+	)
+}
+
+// LowerEntry returns a copy of module whose main is entry, lowered against the program Lower has seen
+// The other functions are shared with module, so one lowered program can be given many entry points
+func (lowerer *Lowerer) LowerEntry(module *Module, entry *ast.FunctionStatement) *Module {
+	entryModule := NewModule(module.Name)
+	entryModule.Globals = module.Globals
+	for _, function := range module.Functions {
+		if function.Name != "main" {
+			entryModule.AddFunction(function)
+		}
+	}
+
+	if function := lowerer.lowerFunction(entry); function != nil {
+		entryModule.AddFunction(function)
+	}
+
+	return entryModule
+}
+
+// lowerGlobal lowers a top-level let statement to a module global
+// Example: let mut counter: int = 0
+//
+//	→ global @counter: int = Constant(0)
+func (lowerer *Lowerer) lowerGlobal(module *Module, letStmt *ast.LetStatement) {
+	name := letStmt.Name.Value
+	if _, exists := lowerer.globals[name]; exists {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			letStmt.Name.Token.Line,
+			letStmt.Name.Token.Column,
+			len(name),
+			"Global %s is already defined",
+			name,
+		)
+		return
+	}
+
+	// Globals are initialized before any code runs, so only literals are allowed
+	switch letStmt.Value.(type) {
+	case *ast.IntegerLiteral, *ast.BooleanLiteral, *ast.CharLiteral, *ast.StringLiteral:
+	default:
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			letStmt.Token.Line,
+			letStmt.Token.Column,
+			len(letStmt.Token.Value),
+			"Global %s must be initialized with a literal",
+			name,
+		)
+		return
+	}
+
+	var declaredType types.Type
+	if letStmt.TypeAnnotation != nil {
+		declaredType = lowerer.getType(letStmt.TypeAnnotation)
+		if declaredType == nil {
+			return
+		}
+	}
+
+	initializer := lowerer.lowerExpression(letStmt.Value)
+	if initializer == nil {
+		return
+	}
+
+	if declaredType != nil {
+		if !declaredType.Equals(initializer.Type()) {
+			lowerer.errorCollector.Add(
+				errors.TypeError,
+				letStmt.TypeAnnotation.Token.Line,
+				letStmt.TypeAnnotation.Token.Column,
+				len(letStmt.TypeAnnotation.Value),
+				"Global %s is declared as %s but initialized with %s",
+				name,
+				declaredType.String(),
+				initializer.Type().String(),
+			)
+			return
+		}
+	}
+
+	variable := lowerer.builder.CreateGlobal(name, initializer.Type())
+	lowerer.globals[name] = variable
+	lowerer.mutableGlobals[name] = letStmt.Mutable
+
+	// String constants are materialized by codegen, so main stores them at startup
+	if initializer.Type() == types.String {
+		module.AddGlobal(NewGlobal(variable, letStmt.Mutable, nil))
+		lowerer.globalInitializers = append(lowerer.globalInitializers, globalInitializer{variable, initializer})
+		return
+	}
+
+	module.AddGlobal(NewGlobal(variable, letStmt.Mutable, initializer))
+}
+
+// functionReturnType returns the declared return type of a function
+// main always returns the process exit code; functions without an annotation return nil
+func (lowerer *Lowerer) functionReturnType(astFunc *ast.FunctionStatement) types.Type {
+	if astFunc.Name.Value == "main" {
+		return types.Int
+	}
+	if astFunc.ReturnType != nil {
+		return lowerer.getType(astFunc.ReturnType)
+	}
+	return types.Nil
+}
+
+// signatureResolved reports whether every type in a signature is known
+// An unknown type is nil in the signature, and was reported at its annotation when the signature was built
+func signatureResolved(signature *types.FunctionType) bool {
+	for _, parameterType := range signature.ParameterTypes {
+		if parameterType == nil {
+			return false
+		}
+	}
+	return signature.ReturnType != nil
+}
+
+// functionSignature returns the parameter and return types calls to a function are checked against
+func (lowerer *Lowerer) functionSignature(astFunc *ast.FunctionStatement) *types.FunctionType {
+	parameterTypes := make([]types.Type, len(astFunc.Parameters))
+	for i, param := range astFunc.Parameters {
+		parameterTypes[i] = lowerer.getType(&param.Type)
+	}
+
+	return &types.FunctionType{
+		ParameterTypes: parameterTypes,
+		ReturnType:     lowerer.functionReturnType(astFunc),
+	}
+}
+
 func (lowerer *Lowerer) lowerFunction(astFunc *ast.FunctionStatement) *Function {
 	// Reset builder for new function
 	lowerer.builder.Reset()
+	lowerer.variableTypes = map[string]types.Type{}
+	lowerer.declarations = map[string]token.Token{}
+	lowerer.scopeLocals = map[string]bool{}
+	lowerer.storageNames = map[string]string{}
+	lowerer.shadowCount = 0
+	lowerer.mutableLocals = map[string]bool{}
+	lowerer.loopVariables = map[string]bool{}
 
 	// Convert parameters
 	var parameters []Parameter
 	for _, param := range astFunc.Parameters {
-		// For now, assume all parameters are int type
-		// TODO: Use type annotations when type system is implemented
+		parameterType := lowerer.getType(&param.Type)
+		lowerer.variableTypes[param.Name.Value] = parameterType
 		parameters = append(parameters, NewParameter(
 			param.Name.Value,
-			types.Int,
+			parameterType,
 		))
 	}
 
-	// Determine return type
-	// For now, default to nil
-	// TODO: Use return type annotation when type system is implemented
-	var returnType types.Type = types.Nil
-
-	if astFunc.Name.Value == "main" {
-		returnType = types.Int
-	} else if astFunc.ReturnType != nil {
-		returnType = lowerer.getType(astFunc.ReturnType)
-	}
+	returnType := lowerer.functionReturnType(astFunc)
 
 	// Create NIR function
 	function := NewFunction(astFunc.Name.Value, parameters, returnType)
+	for _, annotation := range astFunc.Annotations {
+		if annotation.Name == "inline" {
+			function.Flags |= FunctionInline
+		}
+	}
 	lowerer.currentFunction = function
 
 	// Create entry block
-	entryBlock := NewBasicBlock("entry")
-	lowerer.builder.SetInsertBlock(entryBlock)
+	lowerer.startBlock(NewBasicBlock("entry"))
+
+	if astFunc.Name.Value == "main" {
+		for _, initializer := range lowerer.globalInitializers {
+			lowerer.builder.BuildStore(initializer.variable, initializer.value)
+		}
+	}
 
 	// Lower function body
 	lowerer.lowerBlockStatement(astFunc.Body)
 
 	// Add implicit return for void functions if missing
-	if !entryBlock.IsComplete() {
+	// Control falls off the end from the last block, such as the exit of a loop
+	if !lowerer.builder.GetInsertBlock().IsComplete() {
 		if astFunc.Name.Value == "main" {
 			lowerer.builder.BuildReturn(lowerer.builder.CreateConstantInt(0))
 
-		} else {
+		} else if returnType == types.Nil {
 			lowerer.builder.BuildReturn(nil)
+		} else {
+			lowerer.errorCollector.Add(
+				errors.TypeError,
+				astFunc.Name.Token.Line,
+				astFunc.Name.Token.Column,
+				len(astFunc.Name.Value),
+				"Function %s must return a value of type %s",
+				astFunc.Name.Value,
+				returnType.String(),
+			)
+			return nil
 		}
 	}
 
-	function.AddBasicBlock(entryBlock)
-
 	return function
 }
 
+// startBlock appends block to the current function and lowers what follows into it
+// Blocks are appended in the order they are filled, which codegen relies on
+func (lowerer *Lowerer) startBlock(block *BasicBlock) {
+	lowerer.currentFunction.AddBasicBlock(block)
+	lowerer.builder.SetInsertBlock(block)
+}
+
 // lowerBlockStatement lowers a block of statements
 func (lowerer *Lowerer) lowerBlockStatement(block *ast.BlockStatement) {
 	for _, statement := range block.Statements {
+		// Anything after a return or panic can never run
+		if lowerer.builder.GetInsertBlock().IsComplete() {
+			break
+		}
 		lowerer.lowerStatement(statement)
 	}
 }
@@ -113,6 +360,12 @@ func (lowerer *Lowerer) lowerStatement(statement ast.Statement) {
 		lowerer.lowerLetStatement(stmt)
 	case *ast.ReturnStatement:
 		lowerer.lowerReturnStatement(stmt)
+	case *ast.AssignmentStatement:
+		lowerer.lowerAssignmentStatement(stmt)
+	case *ast.IndexAssignmentStatement:
+		lowerer.lowerIndexAssignmentStatement(stmt)
+	case *ast.ForStatement:
+		lowerer.lowerForStatement(stmt)
 	case *ast.ExpressionStatement:
 		lowerer.lowerExpressionStatement(stmt)
 	default:
@@ -134,19 +387,354 @@ func (lowerer *Lowerer) lowerStatement(statement ast.Statement) {
 //	  %x = Alloc(int)
 //	  Store(%x, %2)
 func (lowerer *Lowerer) lowerLetStatement(letStmt *ast.LetStatement) {
-	// Lower the initialization expression
-	initValue := lowerer.lowerExpression(letStmt.Value)
+	// Lower the initialization expression, checking it against the declared type if there is one
+	var declaredType types.Type
+	if letStmt.TypeAnnotation != nil {
+		declaredType = lowerer.getType(letStmt.TypeAnnotation)
+		if declaredType == nil {
+			return
+		}
+	}
+
+	storage, ok := lowerer.declare(letStmt.Name)
+	if !ok {
+		return
+	}
+
+	initValue := lowerer.lowerExpressionAs(letStmt.Value, declaredType)
 	if initValue == nil {
 		return
 	}
 
+	if declaredType != nil && !declaredType.Equals(initValue.Type()) {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			letStmt.TypeAnnotation.Token.Line,
+			letStmt.TypeAnnotation.Token.Column,
+			len(letStmt.TypeAnnotation.Value),
+			"Variable %s is declared as %s but initialized with %s",
+			letStmt.Name.Value,
+			declaredType.String(),
+			initValue.Type().String(),
+		)
+		return
+	}
+
 	// Allocate variable
-	variable := lowerer.builder.BuildAlloc(letStmt.Name.Value, initValue.Type())
+	// Only let mut (or :=) locals get storage that can be written again
+	variable := lowerer.builder.BuildAlloc(storage, initValue.Type(), letStmt.Mutable)
+	lowerer.storageNames[letStmt.Name.Value] = storage
+	lowerer.variableTypes[letStmt.Name.Value] = initValue.Type()
+	lowerer.mutableLocals[letStmt.Name.Value] = letStmt.Mutable
+	delete(lowerer.loopVariables, letStmt.Name.Value)
 
 	// Store initial value
 	lowerer.builder.BuildStore(variable, initValue)
 }
 
+// declare records where a local is declared and returns the name of its NIR variable,
+// reporting a second declaration of the same name in the same scope
+// A local of a loop may shadow one of an enclosing scope; it then gets a variable of its own,
+// so the outer local keeps its value after the loop. Shadowing a global is allowed
+// The caller binds the name to the variable once it is allocated, so an initializer still sees the outer local
+func (lowerer *Lowerer) declare(name *ast.Identifier) (string, bool) {
+	if lowerer.scopeLocals[name.Value] {
+		first := lowerer.declarations[name.Value]
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			name.Token.Line,
+			name.Token.Column,
+			len(name.Value),
+			"Variable %s is already declared in this scope",
+			name.Value,
+		)
+		lowerer.errorCollector.AddNote(first.Line, first.Column, len(name.Value), "first declared here")
+		return "", false
+	}
+
+	// The '.' keeps the shadowing variable's name apart from any identifier
+	storage := name.Value
+	if _, visible := lowerer.variableTypes[name.Value]; visible {
+		lowerer.shadowCount++
+		storage = fmt.Sprintf("%s.%d", name.Value, lowerer.shadowCount)
+	}
+
+	lowerer.declarations[name.Value] = name.Token
+	lowerer.scopeLocals[name.Value] = true
+	return storage, true
+}
+
+// localVariable returns the NIR variable a visible local is stored in
+func (lowerer *Lowerer) localVariable(name string, variableType types.Type) value.Value {
+	storage, ok := lowerer.storageNames[name]
+	if !ok {
+		storage = name
+	}
+	return lowerer.builder.CreateVariable(storage, variableType)
+}
+
+// lowerAssignmentStatement lowers an assignment to a local or global declared with let mut
+// Example: x = x + 1
+//
+//	→ %0 = Load(%x)
+//	  %1 = Constant(1)
+//	  %2 = Add(%0, %1)
+//	  Store(%x, %2)
+func (lowerer *Lowerer) lowerAssignmentStatement(assignment *ast.AssignmentStatement) {
+	name := assignment.Name.Value
+
+	// Locals shadow globals, so a global is only the target when no local has the name
+	var target value.Value
+	if variableType, isLocal := lowerer.variableTypes[name]; isLocal {
+		declaration, isLet := lowerer.declarations[name]
+		if !isLet {
+			lowerer.reportAssignmentError(assignment, "Cannot assign to parameter %s; copy it into a let mut variable first", name)
+			return
+		}
+		if lowerer.loopVariables[name] {
+			lowerer.reportAssignmentError(assignment, "Cannot assign to loop variable %s; it takes each value of the range in turn", name)
+			return
+		}
+		if !lowerer.mutableLocals[name] {
+			lowerer.reportAssignmentError(assignment, "Cannot assign twice to immutable variable %s", name)
+			lowerer.errorCollector.AddNote(declaration.Line, declaration.Column, len(name),
+				"declared here; use let mut %s to make it mutable", name)
+			return
+		}
+		target = lowerer.localVariable(name, variableType)
+	} else if global, isGlobal := lowerer.globals[name]; isGlobal {
+		if !lowerer.mutableGlobals[name] {
+			lowerer.reportAssignmentError(assignment, "Cannot assign to immutable global %s", name)
+			return
+		}
+		target = global
+	} else {
+		lowerer.reportAssignmentError(assignment, "Undefined variable: %s", name)
+		return
+	}
+
+	newValue := lowerer.lowerExpression(assignment.Value)
+	if newValue == nil {
+		return
+	}
+
+	if !newValue.Type().Equals(target.Type()) {
+		lowerer.reportAssignmentError(assignment, "Cannot assign a value of type %s to %s of type %s",
+			newValue.Type().String(), name, target.Type().String())
+		return
+	}
+
+	lowerer.builder.BuildStore(target, newValue)
+}
+
+// reportAssignmentError reports an assignment that cannot be lowered, pointing at the assigned name
+func (lowerer *Lowerer) reportAssignmentError(assignment *ast.AssignmentStatement, format string, args ...interface{}) {
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		assignment.Name.Token.Line,
+		assignment.Name.Token.Column,
+		len(assignment.Name.Value),
+		format,
+		args...,
+	)
+}
+
+// lowerIndexAssignmentStatement lowers a store into one element of an array
+// Arrays are passed by reference, so the element may be assigned through any binding,
+// a parameter or an immutable local included, and every holder of the array sees it
+// Example: xs[0] = 10
+//
+//	→ %0 = Load(%xs)
+//	  %1 = Constant(0)
+//	  %2 = Constant(10)
+//	  SetElement(%0, %1, %2)
+func (lowerer *Lowerer) lowerIndexAssignmentStatement(assignment *ast.IndexAssignmentStatement) {
+	target := assignment.Target
+	array := lowerer.lowerExpression(target.Left)
+	if array == nil {
+		return
+	}
+	position := lowerer.lowerExpression(target.Index)
+	if position == nil {
+		return
+	}
+
+	arrayType, isArray := array.Type().(*types.ArrayType)
+	if !isArray {
+		lowerer.reportIndexAssignmentError(target, "Cannot assign to an element of %s; only array elements can be assigned",
+			array.Type().String())
+		return
+	}
+	if position.Type() != types.Int {
+		lowerer.reportIndexAssignmentError(target, "Array index must be int, found %s", position.Type().String())
+		return
+	}
+
+	newValue := lowerer.lowerExpressionAs(assignment.Value, arrayType.Element)
+	if newValue == nil {
+		return
+	}
+	if !newValue.Type().Equals(arrayType.Element) {
+		lowerer.reportIndexAssignmentError(target, "Cannot assign a value of type %s to an element of %s",
+			newValue.Type().String(), array.Type().String())
+		return
+	}
+
+	lowerer.builder.BuildSetElement(array, position, newValue)
+}
+
+// reportIndexAssignmentError reports an element assignment that cannot be lowered, pointing at the target's '['
+func (lowerer *Lowerer) reportIndexAssignmentError(target *ast.IndexExpression, format string, args ...interface{}) {
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		target.Token.Line,
+		target.Token.Column,
+		len(target.Token.Value),
+		format,
+		args...,
+	)
+}
+
+// lowerForStatement lowers a range loop into a condition block, a body block and an exit block
+// Both bounds are evaluated once, before the first iteration
+// A loop over a string walks its byte offsets from 0 to its length, measured once, and binds
+// the variable to the character decoded at each offset; the offset then skips that character's bytes
+// Example: for i in 0..n { print(i) }
+//
+//	→ %i = AllocMut(int)
+//	  Store(i, %0)
+//	  Branch(for.cond.0)
+//	for.cond.0:
+//	  %2 = Load(i)
+//	  %3 = Less(%2, %1)
+//	  Branch(%3, for.body.0, for.end.0)
+//	for.body.0:
+//	  ...
+//	  Store(i, %5)
+//	  Branch(for.cond.0)
+func (lowerer *Lowerer) lowerForStatement(forStmt *ast.ForStatement) {
+	var start, end, iterable value.Value
+	if forStmt.Iterable != nil {
+		iterable = lowerer.lowerExpression(forStmt.Iterable)
+		if iterable == nil {
+			return
+		}
+		if iterable.Type() != types.String {
+			lowerer.errorCollector.Add(
+				errors.TypeError,
+				forStmt.Token.Line,
+				forStmt.Token.Column,
+				len(forStmt.Token.Value),
+				"for loops iterate over a range or a string, found %s",
+				iterable.Type().String(),
+			)
+			return
+		}
+		start = lowerer.builder.CreateConstantInt(0)
+		end = lowerer.builder.BuildCall("naviary_string_length", []value.Value{iterable}, types.Int)
+	} else {
+		start = lowerer.lowerExpression(forStmt.Start)
+		if start == nil {
+			return
+		}
+		end = lowerer.lowerExpression(forStmt.End)
+		if end == nil {
+			return
+		}
+
+		bounds := []struct {
+			expression ast.Expression
+			value      value.Value
+		}{{forStmt.Start, start}, {forStmt.End, end}}
+		for _, bound := range bounds {
+			if bound.value.Type() != types.Int {
+				position := expressionToken(bound.expression)
+				lowerer.errorCollector.Add(
+					errors.TypeError,
+					position.Line,
+					position.Column,
+					len(position.Value),
+					"for-loop bounds must be int, found %s",
+					bound.value.Type().String(),
+				)
+				return
+			}
+		}
+	}
+
+	// The loop variable and the body's locals go out of scope after the loop
+	// They form a scope of their own, so they may shadow the locals around the loop
+	outerTypes := maps.Clone(lowerer.variableTypes)
+	outerDeclarations := maps.Clone(lowerer.declarations)
+	outerScope := lowerer.scopeLocals
+	outerStorage := maps.Clone(lowerer.storageNames)
+	outerMutable := maps.Clone(lowerer.mutableLocals)
+	outerLoopVariables := maps.Clone(lowerer.loopVariables)
+	lowerer.scopeLocals = map[string]bool{}
+	defer func() {
+		lowerer.variableTypes = outerTypes
+		lowerer.declarations = outerDeclarations
+		lowerer.scopeLocals = outerScope
+		lowerer.storageNames = outerStorage
+		lowerer.mutableLocals = outerMutable
+		lowerer.loopVariables = outerLoopVariables
+	}()
+
+	name := forStmt.Variable.Value
+	storage, ok := lowerer.declare(forStmt.Variable)
+	if !ok {
+		return
+	}
+
+	// The loop advances the counter through its storage, but the body may not assign it
+	// A string loop's counter is hidden; the '.' keeps its name apart from any identifier
+	var counter, element value.Value
+	if iterable != nil {
+		counter = lowerer.builder.BuildAlloc(storage+".index", types.Int, true)
+		element = lowerer.builder.BuildAlloc(storage, types.Char, true)
+		lowerer.variableTypes[name] = types.Char
+	} else {
+		counter = lowerer.builder.BuildAlloc(storage, types.Int, true)
+		lowerer.variableTypes[name] = types.Int
+	}
+	lowerer.storageNames[name] = storage
+	lowerer.mutableLocals[name] = false
+	lowerer.loopVariables[name] = true
+	lowerer.builder.BuildStore(counter, start)
+
+	id := lowerer.builder.NextBlockID()
+	condition := NewBasicBlock(fmt.Sprintf("for.cond.%d", id))
+	body := NewBasicBlock(fmt.Sprintf("for.body.%d", id))
+	exit := NewBasicBlock(fmt.Sprintf("for.end.%d", id))
+
+	lowerer.builder.BuildBranch(condition)
+	lowerer.startBlock(condition)
+	operator := instruction.CompareLess
+	if forStmt.Inclusive {
+		operator = instruction.CompareLessEqual
+	}
+	inRange := lowerer.builder.BuildCompare(lowerer.builder.BuildLoad(counter), end, operator)
+	lowerer.builder.BuildConditionalBranch(inRange, body, exit)
+
+	lowerer.startBlock(body)
+	step := lowerer.builder.CreateConstantInt(1)
+	if iterable != nil {
+		offset := lowerer.builder.BuildLoad(counter)
+		current := lowerer.builder.BuildCall("naviary_string_decode_char", []value.Value{iterable, offset}, types.Char)
+		lowerer.builder.BuildStore(element, current)
+		step = lowerer.builder.BuildCall("naviary_string_char_width", []value.Value{iterable, offset}, types.Int)
+	}
+	lowerer.lowerBlockStatement(forStmt.Body)
+	if !lowerer.builder.GetInsertBlock().IsComplete() {
+		next := lowerer.builder.BuildBinary(lowerer.builder.BuildLoad(counter), step, instruction.BinaryAdd)
+		lowerer.builder.BuildStore(counter, next)
+		lowerer.builder.BuildBranch(condition)
+	}
+
+	// The exit block is appended after any blocks of loops nested in the body
+	lowerer.startBlock(exit)
+}
+
 // lowerReturnStatement lowers a return statement
 // Example: return x + 1
 //
@@ -155,44 +743,150 @@ func (lowerer *Lowerer) lowerLetStatement(letStmt *ast.LetStatement) {
 //	  %2 = Add(%0, %1)
 //	  Return(%2)
 func (lowerer *Lowerer) lowerReturnStatement(returnStmt *ast.ReturnStatement) {
+	function := lowerer.currentFunction
+
 	if returnStmt.ReturnValue == nil {
+		if function.Name == "main" {
+			// A bare return from main exits successfully
+			lowerer.builder.BuildReturn(lowerer.builder.CreateConstantInt(0))
+			return
+		}
+		if function.ReturnType != types.Nil {
+			lowerer.reportReturnError(returnStmt, "Function %s must return a value of type %s",
+				function.Name, function.ReturnType.String())
+			return
+		}
+
 		// Return void
 		lowerer.builder.BuildReturn(nil)
 		return
 	}
 
 	// Lower return expression
-	returnValue := lowerer.lowerExpression(returnStmt.ReturnValue)
+	returnValue := lowerer.lowerExpressionAs(returnStmt.ReturnValue, function.ReturnType)
 	if returnValue == nil {
 		return
 	}
 
+	if function.ReturnType == types.Nil {
+		lowerer.reportReturnError(returnStmt, "Function %s does not declare a return type but returns %s",
+			function.Name, returnValue.Type().String())
+		return
+	}
+	if !returnValue.Type().Equals(function.ReturnType) {
+		lowerer.reportReturnError(returnStmt, "Function %s returns %s but the return value is %s",
+			function.Name, function.ReturnType.String(), returnValue.Type().String())
+		return
+	}
+
+	// return f(x) returns the call's result unchanged, so the call is in tail position
+	if _, isCall := returnStmt.ReturnValue.(*ast.CallExpression); isCall {
+		lowerer.builder.MarkTailCall(returnValue)
+	}
+
 	lowerer.builder.BuildReturn(returnValue)
 }
 
+// reportReturnError reports a return statement that does not match its function's return type
+// The block still ends here, so the function is not also reported as missing its return
+func (lowerer *Lowerer) reportReturnError(returnStmt *ast.ReturnStatement, format string, args ...interface{}) {
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		returnStmt.Token.Line,
+		returnStmt.Token.Column,
+		len(returnStmt.Token.Value),
+		format,
+		args...,
+	)
+	lowerer.builder.BuildUnreachable()
+}
+
 // lowerExpressionStatement lowers an expression statement
 // Example: print(42)
 //
 //	→ %0 = Constant(42)
 //	  Call(print, [%0])
 func (lowerer *Lowerer) lowerExpressionStatement(exprStmt *ast.ExpressionStatement) {
+	// A call statement discards its result, so only here may the function return nothing
+	if call, isCall := exprStmt.Expression.(*ast.CallExpression); isCall {
+		lowerer.lowerCallExpression(call)
+		return
+	}
 	lowerer.lowerExpression(exprStmt.Expression)
 }
 
+// expressionToken returns the token an expression is reported at
+func expressionToken(expr ast.Expression) token.Token {
+	switch expression := expr.(type) {
+	case *ast.IntegerLiteral:
+		return expression.Token
+	case *ast.StringLiteral:
+		return expression.Token
+	case *ast.BooleanLiteral:
+		return expression.Token
+	case *ast.CharLiteral:
+		return expression.Token
+	case *ast.Identifier:
+		return expression.Token
+	case *ast.BinaryExpression:
+		return expression.Token
+	case *ast.CallExpression:
+		return expression.Token
+	case *ast.AscriptionExpression:
+		return expression.Token
+	case *ast.IndexExpression:
+		return expression.Token
+	case *ast.ArrayLiteral:
+		return expression.Token
+	default:
+		return token.Token{}
+	}
+}
+
 // lowerExpression lowers an expression to a NIR value
 // This is where complex nested expressions get flattened
 func (lowerer *Lowerer) lowerExpression(expr ast.Expression) value.Value {
+	// Left-associative chains like 1+1+1+... are built iteratively by the parser
+	// but still recurse here, so the depth is checked again
+	if lowerer.expressionDepth >= constants.MAX_EXPRESSION_DEPTH {
+		position := expressionToken(expr)
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			position.Line,
+			position.Column,
+			len(position.Value),
+			"expression too deeply nested (limit is %d)",
+			constants.MAX_EXPRESSION_DEPTH,
+		)
+		return nil
+	}
+	lowerer.expressionDepth++
+	defer func() { lowerer.expressionDepth-- }()
+
 	switch expression := expr.(type) {
 	case *ast.IntegerLiteral:
 		return lowerer.lowerIntegerLiteral(expression)
 	case *ast.StringLiteral:
 		return lowerer.lowerStringLiteral(expression)
+	case *ast.BooleanLiteral:
+		return lowerer.lowerBooleanLiteral(expression)
+	case *ast.CharLiteral:
+		return lowerer.lowerCharLiteral(expression)
 	case *ast.Identifier:
 		return lowerer.lowerIdentifier(expression)
 	case *ast.BinaryExpression:
 		return lowerer.lowerBinaryExpression(expression)
 	case *ast.CallExpression:
+		if !lowerer.checkCallReturnsValue(expression) {
+			return nil
+		}
 		return lowerer.lowerCallExpression(expression)
+	case *ast.AscriptionExpression:
+		return lowerer.lowerAscriptionExpression(expression)
+	case *ast.IndexExpression:
+		return lowerer.lowerIndexExpression(expression)
+	case *ast.ArrayLiteral:
+		return lowerer.lowerArrayLiteral(expression, nil)
 	default:
 		lowerer.errorCollector.Add(
 			errors.SyntaxError,
@@ -220,12 +914,39 @@ func (lowerer *Lowerer) lowerStringLiteral(literal *ast.StringLiteral) value.Val
 	return lowerer.builder.CreateConstantString(literal.Value)
 }
 
+// lowerBooleanLiteral converts true or false to a constant
+func (lowerer *Lowerer) lowerBooleanLiteral(literal *ast.BooleanLiteral) value.Value {
+	return lowerer.builder.CreateConstantBool(literal.Value)
+}
+
+// lowerCharLiteral converts a character literal to a constant
+func (lowerer *Lowerer) lowerCharLiteral(literal *ast.CharLiteral) value.Value {
+	return lowerer.builder.CreateConstantChar(literal.Value)
+}
+
 // lowerIdentifier converts an identifier to a load instruction
 // Example: x  →  %0 = Load(%x)
 func (lowerer *Lowerer) lowerIdentifier(identifier *ast.Identifier) value.Value {
+	variableType, ok := lowerer.variableTypes[identifier.Value]
+	if !ok {
+		// Locals shadow globals, so globals are only consulted as a fallback
+		if global, isGlobal := lowerer.globals[identifier.Value]; isGlobal {
+			return lowerer.builder.BuildLoad(global)
+		}
+
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			identifier.Token.Line,
+			identifier.Token.Column,
+			len(identifier.Value),
+			"Undefined variable: %s",
+			identifier.Value,
+		)
+		return nil
+	}
+
 	// Create variable reference
-	// TODO: Look up actual variable from symbol table
-	variable := lowerer.builder.CreateVariable(identifier.Value, types.Int)
+	variable := lowerer.localVariable(identifier.Value, variableType)
 
 	// Load the value
 	return lowerer.builder.BuildLoad(variable)
@@ -238,7 +959,7 @@ func (lowerer *Lowerer) lowerIdentifier(identifier *ast.Identifier) value.Value
 //	  %1 = Constant(2)
 //	  %2 = Add(%0, %1)
 func (lowerer *Lowerer) lowerBinaryExpression(binary *ast.BinaryExpression) value.Value {
-	// Lower left and right operands first
+	// Lower left and right operands first, in that order: evaluation is left to right
 	left := lowerer.lowerExpression(binary.Left)
 	if left == nil {
 		return nil
@@ -249,6 +970,29 @@ func (lowerer *Lowerer) lowerBinaryExpression(binary *ast.BinaryExpression) valu
 		return nil
 	}
 
+	if binary.Operator == "in" {
+		return lowerer.lowerMembership(binary, left, right)
+	}
+
+	if lowerer.isChainedComparison(binary) {
+		chained := binary.Left.(*ast.BinaryExpression)
+		lowerer.reportOperatorError(binary, "comparison operators cannot be chained; write `%s %s %s && %s %s %s`",
+			chained.Left.String(), chained.Operator, chained.Right.String(),
+			chained.Right.String(), binary.Operator, binary.Right.String())
+		return nil
+	}
+
+	if compareOperator, ok := compareOperators[binary.Operator]; ok {
+		if !lowerer.checkComparisonOperands(binary, compareOperator, left, right) {
+			return nil
+		}
+		return lowerer.builder.BuildCompare(left, right, compareOperator)
+	}
+
+	if !lowerer.checkArithmeticOperands(binary, left, right) {
+		return nil
+	}
+
 	// Generate appropriate instruction based on operator
 	switch binary.Operator {
 	case "+":
@@ -270,6 +1014,269 @@ func (lowerer *Lowerer) lowerBinaryExpression(binary *ast.BinaryExpression) valu
 	}
 }
 
+// lowerMembership lowers `value in collection`
+// Int arrays reuse find's search, compared against its -1 for none;
+// string arrays call the runtime, which compares by content
+// Example: x in xs
+//
+//	→ %2 = Call(naviary_array_find_int, %1, %0)
+//	  %3 = NotEqual(%2, -1)
+func (lowerer *Lowerer) lowerMembership(binary *ast.BinaryExpression, left value.Value, right value.Value) value.Value {
+	arrayType, isArray := right.Type().(*types.ArrayType)
+	if !isArray {
+		lowerer.reportOperatorError(binary, "Operator 'in' requires an array on the right, got %s",
+			right.Type().String())
+		return nil
+	}
+
+	if arrayType.Element != types.Int && arrayType.Element != types.String {
+		lowerer.reportOperatorError(binary, "Operator 'in' is only supported for int[] and string[], got %s",
+			arrayType.String())
+		return nil
+	}
+
+	if left.Type() != arrayType.Element {
+		lowerer.reportOperatorError(binary, "Cannot search %s for %s using 'in'",
+			arrayType.String(), left.Type().String())
+		return nil
+	}
+
+	if arrayType.Element == types.String {
+		return lowerer.builder.BuildCall("naviary_array_contains_string", []value.Value{right, left}, types.Bool)
+	}
+
+	index := lowerer.builder.BuildCall("naviary_array_find_int", []value.Value{right, left}, types.Int)
+	return lowerer.builder.BuildCompare(index, lowerer.builder.CreateConstantInt(-1), instruction.CompareNotEqual)
+}
+
+// isChainedComparison reports comparisons written as `a < b < c`,
+// which parse as `(a < b) < c` instead of comparing b against both sides
+// A left comparison in explicit parentheses, as in `(a < b) == flag`, is allowed
+func (lowerer *Lowerer) isChainedComparison(binary *ast.BinaryExpression) bool {
+	if _, ok := compareOperators[binary.Operator]; !ok {
+		return false
+	}
+
+	left, ok := binary.Left.(*ast.BinaryExpression)
+	if !ok || left.Grouped {
+		return false
+	}
+
+	_, leftIsComparison := compareOperators[left.Operator]
+	return leftIsComparison
+}
+
+// compareOperators maps comparison operator spellings to NIR compare operators
+var compareOperators = map[string]instruction.CompareOperator{
+	"==": instruction.CompareEqual,
+	"!=": instruction.CompareNotEqual,
+	"<":  instruction.CompareLess,
+	">":  instruction.CompareGreater,
+	"<=": instruction.CompareLessEqual,
+	">=": instruction.CompareGreaterEqual,
+}
+
+// checkComparisonOperands requires both operands to share a type.
+// Ordering comparisons are defined for int, float and char (by code point);
+// bools support == and != only
+func (lowerer *Lowerer) checkComparisonOperands(binary *ast.BinaryExpression, operator instruction.CompareOperator, left value.Value, right value.Value) bool {
+	if !left.Type().Equals(right.Type()) {
+		lowerer.reportOperatorError(binary, "Cannot compare %s with %s using '%s'",
+			left.Type().String(), right.Type().String(), binary.Operator)
+		return false
+	}
+
+	operandType := left.Type()
+	switch {
+	case operandType == types.Int || operandType == types.Float || operandType == types.Char:
+		return true
+	case operandType == types.Bool && !operator.IsOrdering():
+		return true
+	default:
+		lowerer.reportOperatorError(binary, "Operator '%s' is not supported for %s operands",
+			binary.Operator, operandType.String())
+		return false
+	}
+}
+
+// checkArithmeticOperands requires both operands to be the same numeric type
+func (lowerer *Lowerer) checkArithmeticOperands(binary *ast.BinaryExpression, left value.Value, right value.Value) bool {
+	if !left.Type().Equals(right.Type()) {
+		lowerer.reportOperatorError(binary, "Mismatched operand types for '%s': %s and %s",
+			binary.Operator, left.Type().String(), right.Type().String())
+		return false
+	}
+
+	if left.Type() != types.Int && left.Type() != types.Float {
+		lowerer.reportOperatorError(binary, "Operator '%s' is not supported for %s operands",
+			binary.Operator, left.Type().String())
+		return false
+	}
+
+	return true
+}
+
+func (lowerer *Lowerer) reportOperatorError(binary *ast.BinaryExpression, format string, args ...any) {
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		binary.Token.Line,
+		binary.Token.Column,
+		len(binary.Operator),
+		format,
+		args...,
+	)
+}
+
+// lowerAscriptionExpression checks an expression against its ascribed type
+// The ascription emits nothing; the inner expression's value is used as is
+// Example: x as int  →  %0 = Load(%x)
+func (lowerer *Lowerer) lowerAscriptionExpression(ascription *ast.AscriptionExpression) value.Value {
+	inner := lowerer.lowerExpression(ascription.Expression)
+	if inner == nil {
+		return nil
+	}
+
+	ascribedType := types.GetPrimitiveType(ascription.Type.Value)
+	if ascribedType == nil {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			ascription.Type.Token.Line,
+			ascription.Type.Token.Column,
+			len(ascription.Type.Value),
+			"Unknown type: %s",
+			ascription.Type.Value,
+		)
+		return nil
+	}
+
+	if !inner.Type().Equals(ascribedType) {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			ascription.Token.Line,
+			ascription.Token.Column,
+			len(ascription.Token.Value),
+			"Expression of type %s cannot be ascribed type %s",
+			inner.Type().String(),
+			ascribedType.String(),
+		)
+		return nil
+	}
+
+	return inner
+}
+
+// lowerIndexExpression lowers s[i] on a string to the char_at builtin, and xs[i] on an array to an element read
+// Strings are indexed byte-wise; the runtime aborts on an out-of-range index of either
+// Example: name[0]
+//
+//	→ %0 = Load(%name)
+//	  %1 = Constant(0)
+//	  %2 = Call(char_at, [%0, %1])
+func (lowerer *Lowerer) lowerIndexExpression(index *ast.IndexExpression) value.Value {
+	left := lowerer.lowerExpression(index.Left)
+	if left == nil {
+		return nil
+	}
+	position := lowerer.lowerExpression(index.Index)
+	if position == nil {
+		return nil
+	}
+
+	_, isArray := left.Type().(*types.ArrayType)
+	if left.Type() != types.String && !isArray {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			index.Token.Line,
+			index.Token.Column,
+			len(index.Token.Value),
+			"Cannot index a value of type %s",
+			left.Type().String(),
+		)
+		return nil
+	}
+	if position.Type() != types.Int {
+		kind := "String"
+		if isArray {
+			kind = "Array"
+		}
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			index.Token.Line,
+			index.Token.Column,
+			len(index.Token.Value),
+			"%s index must be int, found %s",
+			kind,
+			position.Type().String(),
+		)
+		return nil
+	}
+
+	if isArray {
+		return lowerer.builder.BuildElement(left, position)
+	}
+	return lowerer.builder.BuildCall("char_at", []value.Value{left, position}, types.Char)
+}
+
+// lowerArrayLiteral lowers the elements left to right, then builds the array from them
+// The element type comes from expected when the context gives one, and otherwise from the first element;
+// every element must have it
+// Example: [1, 2]
+//
+//	→ %0 = MakeArray([Constant(1), Constant(2)])
+func (lowerer *Lowerer) lowerArrayLiteral(array *ast.ArrayLiteral, expected types.Type) value.Value {
+	var elementType types.Type
+	if arrayType, ok := expected.(*types.ArrayType); ok {
+		elementType = arrayType.Element
+	}
+
+	if len(array.Elements) == 0 && elementType == nil {
+		lowerer.reportArrayError(array, "Cannot infer the element type of an empty array; declare it, as in let xs: int[] = []")
+		return nil
+	}
+
+	elements := make([]value.Value, len(array.Elements))
+	for i, element := range array.Elements {
+		elementValue := lowerer.lowerExpressionAs(element, elementType)
+		if elementValue == nil {
+			return nil
+		}
+		if elementType == nil {
+			elementType = elementValue.Type()
+		}
+		if !elementValue.Type().Equals(elementType) {
+			lowerer.reportArrayError(array, "Array elements must all be %s, but element %d is %s",
+				elementType.String(), i+1, elementValue.Type().String())
+			return nil
+		}
+		elements[i] = elementValue
+	}
+
+	return lowerer.builder.BuildMakeArray(elementType, elements)
+}
+
+// lowerExpressionAs lowers an expression whose type its context already knows,
+// which is how an empty array literal gets its element type
+// Example: let xs: int[] = []  →  %0 = MakeArray([])
+func (lowerer *Lowerer) lowerExpressionAs(expr ast.Expression, expected types.Type) value.Value {
+	if array, ok := expr.(*ast.ArrayLiteral); ok {
+		return lowerer.lowerArrayLiteral(array, expected)
+	}
+
+	return lowerer.lowerExpression(expr)
+}
+
+// reportArrayError reports an array literal that cannot be lowered, pointing at its '['
+func (lowerer *Lowerer) reportArrayError(array *ast.ArrayLiteral, format string, args ...any) {
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		array.Token.Line,
+		array.Token.Column,
+		len(array.Token.Value),
+		format,
+		args...,
+	)
+}
+
 // lowerCallExpression lowers a function call
 // Example: print(42)
 //
@@ -277,10 +1284,8 @@ func (lowerer *Lowerer) lowerBinaryExpression(binary *ast.BinaryExpression) valu
 //	  Call(print, [%0])
 func (lowerer *Lowerer) lowerCallExpression(call *ast.CallExpression) value.Value {
 	// Get function name
-	functionName := ""
-	if ident, ok := call.Function.(*ast.Identifier); ok {
-		functionName = ident.Value
-	} else {
+	ident, ok := call.Function.(*ast.Identifier)
+	if !ok {
 		lowerer.errorCollector.Add(
 			errors.SyntaxError,
 			0, 0, 0,
@@ -288,36 +1293,407 @@ func (lowerer *Lowerer) lowerCallExpression(call *ast.CallExpression) value.Valu
 		)
 		return nil
 	}
+	functionName := ident.Value
+	signature, isFunction := lowerer.functionSignatures[functionName]
 
-	// Lower arguments
+	// Lower arguments left to right, each exactly once and all before the call itself
+	// Codegen only reads the resulting values, so no lowering of a call (print
+	// included) may evaluate an argument expression again
+	// A parameter's type is the expected type of its argument, so f([]) types the empty array
 	var arguments []value.Value
-	for _, arg := range call.Arguments {
-		argValue := lowerer.lowerExpression(arg)
+	for i, arg := range call.Arguments {
+		var expected types.Type
+		if isFunction && i < len(signature.ParameterTypes) {
+			expected = signature.ParameterTypes[i]
+		}
+		argValue := lowerer.lowerExpressionAs(arg, expected)
 		if argValue == nil {
 			return nil
 		}
 		arguments = append(arguments, argValue)
 	}
 
-	// For now, assume all functions are void
-	// TODO: Look up function signature from symbol table
-	return lowerer.builder.BuildCall(functionName, arguments, nil)
+	if functionName == "panic" {
+		return lowerer.lowerPanic(call, arguments)
+	}
+	if functionName == "swap" {
+		return lowerer.lowerSwap(call, arguments)
+	}
+	if functionName == "len" {
+		return lowerer.lowerLength(call, arguments)
+	}
+	if functionName == "clear" || functionName == "truncate" {
+		return lowerer.lowerResize(call, functionName, arguments)
+	}
+	if floatFunction, ok := reductions[functionName]; ok {
+		return lowerer.lowerReduction(call, functionName, floatFunction, arguments)
+	}
+
+	if functionName == "print" {
+		for _, argument := range arguments {
+			if _, isArray := argument.Type().(*types.ArrayType); isArray {
+				lowerer.errorCollector.Add(
+					errors.TypeError,
+					call.Token.Line,
+					call.Token.Column,
+					len(call.Token.Value),
+					"print does not support %s yet; print its elements instead",
+					argument.Type().String(),
+				)
+				return nil
+			}
+		}
+		return lowerer.builder.BuildCall(functionName, arguments, nil)
+	}
+
+	if builtin, ok := builtinFunctions[functionName]; ok {
+		if !lowerer.checkCallArguments(call, functionName, builtin.parameterTypes, arguments) {
+			return nil
+		}
+		return lowerer.builder.BuildCall(functionName, arguments, builtin.returnType)
+	}
+
+	if !isFunction {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			ident.Token.Line,
+			ident.Token.Column,
+			len(ident.Value),
+			"Undefined function: %s",
+			functionName,
+		)
+		return nil
+	}
+	// Calls to a function whose signature names an unknown type are not checked; the type was reported
+	if !signatureResolved(signature) {
+		return nil
+	}
+	if !lowerer.checkCallArguments(call, functionName, signature.ParameterTypes, arguments) {
+		return nil
+	}
+
+	// Functions without a return type produce no value
+	var returnType types.Type
+	if signature.ReturnType != types.Nil {
+		returnType = signature.ReturnType
+	}
+
+	return lowerer.builder.BuildCall(functionName, arguments, returnType)
+}
+
+// checkCallReturnsValue reports a call used as a value, such as an argument, an operand or an initializer,
+// whose function returns nothing
+// Calls to unknown functions pass, so lowerCallExpression reports them
+func (lowerer *Lowerer) checkCallReturnsValue(call *ast.CallExpression) bool {
+	ident, ok := call.Function.(*ast.Identifier)
+	if !ok {
+		return true
+	}
+
+	returnsNothing := ident.Value == "print" || ident.Value == "panic" || ident.Value == "swap" ||
+		ident.Value == "clear" || ident.Value == "truncate"
+	if signature, isFunction := lowerer.functionSignatures[ident.Value]; isFunction {
+		returnsNothing = signature.ReturnType == types.Nil
+	}
+	if !returnsNothing {
+		return true
+	}
+
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		ident.Token.Line,
+		ident.Token.Column,
+		len(ident.Value),
+		"%s does not return a value",
+		ident.Value,
+	)
+	return false
+}
+
+// builtinSignature describes a builtin implemented by the runtime
+type builtinSignature struct {
+	parameterTypes []types.Type
+	returnType     types.Type
+}
+
+// builtinFunctions lists the runtime builtins with fixed signatures
+var builtinFunctions = map[string]builtinSignature{
+	"char_code": {[]types.Type{types.Char}, types.Int},
+	"char_from": {[]types.Type{types.Int}, types.Char},
+	"char_at":   {[]types.Type{types.String, types.Int}, types.Char},
+
+	"format_pad": {[]types.Type{types.Int, types.Int}, types.String},
+	"to_hex":     {[]types.Type{types.Int}, types.String},
+	"to_string":  {[]types.Type{types.Float}, types.String},
+
+	"repeat": {[]types.Type{types.String, types.Int}, types.String},
+	"join":   {[]types.Type{&types.ArrayType{Element: types.String}, types.String}, types.String},
+
+	"find": {[]types.Type{&types.ArrayType{Element: types.Int}, types.Int}, types.Int},
+}
+
+// reductions take an int[] or a float[] and return its element type
+// Each maps to the runtime function for float arrays; codegen maps the int form by name
+var reductions = map[string]string{
+	"sum":    "naviary_array_sum_float",
+	"min_of": "naviary_array_min_float",
+	"max_of": "naviary_array_max_float",
+}
+
+// IsBuiltin reports whether calls to name reach a builtin rather than a function of that name
+func IsBuiltin(name string) bool {
+	if _, ok := builtinFunctions[name]; ok {
+		return true
+	}
+	if _, ok := reductions[name]; ok {
+		return true
+	}
+	return name == "print" || name == "panic" || name == "swap" || name == "len" ||
+		name == "clear" || name == "truncate"
+}
+
+// BuiltinSignatures lists every builtin as "name(types) -> type", sorted by name
+// print accepts any number of values of any type, shown as "print(...)", and len, swap, clear and truncate
+// an array of any element type
+// The reductions are listed once for each array type they accept
+func BuiltinSignatures() []string {
+	signatures := []string{
+		"clear(T[])", "len(T[]) -> int", "panic(string)", "print(...)", "swap(T[], int, int)", "truncate(T[], int)",
+	}
+
+	for name := range reductions {
+		for _, element := range []types.Type{types.Int, types.Float} {
+			signatures = append(signatures, fmt.Sprintf("%s(%s[]) -> %s", name, element.String(), element.String()))
+		}
+	}
+
+	for name, builtin := range builtinFunctions {
+		parameters := make([]string, len(builtin.parameterTypes))
+		for i, parameterType := range builtin.parameterTypes {
+			parameters[i] = parameterType.String()
+		}
+		signatures = append(signatures, fmt.Sprintf("%s(%s) -> %s", name, strings.Join(parameters, ", "), builtin.returnType.String()))
+	}
+
+	sort.Strings(signatures)
+	return signatures
+}
+
+// checkCallArguments reports a call whose arguments do not match the parameters of the builtin or function it calls
+func (lowerer *Lowerer) checkCallArguments(call *ast.CallExpression, name string, parameterTypes []types.Type, arguments []value.Value) bool {
+	matches := len(arguments) == len(parameterTypes)
+	for i := 0; matches && i < len(arguments); i++ {
+		matches = arguments[i].Type().Equals(parameterTypes[i])
+	}
+	if matches {
+		return true
+	}
+
+	expected := make([]string, len(parameterTypes))
+	for i, parameterType := range parameterTypes {
+		expected[i] = parameterType.String()
+	}
+	actual := make([]string, len(arguments))
+	for i, argument := range arguments {
+		actual[i] = argument.Type().String()
+	}
+
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		call.Token.Line,
+		call.Token.Column,
+		len(call.Token.Value),
+		"%s expects (%s), got (%s)",
+		name,
+		strings.Join(expected, ", "),
+		strings.Join(actual, ", "),
+	)
+	return false
+}
+
+// lowerReduction lowers sum, min_of or max_of over an int or float array
+// Example: sum(xs) with xs: float[]
+//
+//	→ %1 = Call(naviary_array_sum_float, [%0])
+func (lowerer *Lowerer) lowerReduction(call *ast.CallExpression, name string, floatFunction string, arguments []value.Value) value.Value {
+	if len(arguments) == 1 {
+		if arrayType, isArray := arguments[0].Type().(*types.ArrayType); isArray {
+			switch arrayType.Element {
+			case types.Int:
+				return lowerer.builder.BuildCall(name, arguments, types.Int)
+			case types.Float:
+				return lowerer.builder.BuildCall(floatFunction, arguments, types.Float)
+			}
+		}
+	}
+
+	actual := make([]string, len(arguments))
+	for i, argument := range arguments {
+		actual[i] = argument.Type().String()
+	}
+
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		call.Token.Line,
+		call.Token.Column,
+		len(call.Token.Value),
+		"%s expects (int[]) or (float[]), got (%s)",
+		name,
+		strings.Join(actual, ", "),
+	)
+	return nil
+}
+
+// lowerPanic lowers a call to the panic builtin
+// panic never returns, so the current block is terminated after the call
+// Example: panic("bad state")
+//
+//	→ Call(panic, [Constant("bad state")])
+//	  Unreachable
+func (lowerer *Lowerer) lowerPanic(call *ast.CallExpression, arguments []value.Value) value.Value {
+	if len(arguments) != 1 || arguments[0].Type() != types.String {
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			call.Token.Line,
+			call.Token.Column,
+			len(call.Token.Value),
+			"panic expects a single string argument",
+		)
+		return nil
+	}
+
+	lowerer.builder.BuildCall("panic", arguments, nil)
+	lowerer.builder.BuildUnreachable()
+
+	return nil
+}
+
+// lowerSwap lowers a call to the swap builtin, which exchanges two elements of an array in place
+// Both elements are read, and so both indices checked, before either is written
+// Example: swap(xs, i, j)
+//
+//	→ %3 = Element(%0, %1)
+//	  %4 = Element(%0, %2)
+//	  SetElement(%0, %1, %4)
+//	  SetElement(%0, %2, %3)
+func (lowerer *Lowerer) lowerSwap(call *ast.CallExpression, arguments []value.Value) value.Value {
+	matches := len(arguments) == 3
+	if matches {
+		_, isArray := arguments[0].Type().(*types.ArrayType)
+		matches = isArray && arguments[1].Type() == types.Int && arguments[2].Type() == types.Int
+	}
+	if !matches {
+		actual := make([]string, len(arguments))
+		for i, argument := range arguments {
+			actual[i] = argument.Type().String()
+		}
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			call.Token.Line,
+			call.Token.Column,
+			len(call.Token.Value),
+			"swap expects (T[], int, int), got (%s)",
+			strings.Join(actual, ", "),
+		)
+		return nil
+	}
+
+	array, first, second := arguments[0], arguments[1], arguments[2]
+	firstElement := lowerer.builder.BuildElement(array, first)
+	secondElement := lowerer.builder.BuildElement(array, second)
+	lowerer.builder.BuildSetElement(array, first, secondElement)
+	lowerer.builder.BuildSetElement(array, second, firstElement)
+
+	return nil
+}
+
+// lowerLength lowers len over an array of any element type to a read of the length in its header
+// Example: len(xs)
+//
+//	→ %1 = Call(len, [%0])
+func (lowerer *Lowerer) lowerLength(call *ast.CallExpression, arguments []value.Value) value.Value {
+	matches := len(arguments) == 1
+	if matches {
+		_, matches = arguments[0].Type().(*types.ArrayType)
+	}
+	if !matches {
+		actual := make([]string, len(arguments))
+		for i, argument := range arguments {
+			actual[i] = argument.Type().String()
+		}
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			call.Token.Line,
+			call.Token.Column,
+			len(call.Token.Value),
+			"len expects (T[]), got (%s)",
+			strings.Join(actual, ", "),
+		)
+		return nil
+	}
+
+	return lowerer.builder.BuildCall("len", arguments, types.Int)
+}
+
+// lowerResize lowers clear(xs) or truncate(xs, n) over an array of any element type
+// Both only lower the length in the header: the dropped elements stay allocated but are no
+// longer reachable through the array, so nothing needs clearing
+// Example: truncate(xs, n)
+//
+//	→ Call(truncate, [%0, %1])
+func (lowerer *Lowerer) lowerResize(call *ast.CallExpression, name string, arguments []value.Value) value.Value {
+	expected := "T[]"
+	count := 1
+	if name == "truncate" {
+		expected = "T[], int"
+		count = 2
+	}
+
+	matches := len(arguments) == count
+	if matches {
+		_, matches = arguments[0].Type().(*types.ArrayType)
+	}
+	if matches && count == 2 {
+		matches = arguments[1].Type() == types.Int
+	}
+	if !matches {
+		actual := make([]string, len(arguments))
+		for i, argument := range arguments {
+			actual[i] = argument.Type().String()
+		}
+		lowerer.errorCollector.Add(
+			errors.TypeError,
+			call.Token.Line,
+			call.Token.Column,
+			len(call.Token.Value),
+			"%s expects (%s), got (%s)",
+			name,
+			expected,
+			strings.Join(actual, ", "),
+		)
+		return nil
+	}
+
+	return lowerer.builder.BuildCall(name, arguments, nil)
 }
 
 // getType converts AST type annotation to NIR type
+// A name that is not a type is reported at the annotation and yields nil
 func (lowerer *Lowerer) getType(typeAnnotation *ast.TypeAnnotation) types.Type {
-	switch typeAnnotation.Value {
-	case "int":
-		return types.Int
-	case "float":
-		return types.Float
-	case "string":
-		return types.String
-	case "bool":
-		return types.Bool
-	case "nil":
+	if typeAnnotation.Value == "nil" {
 		return types.Nil
-	default:
-		return types.Int // Default fallback
 	}
+	if namedType := types.GetType(typeAnnotation.Value); namedType != nil {
+		return namedType
+	}
+
+	lowerer.errorCollector.Add(
+		errors.TypeError,
+		typeAnnotation.Token.Line,
+		typeAnnotation.Token.Column,
+		len(typeAnnotation.Value),
+		"Unknown type: %s",
+		typeAnnotation.Value,
+	)
+	return nil
 }
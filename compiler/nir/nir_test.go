@@ -0,0 +1,871 @@
+package nir
+
+import (
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/parser"
+	"compiler/testrunner"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func lowerSource(input string) (*Module, *errors.ErrorCollector) {
+	errorCollector := errors.New(input, "test.navi")
+	lexerInstance := lexer.New(input, "test.navi", errorCollector)
+	parserInstance := parser.New(lexerInstance, errorCollector)
+	program := parserInstance.ParseProgram()
+
+	lowerer := NewLowerer(errorCollector)
+	return lowerer.Lower(program), errorCollector
+}
+
+func TestLowerer(t *testing.T) {
+	t.Run("Long operator chains report a clean error", func(t *testing.T) {
+		input := "func main() {\nlet x = 1" + strings.Repeat(" + 1", 50000) + "\n}"
+
+		_, errorCollector := lowerSource(input)
+
+		assert.True(t, errorCollector.HasErrors(),
+			"Lowerer should reject pathologically deep expressions")
+		assert.Len(t, errorCollector.Errors(), 1,
+			"Nesting error should be reported exactly once")
+		nesting := errorCollector.Errors()[0]
+		assert.Contains(t, nesting.Message, "too deeply nested")
+		assert.Equal(t, errors.TypeError, nesting.Type)
+		assert.Equal(t, 2, nesting.Line)
+		assert.Positive(t, nesting.Column)
+	})
+
+	t.Run("Test comparison operand types", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			expression    string
+			expectedError string // empty when the comparison is valid
+		}{
+			{"Int ordering", "1 < 2", ""},
+			{"Int equality", "1 == 2", ""},
+			{"Bool equality", "true == false", ""},
+			{"Bool inequality", "true != false", ""},
+			{"Bool ordering", "true < false", "Operator '<' is not supported for bool operands"},
+			{"Bool greater or equal", "true >= false", "Operator '>=' is not supported for bool operands"},
+			{"String equality", "\"a\" == \"b\"", "Operator '==' is not supported for string operands"},
+			{"Mixed operands", "1 == true", "Cannot compare int with bool using '=='"},
+			{"Bool arithmetic", "true + false", "Operator '+' is not supported for bool operands"},
+			{"Char ordering", "'a' < 'b'", ""},
+			{"Char equality", "'a' == 'b'", ""},
+			{"Char with int", "'a' == 97", "Cannot compare char with int using '=='"},
+			{"Char arithmetic", "'a' + 'b'", "Operator '+' is not supported for char operands"},
+			{"Membership in an int", "1 in 2", "Operator 'in' requires an array on the right, got int"},
+			{"Substring membership", "\"a\" in \"abc\"", "Operator 'in' requires an array on the right, got string"},
+			{"Membership in an int array", "1 in [1, 2]", ""},
+			{"Membership of a char in an int array", "'a' in [1, 2]", "Cannot search int[] for char using 'in'"},
+			{"Membership in a char array", "'a' in ['a']", "Operator 'in' is only supported for int[] and string[], got char[]"},
+			{"Membership in a string array", "\"b\" in [\"a\", \"b\"]", ""},
+			{"Membership of an int in a string array", "1 in [\"a\"]", "Cannot search string[] for int using 'in'"},
+			{"Membership result is a bool", "(1 in [1]) == true", ""},
+			{"Array equality", "[1] == [1]", "Operator '==' is not supported for int[] operands"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource("func main() {\nprint(" + testCase.expression + ")\n}")
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors(),
+						"Lowerer should accept %q", testCase.expression)
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Functions cannot reuse a builtin name", func(t *testing.T) {
+		for _, name := range []string{"sum", "find", "repeat", "to_hex", "swap", "len", "clear", "truncate", "print"} {
+			t.Run(name, func(t *testing.T) {
+				_, errorCollector := lowerSource("func " + name + "(x: int) -> int {\nreturn x\n}\nfunc main() {\n}")
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					conflict := errorCollector.Errors()[0]
+					assert.Equal(t, "Function "+name+" conflicts with builtin function", conflict.Message)
+					assert.Equal(t, 1, conflict.Line)
+					assert.Equal(t, 6, conflict.Column)
+				}
+			})
+		}
+	})
+
+	t.Run("Reductions of a float array return float", func(t *testing.T) {
+		module, errorCollector := lowerSource("func total(xs: float[]) -> float {\nreturn sum(xs)\n}\nfunc main() {\n}")
+
+		assert.False(t, errorCollector.HasErrors())
+		assert.Contains(t, module.GetFunction("total").String(), "Call(naviary_array_sum_float")
+	})
+
+	t.Run("Membership searches the array with find", func(t *testing.T) {
+		module, errorCollector := lowerSource("func main() {\nlet xs = [1, 2]\nprint(2 in xs)\n}")
+
+		assert.False(t, errorCollector.HasErrors())
+		mainIR := module.GetFunction("main").String()
+		assert.Contains(t, mainIR, "Call(naviary_array_find_int")
+		assert.Contains(t, mainIR, "NotEqual(")
+	})
+
+	t.Run("Entry points share the lowered program", func(t *testing.T) {
+		source := "let greeting = \"hi\"\n" +
+			"func test_a() {\nprint(greeting)\n}\n" +
+			"func test_b() -> int {\nreturn 1\n}\n" +
+			"func main() {\ntest_a()\n}"
+		errorCollector := errors.New(source, "test.navi")
+		program := parser.New(lexer.New(source, "test.navi", errorCollector), errorCollector).ParseProgram()
+		lowerer := NewLowerer(errorCollector)
+		module := lowerer.Lower(program)
+
+		for _, testName := range []string{"test_a", "test_b"} {
+			entryModule := lowerer.LowerEntry(module, testrunner.EntryMain(program, testName))
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Len(t, entryModule.Functions, 3)
+			assert.Same(t, module.GetFunction("test_a"), entryModule.GetFunction("test_a"))
+			mainIR := entryModule.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Call("+testName)
+			assert.Contains(t, mainIR, "Store(@greeting", "Globals are still initialized by main")
+		}
+		assert.Contains(t, module.GetFunction("main").String(), "Call(test_a", "The program's own main is unchanged")
+	})
+
+	t.Run("Test globals", func(t *testing.T) {
+		t.Run("Globals are visible in every function", func(t *testing.T) {
+			source := "let mut counter: int = 5\n" +
+				"func show() {\nprint(counter)\n}\n" +
+				"func main() {\nshow()\nprint(counter + 1)\n}"
+
+			module, errorCollector := lowerSource(source)
+
+			assert.False(t, errorCollector.HasErrors())
+			if assert.Len(t, module.Globals, 1) {
+				assert.Equal(t, "global @counter: int = Constant(5)", module.Globals[0].String())
+			}
+			assert.Contains(t, module.GetFunction("show").String(), "Load(@counter)")
+			assert.Contains(t, module.GetFunction("main").String(), "Load(@counter)")
+		})
+
+		t.Run("Locals shadow globals", func(t *testing.T) {
+			source := "let limit = 10\nfunc main() {\nlet limit = true\nprint(limit)\n}"
+
+			module, errorCollector := lowerSource(source)
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Load(limit)")
+			assert.NotContains(t, mainIR, "@limit")
+		})
+
+		t.Run("String globals are stored by main", func(t *testing.T) {
+			module, errorCollector := lowerSource("let name = \"navi\"\nfunc main() {\nprint(name)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			if assert.Len(t, module.Globals, 1) {
+				assert.Nil(t, module.Globals[0].Initializer)
+			}
+			assert.Contains(t, module.GetFunction("main").String(), "Store(@name")
+		})
+
+		t.Run("Invalid globals are rejected", func(t *testing.T) {
+			tests := []struct {
+				name          string
+				source        string
+				expectedError string
+			}{
+				{"Duplicate global", "let x = 1\nlet x = 2\nfunc main() {\n}", "Global x is already defined"},
+				{"Non literal initializer", "let x = 1 + 2\nfunc main() {\n}", "Global x must be initialized with a literal"},
+				{"Mismatched annotation", "let x: bool = 1\nfunc main() {\n}", "Global x is declared as bool but initialized with int"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.name, func(t *testing.T) {
+					_, errorCollector := lowerSource(testCase.source)
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+					}
+				})
+			}
+		})
+	})
+
+	t.Run("Unknown types are reported at the annotation", func(t *testing.T) {
+		tests := []struct {
+			name           string
+			source         string
+			expectedError  string
+			expectedLine   int
+			expectedColumn int
+		}{
+			{"Let", "func main() {\nlet x: foo = 1\n}", "Unknown type: foo", 2, 8},
+			{"Array element", "func main() {\nlet xs: foo[] = []\n}", "Unknown type: foo[]", 2, 9},
+			{"Global", "let g: foo = 1\nfunc main() {\n}", "Unknown type: foo", 1, 8},
+			{"Parameter, with a call", "func f(x: foo) -> int {\nreturn 1\n}\nfunc main() {\nprint(f(1))\n}", "Unknown type: foo", 1, 11},
+			{"Return type", "func f() -> foo {\nreturn 1\n}\nfunc main() {\n}", "Unknown type: foo", 1, 13},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource(testCase.source)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					unknown := errorCollector.Errors()[0]
+					assert.Equal(t, testCase.expectedError, unknown.Message)
+					assert.Equal(t, testCase.expectedLine, unknown.Line)
+					assert.Equal(t, testCase.expectedColumn, unknown.Column)
+				}
+			})
+		}
+	})
+
+	t.Run("Test panic", func(t *testing.T) {
+		t.Run("Panic terminates the block", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\npanic(\"boom\")\nprint(1)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Call(panic")
+			assert.Contains(t, mainIR, "Unreachable")
+			assert.NotContains(t, mainIR, "Return", "panic diverges, so main needs no implicit return")
+			assert.NotContains(t, mainIR, "Constant(1)", "statements after panic are unreachable")
+		})
+
+		t.Run("Function ending in panic needs no return", func(t *testing.T) {
+			_, errorCollector := lowerSource("func answer() -> int {\npanic(\"not implemented\")\n}\nfunc main() {\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+		})
+
+		t.Run("Function without return is rejected", func(t *testing.T) {
+			_, errorCollector := lowerSource("func answer() -> int {\nprint(1)\n}\nfunc main() {\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				assert.Equal(t, "Function answer must return a value of type int", errorCollector.Errors()[0].Message)
+			}
+		})
+
+		t.Run("Panic requires a string message", func(t *testing.T) {
+			_, errorCollector := lowerSource("func main() {\npanic(1)\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				assert.Equal(t, "panic expects a single string argument", errorCollector.Errors()[0].Message)
+			}
+		})
+	})
+
+	t.Run("Test tail position", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			body     string
+			expected bool
+		}{
+			{"Returned call", "return countdown(n - 1)", true},
+			{"Call used in an expression", "return countdown(n - 1) + 1", false},
+			{"Returned variable", "let result = countdown(n - 1)\nreturn result", false},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				source := "func countdown(n: int) -> int {\n" + testCase.body + "\n}\nfunc main() {\n}"
+
+				module, errorCollector := lowerSource(source)
+
+				assert.False(t, errorCollector.HasErrors())
+				functionIR := module.GetFunction("countdown").String()
+				assert.Equal(t, testCase.expected, strings.Contains(functionIR, "TailCall(countdown"))
+			})
+		}
+	})
+
+	t.Run("Let takes the declared return type of a call initializer", func(t *testing.T) {
+		source := "func greet() -> string {\nreturn \"hi\"\n}\nfunc main() {\nlet s = greet()\nprint(s)\n}"
+
+		module, errorCollector := lowerSource(source)
+
+		assert.False(t, errorCollector.HasErrors())
+		mainIR := module.GetFunction("main").String()
+		assert.Contains(t, mainIR, "Alloc(string)")
+		assert.Contains(t, mainIR, "Call(greet")
+	})
+
+	t.Run("Print arguments are lowered once, before the print", func(t *testing.T) {
+		source := "func mark(n: int) -> int {\nprint(n)\nreturn n\n}\n" +
+			"func positive(n: int) -> bool {\nreturn n > 0\n}\n" +
+			"func main() {\nprint(mark(1) + mark(2), positive(mark(3)))\n}"
+
+		module, errorCollector := lowerSource(source)
+
+		assert.False(t, errorCollector.HasErrors())
+		mainIR := module.GetFunction("main").String()
+		assert.Equal(t, 3, strings.Count(mainIR, "Call(mark"))
+		assert.Equal(t, 1, strings.Count(mainIR, "Call(positive"))
+		assert.Greater(t, strings.Index(mainIR, "Call(print"), strings.LastIndex(mainIR, "Call(positive"))
+	})
+
+	t.Run("Only mutable lets are allocated as mutable", func(t *testing.T) {
+		module, errorCollector := lowerSource("func main() {\nlet a = 1\nlet mut b = 2\nlet c := 3\nprint(a, b, c)\n}")
+
+		assert.False(t, errorCollector.HasErrors())
+		mainIR := module.GetFunction("main").String()
+		assert.Equal(t, 1, strings.Count(mainIR, "= Alloc(int)"))
+		assert.Equal(t, 2, strings.Count(mainIR, "= AllocMut(int)"))
+	})
+
+	t.Run("Test assignment", func(t *testing.T) {
+		t.Run("Mutable locals and globals are stored", func(t *testing.T) {
+			module, errorCollector := lowerSource("let mut total = 0\nfunc main() {\nlet mut x = 1\nx = x + 1\ntotal = x\nprint(x, total)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Equal(t, 2, strings.Count(mainIR, "Store(x, "))
+			assert.Contains(t, mainIR, "Store(@total")
+		})
+
+		t.Run("Immutable variable points at its declaration", func(t *testing.T) {
+			_, errorCollector := lowerSource("func main() {\nlet x = 1\nx = 2\nprint(x)\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				assigned := errorCollector.Errors()[0]
+				assert.Equal(t, "Cannot assign twice to immutable variable x", assigned.Message)
+				assert.Equal(t, 3, assigned.Line)
+				assert.Equal(t, 1, assigned.Column)
+
+				if assert.Len(t, assigned.Notes, 1) {
+					assert.Equal(t, "declared here; use let mut x to make it mutable", assigned.Notes[0].Message)
+					assert.Equal(t, 2, assigned.Notes[0].Line)
+				}
+			}
+		})
+
+		tests := []struct {
+			name          string
+			source        string
+			expectedError string
+		}{
+			{"Parameter", "func f(n: int) {\nn = 1\n}\nfunc main() {\n}", "Cannot assign to parameter n; copy it into a let mut variable first"},
+			{"Immutable global", "let limit = 1\nfunc main() {\nlimit = 2\n}", "Cannot assign to immutable global limit"},
+			{"Undefined", "func main() {\ny = 2\n}", "Undefined variable: y"},
+			{"Wrong type", "func main() {\nlet mut x = 1\nx = \"one\"\n}", "Cannot assign a value of type string to x of type int"},
+			{"Local shadowing a mutable global", "let mut x = 1\nfunc main() {\nlet x = 2\nx = 3\n}", "Cannot assign twice to immutable variable x"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource(testCase.source)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test for loops", func(t *testing.T) {
+		t.Run("Range loops branch through a condition block", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nfor i in 0..3 {\nprint(i)\n}\nfor i in 1..=2 {\n}\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			for _, block := range []string{"entry", "for.cond.0", "for.body.0", "for.end.0", "for.cond.1", "for.body.1", "for.end.1"} {
+				assert.Contains(t, mainIR, "BasicBlock: "+block+"\n")
+			}
+			assert.Contains(t, mainIR, "AllocMut(int)")
+			assert.Contains(t, mainIR, "Less(")
+			assert.Contains(t, mainIR, "LessEqual(")
+			assert.Contains(t, mainIR, ", for.body.0, for.end.0)")
+			assert.Equal(t, 2, strings.Count(mainIR, "Branch(for.cond.0)"))
+			assert.True(t, module.GetFunction("main").IsComplete())
+		})
+
+		t.Run("String loops bind each character to a char", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nfor ch in \"navi\" {\nprint(char_code(ch))\n}\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Call(naviary_string_length")
+			assert.Contains(t, mainIR, "Call(naviary_string_decode_char")
+			assert.Contains(t, mainIR, "Call(naviary_string_char_width")
+			assert.NotContains(t, mainIR, "Call(char_at", "The body should not measure the string again")
+			assert.Equal(t, 1, strings.Count(mainIR, "Call(naviary_string_length"))
+			assert.Contains(t, mainIR, "AllocMut(char)")
+			assert.Equal(t, 2, strings.Count(mainIR, "Branch(for.cond.0)"))
+		})
+
+		t.Run("Returning from the body ends the iteration", func(t *testing.T) {
+			module, errorCollector := lowerSource("func first(n: int) -> int {\nfor i in 0..n {\nreturn i\n}\nreturn -1\n}\nfunc main() {\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			firstIR := module.GetFunction("first").String()
+			assert.Equal(t, 1, strings.Count(firstIR, "Branch(for.cond.0)"))
+		})
+
+		t.Run("Bad bounds are reported at the bound", func(t *testing.T) {
+			_, errorCollector := lowerSource("func main() {\nfor i in 0..1 {\n}\nfor j in true..3 {\n}\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				bound := errorCollector.Errors()[0]
+				assert.Equal(t, 4, bound.Line)
+				assert.Equal(t, 10, bound.Column)
+			}
+		})
+
+		t.Run("Array lengths are int bounds", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet xs = [\"a\", \"b\"]\nfor i in 0..len(xs) {\nprint(xs[i])\n}\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Contains(t, module.GetFunction("main").String(), "Call(len")
+		})
+
+		tests := []struct {
+			name          string
+			source        string
+			expectedError string
+		}{
+			{"Bool bound", "func main() {\nfor i in 0..true {\n}\n}", "for-loop bounds must be int, found bool"},
+			{"String bound", "func main() {\nfor i in 0..\"ten\" {\n}\n}", "for-loop bounds must be int, found string"},
+			{"Float bound", "func main() {\nlet none: float[] = []\nfor i in 0..sum(none) {\n}\n}", "for-loop bounds must be int, found float"},
+			{"Length of a string", "func main() {\nfor i in 0..len(\"ten\") {\n}\n}", "len expects (T[]), got (string)"},
+			{"Int iterable", "func main() {\nfor i in 10 {\n}\n}", "for loops iterate over a range or a string, found int"},
+			{"Assigning the character variable", "func main() {\nfor ch in \"ab\" {\nch = 'c'\n}\n}", "Cannot assign to loop variable ch; it takes each value of the range in turn"},
+			{"Assigning the loop variable", "func main() {\nfor i in 0..3 {\ni = 5\n}\n}", "Cannot assign to loop variable i; it takes each value of the range in turn"},
+			{"Loop variable after the loop", "func main() {\nfor i in 0..3 {\n}\nprint(i)\n}", "Undefined variable: i"},
+			{"Body local after the loop", "func main() {\nfor i in 0..3 {\nlet square = i * i\n}\nprint(square)\n}", "Undefined variable: square"},
+			{"Body local redeclaring the loop variable", "func main() {\nfor i in 0..3 {\nlet i = 1\n}\n}", "Variable i is already declared in this scope"},
+			{"Body local declared twice", "func main() {\nfor i in 0..3 {\nlet x = 1\nlet x = 2\n}\n}", "Variable x is already declared in this scope"},
+			{"Missing return after a loop", "func f() -> int {\nfor i in 0..3 {\nreturn i\n}\n}\nfunc main() {\n}", "Function f must return a value of type int"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource(testCase.source)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test arrays", func(t *testing.T) {
+		t.Run("Literals build an array read by index", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet xs = [1, 2, 3]\nprint(xs[1])\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "MakeArray([Constant(1), Constant(2), Constant(3)])")
+			assert.Contains(t, mainIR, "Element(")
+			assert.Contains(t, mainIR, "Alloc(int[])")
+		})
+
+		t.Run("Empty arrays take their type from the declaration or parameter", func(t *testing.T) {
+			source := "func none() -> int[] {\nreturn []\n}\n" +
+				"func count(xs: int[]) -> int {\nreturn sum(xs)\n}\n" +
+				"func main() {\nlet xs: int[] = []\nlet grid: char[][] = [[], ['a']]\nlet ys = none()\nlet n = count([])\n}"
+			module, errorCollector := lowerSource(source)
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Contains(t, module.GetFunction("none").String(), "MakeArray([])")
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Alloc(int[])")
+			assert.Contains(t, mainIR, "Alloc(char[][])")
+		})
+
+		t.Run("Declared types are checked", func(t *testing.T) {
+			tests := []struct {
+				statement     string
+				expectedError string
+			}{
+				{"let xs: int[] = [true]", "Array elements must all be int, but element 1 is bool"},
+				{"let xs: int[] = [[1]]", "Array elements must all be int, but element 1 is int[]"},
+				{"let xs: int[][] = [1]", "Array elements must all be int[], but element 1 is int"},
+				{"let xs: string[] = 1", "Variable xs is declared as string[] but initialized with int"},
+				{"let x: int = \"a\"", "Variable x is declared as int but initialized with string"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.statement, func(t *testing.T) {
+					_, errorCollector := lowerSource("func main() {\n" + testCase.statement + "\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+					}
+				})
+			}
+		})
+
+		t.Run("Elements are assigned through any binding", func(t *testing.T) {
+			source := "func fill(xs: int[]) {\nxs[0] = 10\n}\n" +
+				"func main() {\nlet grid: char[][] = [['a']]\ngrid[0][0] = 'b'\ngrid[0] = []\n}"
+			module, errorCollector := lowerSource(source)
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Contains(t, module.GetFunction("fill").String(), "SetElement(")
+			mainIR := module.GetFunction("main").String()
+			assert.Equal(t, 2, strings.Count(mainIR, "SetElement("))
+			assert.Contains(t, mainIR, "MakeArray([])")
+		})
+
+		t.Run("Element assignments are checked", func(t *testing.T) {
+			tests := []struct {
+				source        string
+				expectedError string
+			}{
+				{"func fill(xs: int[]) {\nxs = [7, 8]\n}", "Cannot assign to parameter xs; copy it into a let mut variable first"},
+				{"func fill(xs: int[]) {\nxs[0] = true\n}", "Cannot assign a value of type bool to an element of int[]"},
+				{"func fill(xs: int[]) {\nxs['a'] = 1\n}", "Array index must be int, found char"},
+				{"func fill(s: string) {\ns[0] = 'a'\n}", "Cannot assign to an element of string; only array elements can be assigned"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.expectedError, func(t *testing.T) {
+					_, errorCollector := lowerSource(testCase.source + "\nfunc main() {\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						err := errorCollector.Errors()[0]
+						assert.Equal(t, testCase.expectedError, err.Message)
+						assert.Equal(t, 2, err.Line)
+					}
+				})
+			}
+		})
+
+		t.Run("Swap reads both elements before writing either", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet xs = [\"a\", \"b\"]\nswap(xs, 0, 1)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			lastRead := strings.LastIndex(mainIR, "= Element(")
+			firstWrite := strings.Index(mainIR, "SetElement(")
+			assert.Equal(t, 2, strings.Count(mainIR, "SetElement("))
+			assert.Less(t, lastRead, firstWrite)
+		})
+
+		t.Run("Clear and truncate only call the runtime", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet xs = [\"a\", \"b\"]\ntruncate(xs, 1)\nclear(xs)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "Call(truncate")
+			assert.Contains(t, mainIR, "Call(clear")
+			assert.NotContains(t, mainIR, "SetElement(")
+		})
+
+		t.Run("Array builtin arguments are checked", func(t *testing.T) {
+			tests := []struct {
+				call          string
+				expectedError string
+			}{
+				{"swap(\"ab\", 0, 1)", "swap expects (T[], int, int), got (string, int, int)"},
+				{"swap([1, 2], 0)", "swap expects (T[], int, int), got (int[], int)"},
+				{"swap([1, 2], 0, true)", "swap expects (T[], int, int), got (int[], int, bool)"},
+				{"clear(\"ab\")", "clear expects (T[]), got (string)"},
+				{"clear([1], [2])", "clear expects (T[]), got (int[], int[])"},
+				{"truncate([1, 2])", "truncate expects (T[], int), got (int[])"},
+				{"truncate([1, 2], '1')", "truncate expects (T[], int), got (int[], char)"},
+				{"let n = clear([1])", "clear does not return a value"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.call, func(t *testing.T) {
+					_, errorCollector := lowerSource("func main() {\n" + testCase.call + "\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+					}
+				})
+			}
+		})
+
+		tests := []struct {
+			name          string
+			expression    string
+			expectedError string // empty when the expression is valid
+		}{
+			{"Nested arrays", "[[1], [2, 3]][1][0]", ""},
+			{"Array of strings", "[\"a\", repeat(\"b\", 2)][0]", ""},
+			{"Indexing with an expression", "[1, 2][1 - 1] + 1", ""},
+			{"Empty array", "[][0]", "Cannot infer the element type of an empty array; declare it, as in let xs: int[] = []"},
+			{"Mixed elements", "[1, true][0]", "Array elements must all be int, but element 2 is bool"},
+			{"Mixed nested elements", "[[1], ['a']][0][0]", "Array elements must all be int[], but element 2 is char[]"},
+			{"Indexing with a bool", "[1][true]", "Array index must be int, found bool"},
+			{"Printing an array", "[1, 2]", "print does not support int[] yet; print its elements instead"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource("func main() {\nprint(" + testCase.expression + ")\n}")
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors(),
+						"Lowerer should accept %q", testCase.expression)
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test ascription", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			expression    string
+			expectedError string // empty when the ascription holds
+		}{
+			{"Matching int", "1 + 2 as int", ""},
+			{"Matching bool", "1 < 2 as bool", ""},
+			{"Conflicting type", "1 as bool", "Expression of type int cannot be ascribed type bool"},
+			{"Conflicting comparison", "1 < 2 as int", "Expression of type bool cannot be ascribed type int"},
+			{"Unknown type", "1 as widget", "Unknown type: widget"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				module, errorCollector := lowerSource("func main() {\nprint(" + testCase.expression + ")\n}")
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors(),
+						"Lowerer should accept %q", testCase.expression)
+					assert.Contains(t, module.GetFunction("main").String(), "Call(print")
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test char builtins", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			expression    string
+			expectedError string // empty when the call is valid
+		}{
+			{"char_code of a char", "char_code('a')", ""},
+			{"char_from of an int", "char_from(98)", ""},
+			{"Round trip", "char_from(char_code('a') + 1)", ""},
+			{"char_code of an int", "char_code(97)", "char_code expects (char), got (int)"},
+			{"char_from without arguments", "char_from()", "char_from expects (int), got ()"},
+			{"format_pad of ints", "format_pad(7, 4)", ""},
+			{"format_pad with a string width", "format_pad(7, \"4\")", "format_pad expects (int, int), got (int, string)"},
+			{"to_hex of an int", "to_hex(255)", ""},
+			{"to_hex of a bool", "to_hex(true)", "to_hex expects (int), got (bool)"},
+			{"to_string of an int", "to_string(1)", "to_string expects (float), got (int)"},
+			{"repeat of a string", "repeat(\"-\", 40)", ""},
+			{"repeat with swapped arguments", "repeat(40, \"-\")", "repeat expects (string, int), got (int, string)"},
+			{"sum of an int array", "sum([1, 2]) + max_of([3]) - min_of([4])", ""},
+			{"sum of a char array", "sum(['a'])", "sum expects (int[]) or (float[]), got (char[])"},
+			{"min_of of an int", "min_of(1)", "min_of expects (int[]) or (float[]), got (int)"},
+			{"max_of of two arrays", "max_of([1], [2])", "max_of expects (int[]) or (float[]), got (int[], int[])"},
+			{"join of a string array", "join([\"a\", \"b\"], \", \")", ""},
+			{"join of an int array", "join([1, 2], \",\")", "join expects (string[], string), got (int[], string)"},
+			{"find in an int array", "find([1, 2], 2)", ""},
+			{"find of a char", "find([1, 2], 'a')", "find expects (int[], int), got (int[], char)"},
+			{"char_at of a string", "char_at(\"abc\", 1)", ""},
+			{"Indexing a string", "\"abc\"[2]", ""},
+			{"Indexing an indexed call", "repeat(\"ab\", 2)[1 + 2]", ""},
+			{"Indexing an int", "42[0]", "Cannot index a value of type int"},
+			{"Indexing a string with a char", "\"abc\"['a']", "String index must be int, found char"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource("func main() {\nprint(" + testCase.expression + ")\n}")
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors(),
+						"Lowerer should accept %q", testCase.expression)
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test chained comparisons", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			expression    string
+			expectedError string // empty when the comparison is valid
+		}{
+			{"Chained ordering", "a < b < c", "comparison operators cannot be chained; write `a < b && b < c`"},
+			{"Chained equality", "a == b == c", "comparison operators cannot be chained; write `a == b && b == c`"},
+			{"Range check", "0 < a < 10", "comparison operators cannot be chained; write `0 < a && a < 10`"},
+			{"Parenthesized bool equality", "(a < b) == flag", ""},
+			{"Parenthesized equality", "(a == b) == flag", ""},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				source := "func main() {\nlet a = 1\nlet b = 2\nlet c = 3\nlet flag = true\nprint(" + testCase.expression + ")\n}"
+
+				_, errorCollector := lowerSource(source)
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors(),
+						"Lowerer should accept %q", testCase.expression)
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+				}
+			})
+		}
+	})
+
+	t.Run("Test return types", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			function      string
+			expectedError string // empty when the function is valid
+		}{
+			{"Comparison returned as bool", "func is_pos(x: int) -> bool {\nreturn x > 0;\n}", ""},
+			{"Bool parameter returned", "func id(b: bool) -> bool {\nreturn b\n}", ""},
+			{"Int returned as bool", "func is_pos(x: int) -> bool {\nreturn x\n}", "Function is_pos returns bool but the return value is int"},
+			{"Value from a void function", "func f() {\nreturn 1\n}", "Function f does not declare a return type but returns int"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := lowerSource(testCase.function + "\nfunc main() {\n}")
+
+				if testCase.expectedError == "" {
+					assert.False(t, errorCollector.HasErrors())
+					return
+				}
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					assert.Equal(t, testCase.expectedError, errorCollector.Errors()[0].Message)
+					assert.Equal(t, 2, errorCollector.Errors()[0].Line)
+				}
+			})
+		}
+
+		t.Run("Bool results are stored and printed", func(t *testing.T) {
+			module, errorCollector := lowerSource("func is_pos(x: int) -> bool {\nreturn x > 0\n}\nfunc main() {\nlet p = is_pos(3)\nprint(p)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Contains(t, module.GetFunction("main").String(), "Alloc(bool)")
+		})
+	})
+
+	t.Run("Calls to functions without a return type are not values", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			statement     string
+			expectedError string
+			column        int
+		}{
+			{"Argument", "print(log())", "log does not return a value", 7},
+			{"Operand", "print(log() + 1)", "log does not return a value", 7},
+			{"Let initializer", "let y = log()", "log does not return a value", 9},
+			{"Builtin", "let y = print(1)", "print does not return a value", 9},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				source := "func log() {\nprint(1)\n}\nfunc main() {\n" + testCase.statement + "\n}"
+
+				_, errorCollector := lowerSource(source)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					voidCall := errorCollector.Errors()[0]
+					assert.Equal(t, testCase.expectedError, voidCall.Message)
+					assert.Equal(t, 5, voidCall.Line)
+					assert.Equal(t, testCase.column, voidCall.Column)
+				}
+			})
+		}
+
+		t.Run("A call statement may call a function without a return type", func(t *testing.T) {
+			_, errorCollector := lowerSource("func log() {\nprint(1)\n}\nfunc main() {\nlog()\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+		})
+	})
+
+	t.Run("Test redeclaration", func(t *testing.T) {
+		t.Run("Same scope redeclaration points at the first declaration", func(t *testing.T) {
+			_, errorCollector := lowerSource("func main() {\nlet x = 1\nlet x = 2\nprint(x)\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				redeclared := errorCollector.Errors()[0]
+				assert.Equal(t, "Variable x is already declared in this scope", redeclared.Message)
+				assert.Equal(t, 3, redeclared.Line)
+				assert.Equal(t, 5, redeclared.Column)
+
+				if assert.Len(t, redeclared.Notes, 1) {
+					assert.Equal(t, "first declared here", redeclared.Notes[0].Message)
+					assert.Equal(t, 2, redeclared.Notes[0].Line)
+					assert.Equal(t, 5, redeclared.Notes[0].Column)
+					assert.Equal(t, "let x = 1", redeclared.Notes[0].Source)
+				}
+			}
+		})
+
+		t.Run("A loop body may shadow an outer local", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet x = 1\nfor i in 0..3 {\nlet x = true\nprint(x)\n}\nprint(x)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Contains(t, mainIR, "x = Alloc(int)")
+			assert.Contains(t, mainIR, "x.1 = Alloc(bool)")
+			assert.Contains(t, mainIR, "Load(x.1)")
+			assert.Contains(t, mainIR[strings.Index(mainIR, "for.end.0:"):], "Load(x)",
+				"the outer local is used again after the loop")
+		})
+
+		t.Run("A shadowing initializer reads the outer local", func(t *testing.T) {
+			module, errorCollector := lowerSource("func main() {\nlet x = 1\nfor i in 0..3 {\nlet x = x + 1\nprint(x)\n}\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			mainIR := module.GetFunction("main").String()
+			assert.Less(t, strings.Index(mainIR, "Load(x)"), strings.Index(mainIR, "x.1 = Alloc(int)"))
+		})
+
+		t.Run("A loop variable may shadow an outer local or loop variable", func(t *testing.T) {
+			_, errorCollector := lowerSource("func main() {\nlet i = 10\nfor i in 0..3 {\nfor i in 0..i {\nprint(i)\n}\n}\nprint(i)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+		})
+
+		t.Run("Shadowing a global is allowed", func(t *testing.T) {
+			module, errorCollector := lowerSource("let x = 1\nfunc main() {\nlet x = true\nprint(x)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Contains(t, module.GetFunction("main").String(), "Alloc(bool)")
+		})
+
+		t.Run("Each function has its own scope", func(t *testing.T) {
+			_, errorCollector := lowerSource("func f() {\nlet x = 1\nprint(x)\n}\nfunc main() {\nlet x = 2\nprint(x)\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+		})
+	})
+
+	t.Run("Comparison result is a bool", func(t *testing.T) {
+		module, errorCollector := lowerSource("func main() {\nlet a = 1\nlet b = a < 2\nprint(b)\n}")
+
+		assert.False(t, errorCollector.HasErrors())
+		assert.Contains(t, module.String(), "Less(")
+		assert.Contains(t, module.String(), "Alloc(bool)")
+	})
+}
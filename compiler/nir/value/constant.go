@@ -18,6 +18,11 @@ func NewConstant(value any, valueType types.Type) *Constant {
 	}
 }
 
+// Value returns the Go value of the constant
+func (constant *Constant) Value() any {
+	return constant.value
+}
+
 func (constant *Constant) Type() types.Type {
 	return constant.valueType
 }
@@ -36,6 +41,8 @@ func (constant *Constant) String() string {
 		return fmt.Sprintf("Constant(%f)", v)
 	case bool:
 		return fmt.Sprintf("Constant(%t)", v)
+	case rune:
+		return fmt.Sprintf("Constant(%q)", v)
 	default:
 		return "Constant(?)"
 	}
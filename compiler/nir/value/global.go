@@ -0,0 +1,32 @@
+package value
+
+import "compiler/types"
+
+// Global represents a module level variable shared by all functions
+type Global struct {
+	name       string
+	globalType types.Type
+}
+
+func NewGlobal(name string, globalType types.Type) *Global {
+	return &Global{
+		name:       name,
+		globalType: globalType,
+	}
+}
+
+func (global *Global) Name() string {
+	return global.name
+}
+
+func (global *Global) Type() types.Type {
+	return global.globalType
+}
+
+func (global *Global) IsConstant() bool {
+	return false
+}
+
+func (global *Global) String() string {
+	return "@" + global.name
+}
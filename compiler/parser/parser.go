@@ -2,9 +2,20 @@ package parser
 
 import (
 	"compiler/ast"
+	"compiler/constants"
 	"compiler/errors"
 	"compiler/lexer"
 	"compiler/token"
+	"fmt"
+	"math"
+	"unicode/utf8"
+)
+
+type (
+	// prefixParseFn parses an expression that starts at the current token
+	prefixParseFn func() ast.Expression
+	// infixParseFn parses an expression continuing from an already parsed left operand
+	infixParseFn func(left ast.Expression) ast.Expression
 )
 
 // Parser analyzes tokens and builds an AST
@@ -13,20 +24,71 @@ type Parser struct {
 	currentToken   token.Token
 	peekToken      token.Token
 	errorCollector *errors.ErrorCollector
+
+	prefixParseFns map[token.TokenType]prefixParseFn
+	infixParseFns  map[token.TokenType]infixParseFn
+
+	expressionDepth    int
+	maxExpressionDepth int
+
+	functionName  string // function whose body is being parsed, for diagnostics
+	depthReported bool   // report "too deeply nested" only once per parse
+
+	// Set after a top-level item fails to parse: the rest of it is skipped silently
+	// until the next item starting in column 1, instead of reporting every line
+	skippingItem bool
 }
 
 func New(lexer *lexer.Lexer, errorCollector *errors.ErrorCollector) *Parser {
 	parser := &Parser{
-		lexer:          lexer,
-		errorCollector: errorCollector,
+		lexer:              lexer,
+		errorCollector:     errorCollector,
+		maxExpressionDepth: constants.MAX_EXPRESSION_DEPTH,
 	}
 
+	parser.registerParseFunctions()
+
 	parser.advance()
 	parser.advance()
 
 	return parser
 }
 
+// registerParseFunctions builds the Pratt parser dispatch tables
+func (parser *Parser) registerParseFunctions() {
+	parser.prefixParseFns = map[token.TokenType]prefixParseFn{
+		token.INT_LITERAL:    parser.parseIntegerLiteral,
+		token.MINUS:          parser.parseNegativeLiteral,
+		token.STRING_LITERAL: parser.parseStringLiteral,
+		token.CHAR_LITERAL:   parser.parseCharLiteral,
+		token.IDENTIFIER:     parser.parseIdentifier,
+		token.TRUE:           parser.parseBooleanLiteral,
+		token.FALSE:          parser.parseBooleanLiteral,
+		token.LEFT_PAREN:     parser.parseGroupedExpression,
+		token.LEFT_BRACKET:   parser.parseArrayLiteral,
+	}
+
+	parser.infixParseFns = map[token.TokenType]infixParseFn{
+		token.LEFT_PAREN:   parser.parseCallExpression,
+		token.LEFT_BRACKET: parser.parseIndexExpression,
+	}
+
+	for tokenType := range operatorTable {
+		if tokenType.IsOperator() {
+			parser.infixParseFns[tokenType] = parser.parseBinaryExpression
+		}
+	}
+
+	// in is a keyword, but in expression position it is a membership test
+	parser.infixParseFns[token.IN] = parser.parseBinaryExpression
+	parser.infixParseFns[token.AS] = parser.parseAscriptionExpression
+}
+
+// SetMaxExpressionDepth overrides the default expression nesting limit
+func (parser *Parser) SetMaxExpressionDepth(depth int) {
+	parser.maxExpressionDepth = depth
+}
+
 func (parser *Parser) advance() {
 	parser.currentToken = parser.peekToken
 	parser.peekToken = parser.lexer.NextToken()
@@ -43,9 +105,11 @@ func (parser *Parser) ParseProgram() *ast.Program {
 			continue
 		}
 
-		statement := parser.parseStatement()
+		statement := parser.parseTopLevelStatement()
 
-		if statement != nil {
+		if global, ok := statement.(*ast.LetStatement); ok {
+			program.Globals = append(program.Globals, global)
+		} else if statement != nil {
 			program.Statements = append(program.Statements, statement)
 		}
 
@@ -57,21 +121,145 @@ func (parser *Parser) ParseProgram() *ast.Program {
 	return program
 }
 
+// parseTopLevelStatement parses a function or a global, the only items allowed outside a function
+// Anything else is reported with the rule it breaks and skipped to the end of its line,
+// so the rest of the file is still checked
+// Tokens the lexer rejected are never reported again here: one bad character is one error
+func (parser *Parser) parseTopLevelStatement() ast.Statement {
+	isItem := parser.currentToken.Type == token.LET || parser.currentToken.Type == token.FUNC || parser.currentToken.Type == token.AT
+	if parser.skippingItem {
+		if !isItem || parser.currentToken.Column != 1 {
+			return nil
+		}
+		parser.skippingItem = false
+	}
+
+	switch parser.currentToken.Type {
+	case token.LET, token.FUNC, token.AT:
+		statement := parser.parseStatement()
+		parser.skippingItem = statement == nil
+		return statement
+	case token.RIGHT_BRACE:
+		parser.errorCollector.Add(errors.SyntaxError,
+			parser.currentToken.Line,
+			parser.currentToken.Column,
+			1,
+			"unexpected '}' outside any function",
+		)
+		return nil
+	case token.ILLEGAL:
+		// The lexer has already reported it
+		return nil
+	default:
+		parser.errorCollector.Add(errors.SyntaxError,
+			parser.currentToken.Line,
+			parser.currentToken.Column,
+			len(parser.currentToken.Value),
+			"statements must appear inside a function",
+		)
+		parser.recoverToEndOfStatement()
+		return nil
+	}
+}
+
+// parseNestedFunction reports a function defined inside another one
+// The function is still parsed so its body is skipped as a whole
+func (parser *Parser) parseNestedFunction() {
+	start := parser.currentToken
+	enclosing := parser.functionName
+
+	statement := parser.parseStatement()
+	parser.functionName = enclosing
+
+	message := "nested function definitions are not supported; move it to the top level"
+	if function, ok := statement.(*ast.FunctionStatement); ok {
+		message = fmt.Sprintf("nested function definitions are not supported; move '%s' to the top level", function.Name.Value)
+	}
+
+	parser.errorCollector.Add(errors.SyntaxError,
+		start.Line,
+		start.Column,
+		len(start.Value),
+		"%s",
+		message,
+	)
+}
+
 func (parser *Parser) parseStatement() ast.Statement {
 	switch parser.currentToken.Type {
 	case token.LET:
 		return parser.parseLetStatement()
 	case token.FUNC:
 		return parser.parseFunctionStatement()
+	case token.AT:
+		return parser.parseAnnotatedFunction()
 	case token.RETURN:
 		return parser.parseReturnStatement()
+	case token.FOR:
+		return parser.parseForStatement()
 	case token.IDENTIFIER:
+		// A name followed by '=' starts an assignment; looking ahead decides
+		// before anything is consumed, so the expression path sees every token
+		if parser.peekToken.Type == token.ASSIGN {
+			return parser.parseAssignment()
+		}
 		return parser.parseExpressionStatement()
 	default:
+		// Any other expression can stand alone, so 1 = 2 reaches the assignment check
+		if _, isExpression := parser.prefixParseFns[parser.currentToken.Type]; isExpression {
+			return parser.parseExpressionStatement()
+		}
 		return nil
 	}
 }
 
+// parseAssignment parses `name = value`
+// The current token is the name; it is left on the last token of the value
+func (parser *Parser) parseAssignment() ast.Statement {
+	name := &ast.Identifier{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
+
+	parser.advance() // consume name
+	statement := &ast.AssignmentStatement{
+		Token: parser.currentToken,
+		Name:  name,
+	}
+
+	parser.advance() // consume '='
+	statement.Value = parser.parseExpression(LOWEST)
+	if statement.Value == nil {
+		parser.recoverToEndOfStatement()
+		return nil
+	}
+
+	parser.skipEndOfStatement()
+
+	return statement
+}
+
+// parseIndexAssignment parses the rest of `target[index] = value` once the target is parsed
+// The current token is the target's ']'; it is left on the last token of the value
+func (parser *Parser) parseIndexAssignment(target *ast.IndexExpression) ast.Statement {
+	parser.advance() // consume ']'
+	statement := &ast.IndexAssignmentStatement{
+		Token:  parser.currentToken,
+		Target: target,
+	}
+
+	parser.advance() // consume '='
+	statement.Value = parser.parseExpression(LOWEST)
+	if statement.Value == nil {
+		parser.recoverToEndOfStatement()
+		return nil
+	}
+
+	parser.skipEndOfStatement()
+
+	return statement
+}
+
 func (parser *Parser) parseLetStatement() ast.Statement {
 	letToken := parser.currentToken
 
@@ -113,6 +301,10 @@ func (parser *Parser) parseLetStatement() ast.Statement {
 	}
 
 	value := parser.parseExpression(LOWEST)
+	if value == nil {
+		parser.recoverToEndOfStatement()
+		return nil
+	}
 
 	statement := &ast.LetStatement{
 		Token:          letToken,
@@ -142,6 +334,8 @@ func (parser *Parser) parseFunctionStatement() ast.Statement {
 		Token: parser.currentToken,
 		Value: parser.currentToken.Value,
 	}
+	parser.functionName = function.Name.Value
+	defer func() { parser.functionName = "" }()
 
 	parser.advance() // consume function name
 	if !parser.expect(token.LEFT_PAREN) {
@@ -154,16 +348,10 @@ func (parser *Parser) parseFunctionStatement() ast.Statement {
 		parser.advance()
 		parser.advance() // consume '->'
 
-		if !parser.expect(token.IDENTIFIER) {
+		function.ReturnType = parser.parseTypeName()
+		if function.ReturnType == nil {
 			return nil
 		}
-
-		function.ReturnType = &ast.TypeAnnotation{
-			Token: parser.currentToken,
-			Value: parser.currentToken.Value,
-		}
-
-		parser.advance() // consume return type
 	}
 
 	if !parser.expect(token.LEFT_BRACE) {
@@ -179,6 +367,58 @@ func (parser *Parser) parseFunctionStatement() ast.Statement {
 	return function
 }
 
+// knownAnnotations lists the annotations a function may carry
+var knownAnnotations = map[string]bool{
+	"inline": true,
+}
+
+// parseAnnotatedFunction parses one or more annotations followed by a function
+// Example: @inline func square(x: int) -> int { ... }
+func (parser *Parser) parseAnnotatedFunction() ast.Statement {
+	annotations := []*ast.Annotation{}
+
+	for parser.currentToken.Type == token.AT {
+		atToken := parser.currentToken
+
+		if !parser.expectPeek(token.IDENTIFIER) {
+			return nil
+		}
+		parser.advance() // consume '@'
+
+		name := parser.currentToken.Value
+		if !knownAnnotations[name] {
+			parser.errorCollector.Add(errors.SyntaxError,
+				atToken.Line,
+				atToken.Column,
+				len(name)+1,
+				"Unknown annotation '@%s'",
+				name,
+			)
+			return nil
+		}
+
+		annotations = append(annotations, &ast.Annotation{Token: atToken, Name: name})
+		parser.advance() // consume annotation name
+
+		for parser.currentToken.Type == token.NEW_LINE {
+			parser.advance()
+		}
+	}
+
+	if !parser.expect(token.FUNC) {
+		return nil
+	}
+
+	statement := parser.parseFunctionStatement()
+	function, ok := statement.(*ast.FunctionStatement)
+	if !ok || function == nil {
+		return nil
+	}
+
+	function.Annotations = annotations
+	return function
+}
+
 func (parser *Parser) parseFunctionParameters() []*ast.FunctionParameter {
 	parameters := []*ast.FunctionParameter{}
 
@@ -241,8 +481,9 @@ func (parser *Parser) parseBlockStatement() *ast.BlockStatement {
 			continue
 		}
 
-		statement := parser.parseStatement()
-		if statement != nil {
+		if parser.currentToken.Type == token.FUNC || parser.currentToken.Type == token.AT {
+			parser.parseNestedFunction()
+		} else if statement := parser.parseStatement(); statement != nil {
 			block.Statements = append(block.Statements, statement)
 		}
 
@@ -251,10 +492,12 @@ func (parser *Parser) parseBlockStatement() *ast.BlockStatement {
 		}
 	}
 
-	if !parser.expect(token.RIGHT_BRACE) {
+	if parser.currentToken.Type == token.EOF {
+		parser.reportUnclosed(block.Token)
 		return nil
 	}
 
+	block.End = parser.currentToken
 	parser.advance() // consume '}'
 	return block
 }
@@ -264,6 +507,27 @@ func (parser *Parser) parseExpressionStatement() ast.Statement {
 		Token:      parser.currentToken,
 		Expression: parser.parseExpression(LOWEST),
 	}
+	if statement.Expression == nil {
+		parser.recoverToEndOfStatement()
+		return nil
+	}
+
+	// Only a plain name or an element can be assigned; anything else followed by '='
+	// would otherwise be reported as a missing ';'
+	if parser.peekToken.Type == token.ASSIGN {
+		if target, isIndex := statement.Expression.(*ast.IndexExpression); isIndex {
+			return parser.parseIndexAssignment(target)
+		}
+		parser.errorCollector.Add(errors.SyntaxError,
+			statement.Token.Line,
+			statement.Token.Column,
+			len(statement.Token.Value),
+			"cannot assign to %s: only variables and elements can be assigned",
+			statement.Expression.String(),
+		)
+		parser.recoverToEndOfStatement()
+		return nil
+	}
 
 	parser.skipEndOfStatement()
 
@@ -278,6 +542,10 @@ func (parser *Parser) parseReturnStatement() ast.Statement {
 	parser.advance()
 
 	returnStatement.ReturnValue = parser.parseExpression(LOWEST)
+	if returnStatement.ReturnValue == nil {
+		parser.recoverToEndOfStatement()
+		return nil
+	}
 
 	parser.skipEndOfStatement()
 
@@ -285,44 +553,254 @@ func (parser *Parser) parseReturnStatement() ast.Statement {
 
 }
 
+// parseForStatement parses `for name in start..end { body }`, or ..= to include end
+// Without a range operator the expression is an iterable: `for ch in name { body }`
+// Neither range operator has an infix rule, so each bound is a whole expression: 0..n*2 ends at n*2
+// Like a function, the loop ends after its body's '}'
+func (parser *Parser) parseForStatement() ast.Statement {
+	statement := &ast.ForStatement{
+		Token: parser.currentToken,
+	}
+
+	if !parser.expectPeek(token.IDENTIFIER) {
+		parser.recoverToForBody()
+		return nil
+	}
+	parser.advance() // consume 'for'
+
+	statement.Variable = &ast.Identifier{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
+
+	if !parser.expectPeek(token.IN) {
+		parser.recoverToForBody()
+		return nil
+	}
+	parser.advance()
+	parser.advance() // consume 'in'
+
+	statement.Start = parser.parseExpression(LOWEST)
+	if statement.Start == nil {
+		parser.recoverToForBody()
+		return nil
+	}
+
+	if parser.peekToken.Type == token.LEFT_BRACE {
+		statement.Iterable = statement.Start
+		statement.Start = nil
+	} else if !parser.parseForRange(statement) {
+		parser.recoverToForBody()
+		return nil
+	}
+
+	if !parser.expectPeek(token.LEFT_BRACE) {
+		parser.recoverToForBody()
+		return nil
+	}
+	parser.advance()
+
+	statement.Body = parser.parseBlockStatement()
+	if statement.Body == nil {
+		return nil
+	}
+
+	if !isStatementBoundary(parser.currentToken.Type) && parser.currentToken.Type != token.ILLEGAL {
+		parser.errorCollector.Add(errors.SyntaxError,
+			parser.currentToken.Line,
+			parser.currentToken.Column,
+			len(parser.currentToken.Value),
+			"missing ';' after for loop",
+		)
+	}
+
+	return statement
+}
+
+// parseForRange parses the rest of a range after its start: the range operator and the end
+func (parser *Parser) parseForRange(statement *ast.ForStatement) bool {
+	switch parser.peekToken.Type {
+	case token.DOT_DOT:
+	case token.DOT_DOT_EQUAL:
+		statement.Inclusive = true
+	case token.ILLEGAL:
+		// The lexer has already reported it
+		return false
+	default:
+		parser.errorCollector.Add(errors.SyntaxError,
+			parser.peekToken.Line,
+			parser.peekToken.Column,
+			len(parser.peekToken.Value),
+			"expected '..', '..=' or '{' after the start of the range, got %s",
+			parser.peekToken.Type.String(),
+		)
+		return false
+	}
+	parser.advance()
+	parser.advance() // consume the range operator
+
+	statement.End = parser.parseExpression(LOWEST)
+	return statement.End != nil
+}
+
+// recoverToForBody skips a malformed loop header and then the body after it,
+// so the body's statements and closing '}' are not taken for the enclosing block's
+func (parser *Parser) recoverToForBody() {
+	for parser.currentToken.Type != token.LEFT_BRACE && !isStatementBoundary(parser.currentToken.Type) {
+		parser.advance()
+	}
+	if parser.currentToken.Type == token.LEFT_BRACE {
+		parser.parseBlockStatement()
+	}
+}
+
 func (parser *Parser) parseExpression(precedence int) ast.Expression {
-	left := parser.parseAtom()
+	if !parser.enterExpression() {
+		return nil
+	}
+	defer parser.leaveExpression()
+
+	prefix, ok := parser.prefixParseFns[parser.currentToken.Type]
+	if !ok {
+		if parser.currentToken.Type == token.ILLEGAL {
+			return nil // the lexer has already reported it
+		}
+		parser.errorCollector.Add(errors.SyntaxError,
+			parser.currentToken.Line,
+			parser.currentToken.Column,
+			len(parser.currentToken.Value),
+			"Unexpected token '%s' in expression",
+			parser.currentToken.Type.String(),
+		)
+		return nil
+	}
+
+	left := prefix()
 	if left == nil {
 		return nil
 	}
 
 	for !parser.isStatementEnd() && precedence < getPrecedence(parser.peekToken.Type) {
-
-		if parser.peekToken.Type == token.LEFT_PAREN {
-			parser.advance() // advance to '('
-			left = parser.parseCallExpression(left)
-			continue
+		infix, ok := parser.infixParseFns[parser.peekToken.Type]
+		if !ok {
+			break
 		}
 
-		if !parser.peekToken.Type.IsOperator() {
-			break
+		parser.advance() // advance to operator
+
+		left = infix(left)
+		if left == nil {
+			return nil
 		}
+	}
+
+	return left
+}
 
-		operatorToken := parser.peekToken
-		operatorPrecedence := getPrecedence(operatorToken.Type)
+// parseBinaryExpression parses the right operand of an infix operator
+// The current token is the operator
+func (parser *Parser) parseBinaryExpression(left ast.Expression) ast.Expression {
+	operatorToken := parser.currentToken
 
-		parser.advance()
-		parser.advance() // advance to right operand
+	parser.advance() // advance to right operand
+
+	right := parser.parseExpression(getRightBindingPrecedence(operatorToken.Type))
+	if right == nil {
+		return nil
+	}
+
+	return &ast.BinaryExpression{
+		Token:    operatorToken,
+		Left:     left,
+		Operator: operatorToken.Value,
+		Right:    right,
+	}
+}
+
+// parseGroupedExpression parses `( expression )`
+// Grouping only affects the tree's shape; binary expressions remember it
+// so the type checker can tell `(a < b) == c` from a chained comparison
+func (parser *Parser) parseGroupedExpression() ast.Expression {
+	openParen := parser.currentToken
+	if !parser.expectOpenLine(openParen) {
+		return nil
+	}
+
+	parser.advance() // consume '('
+
+	expression := parser.parseExpression(LOWEST)
+	if expression == nil {
+		return nil
+	}
+
+	if !parser.expectClosingParen(openParen) {
+		return nil
+	}
+	parser.advance() // consume ')'
+
+	if binary, ok := expression.(*ast.BinaryExpression); ok {
+		binary.Grouped = true
+	}
+
+	return expression
+}
+
+// parseArrayLiteral parses `[a, b, c]`; a trailing comma is allowed and `[]` is empty
+// Like parentheses, brackets cannot span lines
+// The current token is '['; it is left on the closing ']'
+func (parser *Parser) parseArrayLiteral() ast.Expression {
+	array := &ast.ArrayLiteral{
+		Token:    parser.currentToken,
+		Elements: []ast.Expression{},
+	}
 
-		right := parser.parseExpression(operatorPrecedence)
-		if right == nil {
+	for parser.peekToken.Type != token.RIGHT_BRACKET {
+		if !parser.expectOpenLine(array.Token) {
 			return nil
 		}
+		parser.advance() // consume '[' or ','
 
-		left = &ast.BinaryExpression{
-			Token:    operatorToken,
-			Left:     left,
-			Operator: operatorToken.Value,
-			Right:    right,
+		element := parser.parseExpression(LOWEST)
+		if element == nil {
+			return nil
+		}
+		array.Elements = append(array.Elements, element)
+
+		if parser.peekToken.Type != token.COMMA {
+			break
 		}
+		parser.advance() // consume element
 	}
 
-	return left
+	if !parser.expectOpenLine(array.Token) || !parser.expectPeek(token.RIGHT_BRACKET) {
+		return nil
+	}
+	parser.advance() // consume ']'
+
+	return array
+}
+
+// parseAscriptionExpression parses `expression as type`
+// The current token is 'as'; the type is left as the current token
+func (parser *Parser) parseAscriptionExpression(expression ast.Expression) ast.Expression {
+	ascription := &ast.AscriptionExpression{
+		Token:      parser.currentToken,
+		Expression: expression,
+	}
+
+	parser.advance() // consume 'as'
+
+	if !isTypeToken(parser.currentToken.Type) {
+		parser.reportExpectedType()
+		return nil
+	}
+
+	ascription.Type = &ast.TypeAnnotation{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
+
+	return ascription
 }
 
 func (parser *Parser) parseCallExpression(function ast.Expression) ast.Expression {
@@ -333,28 +811,71 @@ func (parser *Parser) parseCallExpression(function ast.Expression) ast.Expressio
 	}
 
 	call.Arguments = parser.parseCallArguments()
+	if call.Arguments == nil {
+		return nil
+	}
 
 	return call
 }
 
+// parseIndexExpression parses `left[index]`
+// The current token is '['; it is left on the closing ']'
+func (parser *Parser) parseIndexExpression(left ast.Expression) ast.Expression {
+	openBracket := parser.currentToken
+	if !parser.expectOpenLine(openBracket) {
+		return nil
+	}
+
+	parser.advance() // consume '['
+
+	index := parser.parseExpression(LOWEST)
+	if index == nil {
+		return nil
+	}
+
+	if !parser.expectOpenLine(openBracket) || !parser.expectPeek(token.RIGHT_BRACKET) {
+		return nil
+	}
+
+	parser.advance() // consume ']'
+
+	return &ast.IndexExpression{
+		Token: openBracket,
+		Left:  left,
+		Index: index,
+	}
+}
+
 func (parser *Parser) parseCallArguments() []ast.Expression {
 	arguments := []ast.Expression{}
+	openParen := parser.currentToken
 
 	if parser.peekToken.Type == token.RIGHT_PAREN {
 		parser.advance() // consume ')'
 		return arguments
 	}
+	if !parser.expectOpenLine(openParen) {
+		return nil
+	}
 
 	parser.advance() // consume '('
-	arguments = append(arguments, parser.parseExpression(LOWEST))
+	argument := parser.parseExpression(LOWEST)
+	if argument == nil {
+		return nil
+	}
+	arguments = append(arguments, argument)
 
 	for parser.peekToken.Type == token.COMMA {
 		parser.advance() // consume argument
 		parser.advance() // consume comma
 
-		arguments = append(arguments, parser.parseExpression(LOWEST))
+		argument := parser.parseExpression(LOWEST)
+		if argument == nil {
+			return nil
+		}
+		arguments = append(arguments, argument)
 	}
-	if !parser.expectPeek(token.RIGHT_PAREN) {
+	if !parser.expectClosingParen(openParen) {
 		return nil
 	}
 
@@ -363,40 +884,145 @@ func (parser *Parser) parseCallArguments() []ast.Expression {
 	return arguments
 }
 
-func (parser *Parser) skipEndOfStatement() {
-	if parser.peekToken.Type == token.SEMICOLON || parser.peekToken.Type == token.NEW_LINE {
+// enterExpression guards parseExpression against unbounded recursion
+// Returns false (after reporting once) when the nesting limit is exceeded
+func (parser *Parser) enterExpression() bool {
+	if parser.expressionDepth >= parser.maxExpressionDepth {
+		if !parser.depthReported {
+			parser.depthReported = true
+			parser.errorCollector.Add(errors.SyntaxError,
+				parser.currentToken.Line,
+				parser.currentToken.Column,
+				len(parser.currentToken.Value),
+				"expression too deeply nested (limit is %d)",
+				parser.maxExpressionDepth,
+			)
+		}
+		return false
+	}
+
+	parser.expressionDepth++
+	return true
+}
+
+func (parser *Parser) leaveExpression() {
+	parser.expressionDepth--
+}
+
+// recoverToEndOfStatement skips the rest of a statement that failed to parse,
+// so one mistake is reported once rather than again for each leftover token
+func (parser *Parser) recoverToEndOfStatement() {
+	for !parser.isStatementEnd() && !isStatementBoundary(parser.peekToken.Type) {
 		parser.advance()
 	}
 }
 
-// parseAtom parses an literals and identifiers
-func (parser *Parser) parseAtom() ast.Expression {
-	switch parser.currentToken.Type {
-	case token.INT_LITERAL:
-		return &ast.IntegerLiteral{
-			Token: parser.currentToken,
-			Value: parser.currentToken.Value,
-		}
-	case token.STRING_LITERAL:
-		return &ast.StringLiteral{
-			Token: parser.currentToken,
-			Value: parser.currentToken.Value,
-		}
-	case token.IDENTIFIER:
-		return &ast.Identifier{
-			Token: parser.currentToken,
-			Value: parser.currentToken.Value,
-		}
+func isStatementBoundary(tokenType token.TokenType) bool {
+	switch tokenType {
+	case token.SEMICOLON, token.NEW_LINE, token.RIGHT_BRACE, token.EOF:
+		return true
+	default:
+		return false
+	}
+}
+
+// skipEndOfStatement consumes the ';' or newline ending a statement
+// Anything else on the same line means the separator was forgotten: the error
+// points just past the statement, and parsing continues as if it were there
+func (parser *Parser) skipEndOfStatement() {
+	switch parser.peekToken.Type {
+	case token.SEMICOLON, token.NEW_LINE:
+		parser.advance()
+	case token.RIGHT_BRACE, token.EOF:
+		// The enclosing block or file ends the statement
+	case token.ILLEGAL:
+		// The lexer has already reported it; the bad character is skipped as its own statement
 	default:
 		parser.errorCollector.Add(errors.SyntaxError,
 			parser.currentToken.Line,
-			parser.currentToken.Column,
-			len(parser.currentToken.Value),
-			"Unexpected token '%s' in expression",
-			parser.currentToken.Type.String(),
+			parser.currentToken.Column+len(parser.currentToken.Value),
+			1,
+			"missing ';' after expression",
+		)
+	}
+}
+
+// minIntMagnitude is -math.MinInt64, the one literal the lexer accepts that only fits after a minus
+const minIntMagnitude = "9223372036854775808"
+
+// parseIntegerLiteral parses a literal that is not negated
+// The lexer has already reported every other literal that does not fit in int
+func (parser *Parser) parseIntegerLiteral() ast.Expression {
+	literal := parser.currentToken
+	if literal.Value == minIntMagnitude {
+		parser.errorCollector.Add(errors.SyntaxError,
+			literal.Line,
+			literal.Column,
+			literal.End-literal.Offset,
+			"integer literal out of range for int (max %d)",
+			int64(math.MaxInt64),
 		)
 		return nil
 	}
+
+	return &ast.IntegerLiteral{
+		Token: literal,
+		Value: literal.Value,
+	}
+}
+
+// parseNegativeLiteral parses a minus in prefix position followed by an integer literal
+// The lexer never folds the sign into the number, so x-1 stays a subtraction;
+// only a minus where an operand is expected makes a negative constant
+// There is no general unary minus yet, so -x is an error
+func (parser *Parser) parseNegativeLiteral() ast.Expression {
+	minus := parser.currentToken
+	if parser.peekToken.Type != token.INT_LITERAL {
+		parser.errorCollector.Add(errors.SyntaxError,
+			minus.Line,
+			minus.Column,
+			len(minus.Value),
+			"unary minus only applies to integer literals; subtract from 0 instead",
+		)
+		return nil
+	}
+
+	parser.advance() // consume '-'
+
+	literal := parser.currentToken
+	literal.Value = "-" + literal.Value
+	literal.Line = minus.Line
+	literal.Column = minus.Column
+	literal.Offset = minus.Offset
+
+	return &ast.IntegerLiteral{
+		Token: literal,
+		Value: literal.Value,
+	}
+}
+
+// parseStringLiteral merges adjacent literals into one: "ab" "cd" is "abcd"
+// Token values are already escape-processed, so each piece keeps its own escapes
+// Only plain literals are merged; interpolated strings will not take part
+func (parser *Parser) parseStringLiteral() ast.Expression {
+	literal := &ast.StringLiteral{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
+
+	for parser.peekToken.Type == token.STRING_LITERAL {
+		parser.advance()
+		literal.Value += parser.currentToken.Value
+	}
+
+	return literal
+}
+
+func (parser *Parser) parseIdentifier() ast.Expression {
+	return &ast.Identifier{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
 }
 
 func (parser *Parser) isStatementEnd() bool {
@@ -411,6 +1037,9 @@ func (parser *Parser) expectPeek(tokenType token.TokenType) bool {
 	if parser.peekToken.Type == tokenType {
 		return true
 	}
+	if parser.peekToken.Type == token.ILLEGAL {
+		return false // the lexer has already reported it
+	}
 
 	parser.errorCollector.Add(
 		errors.SyntaxError,
@@ -425,10 +1054,51 @@ func (parser *Parser) expectPeek(tokenType token.TokenType) bool {
 	return false
 }
 
+// expectClosingParen checks that the peek token is the ')' matching openParen
+// Parentheses cannot span lines, so a line or file that ends first is
+// reported as an unclosed '(' at its own position
+func (parser *Parser) expectClosingParen(openParen token.Token) bool {
+	if !parser.expectOpenLine(openParen) {
+		return false
+	}
+
+	return parser.expectPeek(token.RIGHT_PAREN)
+}
+
+// expectOpenLine reports openParen as unclosed when the line ends after the current token
+func (parser *Parser) expectOpenLine(openParen token.Token) bool {
+	if parser.peekToken.Type == token.NEW_LINE || parser.peekToken.Type == token.EOF {
+		parser.reportUnclosed(openParen)
+		return false
+	}
+
+	return true
+}
+
+// reportUnclosed reports a '{' or '(' that was never closed at the delimiter itself,
+// naming the enclosing function when there is one
+func (parser *Parser) reportUnclosed(open token.Token) {
+	message := fmt.Sprintf("unclosed '%s' opened at line %d", open.Value, open.Line)
+	if parser.functionName != "" {
+		message += fmt.Sprintf(" in function %s", parser.functionName)
+	}
+
+	parser.errorCollector.Add(errors.SyntaxError,
+		open.Line,
+		open.Column,
+		len(open.Value),
+		"%s",
+		message,
+	)
+}
+
 func (parser *Parser) expect(tokenType token.TokenType) bool {
 	if parser.currentToken.Type == tokenType {
 		return true
 	}
+	if parser.currentToken.Type == token.ILLEGAL {
+		return false // the lexer has already reported it
+	}
 
 	parser.errorCollector.Add(errors.SyntaxError,
 		parser.currentToken.Line,
@@ -448,19 +1118,66 @@ func (parser *Parser) parseTypeAnnotation() *ast.TypeAnnotation {
 
 	parser.advance() // consume ':'
 
-	switch parser.currentToken.Type {
-	case token.INT, token.FLOAT, token.STRING, token.BOOL, token.IDENTIFIER:
+	return parser.parseTypeName()
+}
 
-		typeAnnotation := &ast.TypeAnnotation{
-			Token: parser.currentToken,
-			Value: parser.currentToken.Value,
-		}
+// parseTypeName parses a builtin type keyword or a named type, followed by one [] per array level
+// Example: int, string[][]
+func (parser *Parser) parseTypeName() *ast.TypeAnnotation {
+	if !isTypeToken(parser.currentToken.Type) {
+		parser.reportExpectedType()
+		return nil
+	}
+
+	typeAnnotation := &ast.TypeAnnotation{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Value,
+	}
 
-		parser.advance() // consume type
+	parser.advance() // consume type
 
-		return typeAnnotation
+	for parser.currentToken.Type == token.LEFT_BRACKET && parser.peekToken.Type == token.RIGHT_BRACKET {
+		typeAnnotation.Value += "[]"
+		parser.advance() // consume '['
+		parser.advance() // consume ']'
+	}
+
+	return typeAnnotation
+}
+
+// isTypeToken reports whether a token can name a type
+func isTypeToken(tokenType token.TokenType) bool {
+	switch tokenType {
+	case token.INT, token.FLOAT, token.STRING, token.BOOL, token.CHAR, token.IDENTIFIER:
+		return true
 	default:
-		return nil
+		return false
+	}
+}
+
+func (parser *Parser) reportExpectedType() {
+	parser.errorCollector.Add(errors.SyntaxError,
+		parser.currentToken.Line,
+		parser.currentToken.Column,
+		len(parser.currentToken.Value),
+		"expected type, got %s",
+		parser.currentToken.Type.String(),
+	)
+}
+
+func (parser *Parser) parseCharLiteral() ast.Expression {
+	// The lexer has already decoded escapes and reported malformed literals
+	value, _ := utf8.DecodeRuneInString(parser.currentToken.Value)
+
+	return &ast.CharLiteral{
+		Token: parser.currentToken,
+		Value: value,
 	}
+}
 
+func (parser *Parser) parseBooleanLiteral() ast.Expression {
+	return &ast.BooleanLiteral{
+		Token: parser.currentToken,
+		Value: parser.currentToken.Type == token.TRUE,
+	}
 }
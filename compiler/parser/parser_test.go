@@ -0,0 +1,740 @@
+package parser
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func parseSource(input string) (*ast.Program, *errors.ErrorCollector) {
+	errorCollector := errors.New(input, "test.navi")
+	lexerInstance := lexer.New(input, "test.navi", errorCollector)
+	parserInstance := New(lexerInstance, errorCollector)
+
+	return parserInstance.ParseProgram(), errorCollector
+}
+
+// parseExpressionString parses a single expression statement inside main and
+// returns its fully parenthesized form
+func parseExpressionString(t *testing.T, expression string) string {
+	program, errorCollector := parseSource("func main() {\n" + expression + "\n}")
+
+	assert.False(t, errorCollector.HasErrors(),
+		"Parser should not produce errors for %q", expression)
+	if !assert.Len(t, program.Statements, 1) {
+		return ""
+	}
+
+	function := program.Statements[0].(*ast.FunctionStatement)
+	if !assert.Len(t, function.Body.Statements, 1) {
+		return ""
+	}
+
+	return function.Body.Statements[0].String()
+}
+
+func TestParser(t *testing.T) {
+	t.Run("Test operator precedence", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Addition", "a + b", "(a + b)"},
+			{"Product binds tighter than sum", "a + b * c", "(a + (b * c))"},
+			{"Product on the left", "a * b + c", "((a * b) + c)"},
+			{"Division binds tighter than subtraction", "a - b / c", "(a - (b / c))"},
+			{"Subtraction is left associative", "a - b - c", "((a - b) - c)"},
+			{"Addition is left associative", "a + b + c", "((a + b) + c)"},
+			{"Division is left associative", "a / b / c", "((a / b) / c)"},
+			{"Mixed product operators", "a / b * c", "((a / b) * c)"},
+			{"Mixed sum operators", "a + b - c", "((a + b) - c)"},
+			{"Full mix", "a + b - c * d / e", "((a + b) - ((c * d) / e))"},
+			{"Products on both sides", "a * b + c * d", "((a * b) + (c * d))"},
+			{"Call binds tighter than operators", "f(a) + b * g(c)", "(f(a) + (b * g(c)))"},
+			{"Operators inside call arguments", "f(a + b * c, d - e)", "f((a + (b * c)), (d - e))"},
+			{"Nested calls", "f(g(a), h(b))", "f(g(a), h(b))"},
+			{"Literals", "x * 2 + 1", "((x * 2) + 1)"},
+			{"Sum binds tighter than comparison", "a + 1 < b * 2", "((a + 1) < (b * 2))"},
+			{"Comparison binds tighter than equality", "a < b == c > d", "((a < b) == (c > d))"},
+			{"Equality is left associative", "a == b != c", "((a == b) != c)"},
+			{"Boolean literals", "true == false", "(true == false)"},
+			{"Char literals", "'a' < '\\n'", "('a' < '\\n')"},
+			{"Membership binds like comparison", "a + 1 in b == c", "(((a + 1) in b) == c)"},
+			{"Ascription binds looser than comparison", "a < b as bool", "((a < b) as bool)"},
+			{"Ascription inside call arguments", "f(x as int, y)", "f((x as int), y)"},
+			{"Parentheses override precedence", "(a + b) * c", "((a + b) * c)"},
+			{"Parentheses on the right", "a - (b - c)", "(a - (b - c))"},
+			{"Nested parentheses", "((a))", "a"},
+			{"Parenthesized call argument", "f((a + b))", "f((a + b))"},
+			{"Minus without spaces is subtraction", "x-1", "(x - 1)"},
+			{"Minus before a literal after an operand is subtraction", "x -1", "(x - 1)"},
+			{"Minus after an operand is subtraction", "x- 1", "(x - 1)"},
+			{"Subtraction of literals", "1-2", "(1 - 2)"},
+			{"Negative literal", "-5", "-5"},
+			{"Negative literal operand", "-5 * 2", "(-5 * 2)"},
+			{"Subtracting a negative literal", "1 - -2", "(1 - -2)"},
+			{"Negative literal argument", "f(-1, x-1)", "f(-1, (x - 1))"},
+			{"Index binds tighter than operators", "s[0] + 1", "(s[0] + 1)"},
+			{"Operators inside an index", "s[i + 1] < c", "(s[(i + 1)] < c)"},
+			{"Index of a call result", "f(x)[0]", "f(x)[0]"},
+			{"Index as a call argument", "print(s[0], t[1])", "print(s[0], t[1])"},
+			{"Chained index", "m[i][j]", "m[i][j]"},
+			{"Chained index binds tighter than operators", "m[i][j] * 2", "(m[i][j] * 2)"},
+			{"Index of a call without arguments", "f()[0]", "f()[0]"},
+			{"Index inside an index", "xs[ys[0]]", "xs[ys[0]]"},
+			{"Indexes on both sides", "xs[i] - ys[i + 1]", "(xs[i] - ys[(i + 1)])"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				assert.Equal(t, testCase.expected, parseExpressionString(t, testCase.input))
+			})
+		}
+	})
+
+	t.Run("Test the minimum int literal", func(t *testing.T) {
+		t.Run("Negated literals are accepted", func(t *testing.T) {
+			for _, input := range []string{"-9223372036854775808", "- 9223372036854775808", "-0x8000000000000000"} {
+				assert.Equal(t, "-9223372036854775808", parseExpressionString(t, input))
+			}
+		})
+
+		t.Run("Literals that are not negated overflow", func(t *testing.T) {
+			tests := []struct {
+				input          string
+				expectedColumn int
+			}{
+				{"9223372036854775808", 3},
+				{"x-9223372036854775808", 5},
+				{"1 - 0x8000000000000000", 7},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.input, func(t *testing.T) {
+					_, errorCollector := parseSource("func main() {\n  " + testCase.input + "\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						overflow := errorCollector.Errors()[0]
+						assert.Equal(t, "integer literal out of range for int (max 9223372036854775807)", overflow.Message)
+						assert.Equal(t, 2, overflow.Line)
+						assert.Equal(t, testCase.expectedColumn, overflow.Column)
+					}
+				})
+			}
+		})
+	})
+
+	t.Run("Test unary minus on a non-literal is rejected", func(t *testing.T) {
+		_, errorCollector := parseSource("func main() {\n  print(-x)\n}")
+
+		if assert.True(t, errorCollector.HasErrors()) {
+			assert.Equal(t, "unary minus only applies to integer literals; subtract from 0 instead",
+				errorCollector.Errors()[0].Message)
+		}
+	})
+
+	t.Run("Test function return types", func(t *testing.T) {
+		for _, returnType := range []string{"int", "float", "string", "bool"} {
+			t.Run(returnType, func(t *testing.T) {
+				program, errorCollector := parseSource("func f() -> " + returnType + " {\n}")
+
+				assert.False(t, errorCollector.HasErrors(),
+					"Type keywords should be accepted as return types")
+				if assert.Len(t, program.Statements, 1) {
+					function := program.Statements[0].(*ast.FunctionStatement)
+					assert.Equal(t, returnType, function.ReturnType.Value)
+				}
+			})
+		}
+	})
+
+	t.Run("Test annotations", func(t *testing.T) {
+		t.Run("Inline annotation is attached to the function", func(t *testing.T) {
+			program, errorCollector := parseSource("@inline\nfunc square(x: int) -> int {\nreturn x * x\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			if assert.Len(t, program.Statements, 1) {
+				function := program.Statements[0].(*ast.FunctionStatement)
+				if assert.Len(t, function.Annotations, 1) {
+					assert.Equal(t, "inline", function.Annotations[0].Name)
+				}
+			}
+		})
+
+		t.Run("Unknown annotations are rejected", func(t *testing.T) {
+			_, errorCollector := parseSource("@fast func square(x: int) -> int {\nreturn x * x\n}")
+
+			if assert.True(t, errorCollector.HasErrors()) {
+				assert.Equal(t, "Unknown annotation '@fast'", errorCollector.Errors()[0].Message)
+			}
+		})
+	})
+
+	t.Run("Test let statements", func(t *testing.T) {
+		tests := []struct {
+			name            string
+			input           string
+			expectedMutable bool
+			expectedType    string
+		}{
+			{"Immutable", "let x = 1", false, ""},
+			{"Immutable with a type", "let x: int = 1", false, "int"},
+			{"Mutable", "let mut x = 1", true, ""},
+			{"Mutable with a type", "let mut x: int = 1", true, "int"},
+			{"Colon assign is mutable", "let x := 1", true, ""},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				program, errorCollector := parseSource("func main() {\n" + testCase.input + "\n}")
+
+				assert.False(t, errorCollector.HasErrors())
+				function := program.Statements[0].(*ast.FunctionStatement)
+				if assert.Len(t, function.Body.Statements, 1) {
+					let := function.Body.Statements[0].(*ast.LetStatement)
+					assert.Equal(t, "x", let.Name.Value)
+					assert.Equal(t, testCase.expectedMutable, let.Mutable)
+					if testCase.expectedType == "" {
+						assert.Nil(t, let.TypeAnnotation)
+					} else if assert.NotNil(t, let.TypeAnnotation) {
+						assert.Equal(t, testCase.expectedType, let.TypeAnnotation.Value)
+					}
+					assert.Equal(t, "1", let.Value.String())
+				}
+			})
+		}
+	})
+
+	t.Run("Test assignments", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Literal", "x = 10", "x = 10"},
+			{"Call result", "x = f(1) + 2", "x = (f(1) + 2)"},
+			{"Name on the right", "x = x * 2", "x = (x * 2)"},
+			{"With a separator", "x = 1; y = 2", "x = 1"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				program, errorCollector := parseSource("func main() {\n" + testCase.input + "\n}")
+
+				assert.False(t, errorCollector.HasErrors())
+				function := program.Statements[0].(*ast.FunctionStatement)
+				assignment, ok := function.Body.Statements[0].(*ast.AssignmentStatement)
+				if assert.True(t, ok, "expected an assignment, got %T", function.Body.Statements[0]) {
+					assert.Equal(t, testCase.expected, assignment.String())
+					assert.Equal(t, "x", assignment.Name.Value)
+				}
+			})
+		}
+
+		t.Run("Only a name followed by '=' starts an assignment", func(t *testing.T) {
+			tests := []struct {
+				input    string
+				expected string
+			}{
+				{"x == 1", "(x == 1)"},
+				{"x(1)", "x(1)"},
+				{"x", "x"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.input, func(t *testing.T) {
+					program, errorCollector := parseSource("func main(x: int) {\n" + testCase.input + "\n}")
+
+					assert.False(t, errorCollector.HasErrors())
+					body := program.Statements[0].(*ast.FunctionStatement).Body
+					if assert.Len(t, body.Statements, 1) {
+						_, isExpression := body.Statements[0].(*ast.ExpressionStatement)
+						assert.True(t, isExpression, "expected an expression statement, got %T", body.Statements[0])
+						assert.Equal(t, testCase.expected, body.Statements[0].String())
+					}
+				})
+			}
+		})
+
+		t.Run("Elements can be assigned", func(t *testing.T) {
+			tests := []struct {
+				input    string
+				expected string
+			}{
+				{"xs[0] = 10", "xs[0] = 10"},
+				{"xs[i + 1] = xs[i] * 2", "xs[(i + 1)] = (xs[i] * 2)"},
+				{"grid[1][j] = 'x'", "grid[1][j] = 'x'"},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.input, func(t *testing.T) {
+					program, errorCollector := parseSource("func main() {\n" + testCase.input + "\nprint(1)\n}")
+
+					assert.False(t, errorCollector.HasErrors())
+					function := program.Statements[0].(*ast.FunctionStatement)
+					if !assert.Len(t, function.Body.Statements, 2) {
+						return
+					}
+					assignment, ok := function.Body.Statements[0].(*ast.IndexAssignmentStatement)
+					if assert.True(t, ok, "expected an element assignment, got %T", function.Body.Statements[0]) {
+						assert.Equal(t, testCase.expected, assignment.String())
+					}
+				})
+			}
+		})
+
+		t.Run("Only names and elements can be assigned", func(t *testing.T) {
+			tests := []struct {
+				input          string
+				expectedError  string
+				expectedColumn int
+			}{
+				{"1 = 2", "cannot assign to 1: only variables and elements can be assigned", 3},
+				{"f() = 1", "cannot assign to f(): only variables and elements can be assigned", 3},
+				{"(x) = 1", "cannot assign to x: only variables and elements can be assigned", 3},
+				{"xs[0] + 1 = 2", "cannot assign to (xs[0] + 1): only variables and elements can be assigned", 3},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.input, func(t *testing.T) {
+					program, errorCollector := parseSource("func main() {\n  " + testCase.input + "\n  print(1)\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						err := errorCollector.Errors()[0]
+						assert.Equal(t, testCase.expectedError, err.Message)
+						assert.Equal(t, 2, err.Line)
+						assert.Equal(t, testCase.expectedColumn, err.Column)
+					}
+
+					// The rest of the function is still parsed
+					function := program.Statements[0].(*ast.FunctionStatement)
+					assert.Len(t, function.Body.Statements, 1)
+				})
+			}
+		})
+	})
+
+	t.Run("Test for loops", func(t *testing.T) {
+		tests := []struct {
+			name              string
+			input             string
+			expected          string
+			expectedInclusive bool
+		}{
+			{"Exclusive range", "for i in 0..10 {\n  print(i)\n}", "for i in 0..10 {\n  print(i)\n}", false},
+			{"Inclusive range", "for i in 1..=n {\n}", "for i in 1..=n {\n}", true},
+			{"Expression bounds", "for i in start..n*2 {}", "for i in start..(n * 2) {\n}", false},
+			{"Call bounds", "for i in f(1)..=f(2) + 1 {}", "for i in f(1)..=(f(2) + 1) {\n}", true},
+			{"String iterable", "for ch in name {\n  print(ch)\n}", "for ch in name {\n  print(ch)\n}", false},
+			{"Nested loops", "for i in 0..2 {\n  for j in 0..i {\n    print(i, j)\n  }\n}",
+				"for i in 0..2 {\n  for j in 0..i {\n  print(i, j)\n}\n}", false},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				program, errorCollector := parseSource("func main() {\n" + testCase.input + "\nprint(0)\n}")
+
+				assert.False(t, errorCollector.HasErrors())
+				function := program.Statements[0].(*ast.FunctionStatement)
+				if !assert.Len(t, function.Body.Statements, 2) {
+					return
+				}
+				loop, ok := function.Body.Statements[0].(*ast.ForStatement)
+				if assert.True(t, ok, "expected a for loop, got %T", function.Body.Statements[0]) {
+					assert.Equal(t, testCase.expected, loop.String())
+					assert.Equal(t, testCase.expectedInclusive, loop.Inclusive)
+				}
+			})
+		}
+
+		t.Run("Malformed headers skip the loop body", func(t *testing.T) {
+			tests := []struct {
+				input          string
+				expectedError  string
+				expectedColumn int
+			}{
+				{"for 0..10 {", "expected IDENTIFIER, got INT_LITERAL", 7},
+				{"for i 0..10 {", "expected in, got INT_LITERAL", 9},
+				{"for i in 0 10 {", "expected '..', '..=' or '{' after the start of the range, got INT_LITERAL", 14},
+			}
+
+			for _, testCase := range tests {
+				t.Run(testCase.input, func(t *testing.T) {
+					program, errorCollector := parseSource("func main() {\n  " + testCase.input + "\n    print(1)\n  }\n  print(2)\n}")
+
+					if assert.Len(t, errorCollector.Errors(), 1) {
+						err := errorCollector.Errors()[0]
+						assert.Equal(t, testCase.expectedError, err.Message)
+						assert.Equal(t, 2, err.Line)
+						assert.Equal(t, testCase.expectedColumn, err.Column)
+					}
+				})
+			}
+		})
+	})
+
+	t.Run("Test chained indexing nests left to right", func(t *testing.T) {
+		program, errorCollector := parseSource("func main() {\nm[i][j + 1]\n}")
+
+		assert.False(t, errorCollector.HasErrors())
+		function := program.Statements[0].(*ast.FunctionStatement)
+		statement := function.Body.Statements[0].(*ast.ExpressionStatement)
+
+		outer, ok := statement.Expression.(*ast.IndexExpression)
+		if !assert.True(t, ok, "expected an index, got %T", statement.Expression) {
+			return
+		}
+		assert.Equal(t, "(j + 1)", outer.Index.String())
+
+		inner, ok := outer.Left.(*ast.IndexExpression)
+		if assert.True(t, ok, "expected the left side to be an index, got %T", outer.Left) {
+			assert.Equal(t, "m", inner.Left.String())
+			assert.Equal(t, "i", inner.Index.String())
+		}
+	})
+
+	t.Run("Test array literals", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Elements", "[1, 2, 3]", "[1, 2, 3]"},
+			{"Empty array", "[]", "[]"},
+			{"Single element", "[x]", "[x]"},
+			{"Trailing comma", "[1, 2,]", "[1, 2]"},
+			{"Expression elements", "[a + 1, f(2)]", "[(a + 1), f(2)]"},
+			{"Nested arrays", "[[1], [2, 3]]", "[[1], [2, 3]]"},
+			{"Array as a call argument", "f([1], 2)", "f([1], 2)"},
+			{"Indexing an array literal", "[1, 2][0] + 1", "([1, 2][0] + 1)"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				assert.Equal(t, testCase.expected, parseExpressionString(t, testCase.input))
+			})
+		}
+
+		t.Run("Array types are written with []", func(t *testing.T) {
+			program, errorCollector := parseSource("func f(xs: int[], grid: char[][]) -> string[] {\n  let ys: int[] = []\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			if assert.Len(t, program.Statements, 1) {
+				function := program.Statements[0].(*ast.FunctionStatement)
+				assert.Equal(t, "int[]", function.Parameters[0].Type.Value)
+				assert.Equal(t, "char[][]", function.Parameters[1].Type.Value)
+				assert.Equal(t, "string[]", function.ReturnType.Value)
+				assert.Equal(t, "let ys: int[] = []", function.Body.Statements[0].String())
+			}
+		})
+
+		t.Run("Elements must be separated by commas", func(t *testing.T) {
+			_, errorCollector := parseSource("func main() {\n  print([1 2])\n}")
+
+			if assert.True(t, errorCollector.HasErrors()) {
+				err := errorCollector.Errors()[0]
+				assert.Equal(t, "expected ], got INT_LITERAL", err.Message)
+				assert.Equal(t, 2, err.Line)
+				assert.Equal(t, 12, err.Column)
+			}
+		})
+	})
+
+	t.Run("Test adjacent string literals", func(t *testing.T) {
+		tests := []struct {
+			name     string
+			input    string
+			expected string
+		}{
+			{"Two literals", `"ab" "cd"`, "abcd"},
+			{"Three literals", `"a" "b" "c"`, "abc"},
+			{"Escapes are processed per literal", `"a\\" "n"`, "a\\n"},
+			{"Empty literal", `"" "x"`, "x"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				program, errorCollector := parseSource("func main() {\nprint(" + testCase.input + ")\n}")
+
+				assert.False(t, errorCollector.HasErrors())
+				function := program.Statements[0].(*ast.FunctionStatement)
+				statement := function.Body.Statements[0].(*ast.ExpressionStatement)
+				call := statement.Expression.(*ast.CallExpression)
+				if assert.Len(t, call.Arguments, 1, "Adjacent literals should be one argument") {
+					literal := call.Arguments[0].(*ast.StringLiteral)
+					assert.Equal(t, testCase.expected, literal.Value)
+				}
+			})
+		}
+	})
+
+	t.Run("Test missing statement separators", func(t *testing.T) {
+		t.Run("Two calls on one line", func(t *testing.T) {
+			program, errorCollector := parseSource("func main() {\nprint(a) print(b)\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				missing := errorCollector.Errors()[0]
+				assert.Equal(t, "missing ';' after expression", missing.Message)
+				assert.Equal(t, 2, missing.Line)
+				assert.Equal(t, 9, missing.Column, "Error should point just past print(a)")
+			}
+
+			// Recovery assumes the ';' was there, so the second call is still parsed
+			function := program.Statements[0].(*ast.FunctionStatement)
+			assert.Len(t, function.Body.Statements, 2)
+		})
+
+		t.Run("Let followed by another statement", func(t *testing.T) {
+			_, errorCollector := parseSource("func main() {\nlet x = 1 let y = 2\nprint(y)\n}")
+
+			if assert.Len(t, errorCollector.Errors(), 1) {
+				assert.Equal(t, "missing ';' after expression", errorCollector.Errors()[0].Message)
+				assert.Equal(t, 10, errorCollector.Errors()[0].Column)
+			}
+		})
+
+		t.Run("Separators are accepted", func(t *testing.T) {
+			inputs := []string{
+				"func main() {\nprint(a); print(b)\n}",
+				"func main() {\nprint(a)\nprint(b)\n}",
+				"func main() { print(a) }",
+			}
+
+			for _, input := range inputs {
+				_, errorCollector := parseSource(input)
+				assert.False(t, errorCollector.HasErrors(), "Parser should accept %q", input)
+			}
+		})
+	})
+
+	t.Run("Test items outside and inside functions", func(t *testing.T) {
+		tests := []struct {
+			name           string
+			input          string
+			expectedError  string
+			expectedLine   int
+			expectedColumn int
+		}{
+			{
+				name:           "Statement at the top level",
+				input:          "print(1)\n\nfunc main() {\n  print(2)\n}",
+				expectedError:  "statements must appear inside a function",
+				expectedLine:   1,
+				expectedColumn: 1,
+			},
+			{
+				name:           "Return at the top level",
+				input:          "func main() {\n}\nreturn 1\n",
+				expectedError:  "statements must appear inside a function",
+				expectedLine:   3,
+				expectedColumn: 1,
+			},
+			{
+				name:           "Nested function",
+				input:          "func main() {\n  func inner() {\n    print(1)\n  }\n  print(2)\n}",
+				expectedError:  "nested function definitions are not supported; move 'inner' to the top level",
+				expectedLine:   2,
+				expectedColumn: 3,
+			},
+			{
+				name:           "Stray closing brace",
+				input:          "func main() {\n  print(1)\n}\n}\n\nfunc other() {\n}",
+				expectedError:  "unexpected '}' outside any function",
+				expectedLine:   4,
+				expectedColumn: 1,
+			},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				program, errorCollector := parseSource(testCase.input)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					err := errorCollector.Errors()[0]
+					assert.Equal(t, testCase.expectedError, err.Message)
+					assert.Equal(t, testCase.expectedLine, err.Line)
+					assert.Equal(t, testCase.expectedColumn, err.Column)
+				}
+
+				// The offending item is skipped and every function around it is still parsed
+				for _, statement := range program.Statements {
+					function := statement.(*ast.FunctionStatement)
+					assert.NotEqual(t, "inner", function.Name.Value)
+				}
+			})
+		}
+
+		t.Run("Code after a nested function stays in the outer one", func(t *testing.T) {
+			program, _ := parseSource("func main() {\n  func inner() {\n  }\n  print(2)\n}\n\nfunc other() {\n}")
+
+			if assert.Len(t, program.Statements, 2) {
+				main := program.Statements[0].(*ast.FunctionStatement)
+				assert.Equal(t, "main", main.Name.Value)
+				assert.Len(t, main.Body.Statements, 1)
+				assert.Equal(t, "other", program.Statements[1].(*ast.FunctionStatement).Name.Value)
+			}
+		})
+
+		t.Run("Globals are allowed at the top level", func(t *testing.T) {
+			program, errorCollector := parseSource("let limit = 10\n\nfunc main() {\n}")
+
+			assert.False(t, errorCollector.HasErrors())
+			assert.Len(t, program.Globals, 1)
+		})
+
+		t.Run("A broken function is not reported line by line", func(t *testing.T) {
+			program, errorCollector := parseSource("func (x: int) {\n  print(x)\n}\n\nfunc other() {\n}")
+
+			assert.Len(t, errorCollector.Errors(), 1)
+			if assert.Len(t, program.Statements, 1) {
+				assert.Equal(t, "other", program.Statements[0].(*ast.FunctionStatement).Name.Value)
+			}
+		})
+	})
+
+	t.Run("Test invalid characters are reported once", func(t *testing.T) {
+		type position struct {
+			line   int
+			column int
+		}
+
+		tests := []struct {
+			name     string
+			input    string
+			expected []position
+		}{
+			{
+				name:     "Between statements, in arguments and as a value",
+				input:    "func main() {\n  let a = 1 # 2\n  print(a $ 1)\n  let b = €\n}",
+				expected: []position{{2, 13}, {3, 11}, {4, 11}},
+			},
+			{
+				name:     "At the top level",
+				input:    "~\nfunc main() {\n}\n$ $",
+				expected: []position{{1, 1}, {4, 1}, {4, 3}},
+			},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := parseSource(testCase.input)
+
+				if assert.Len(t, errorCollector.Errors(), len(testCase.expected)) {
+					for i, err := range errorCollector.Errors() {
+						assert.Equal(t, errors.LexicalError, err.Type)
+						assert.Contains(t, err.Message, "unexpected character")
+						assert.Equal(t, testCase.expected[i].line, err.Line)
+						assert.Equal(t, testCase.expected[i].column, err.Column)
+					}
+				}
+			})
+		}
+	})
+
+	t.Run("Test unclosed delimiters", func(t *testing.T) {
+		tests := []struct {
+			name           string
+			input          string
+			expectedError  string
+			expectedLine   int
+			expectedColumn int
+		}{
+			{
+				name:           "Function body without closing brace",
+				input:          "func main() {\nlet x = 1",
+				expectedError:  "unclosed '{' opened at line 1 in function main",
+				expectedLine:   1,
+				expectedColumn: 13,
+			},
+			{
+				name:           "Call without closing paren",
+				input:          "func main() {\nprint(1\n}",
+				expectedError:  "unclosed '(' opened at line 2 in function main",
+				expectedLine:   2,
+				expectedColumn: 6,
+			},
+			{
+				name:           "Empty call without closing paren",
+				input:          "func main() {\nprint(\n}",
+				expectedError:  "unclosed '(' opened at line 2 in function main",
+				expectedLine:   2,
+				expectedColumn: 6,
+			},
+			{
+				name:           "Group without closing paren",
+				input:          "func main() {\nprint((1 + 2)\n}",
+				expectedError:  "unclosed '(' opened at line 2 in function main",
+				expectedLine:   2,
+				expectedColumn: 6,
+			},
+			{
+				name:           "Array without closing bracket",
+				input:          "func main() {\nprint([1, 2\n}",
+				expectedError:  "unclosed '[' opened at line 2 in function main",
+				expectedLine:   2,
+				expectedColumn: 7,
+			},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, errorCollector := parseSource(testCase.input)
+
+				if assert.Len(t, errorCollector.Errors(), 1) {
+					unclosed := errorCollector.Errors()[0]
+					assert.Equal(t, testCase.expectedError, unclosed.Message)
+					assert.Equal(t, testCase.expectedLine, unclosed.Line)
+					assert.Equal(t, testCase.expectedColumn, unclosed.Column)
+				}
+			})
+		}
+	})
+
+	t.Run("Test expression nesting limit", func(t *testing.T) {
+		t.Run("Deeply nested calls report a clean error", func(t *testing.T) {
+			depth := 100000
+			input := "func main() {\n" +
+				strings.Repeat("f(", depth) + "1" + strings.Repeat(")", depth) +
+				"\n}"
+
+			_, errorCollector := parseSource(input)
+
+			assert.True(t, errorCollector.HasErrors(),
+				"Parser should reject pathologically nested expressions")
+			assert.Len(t, errorCollector.Errors(), 1,
+				"Nesting error should be reported exactly once")
+			assert.Contains(t, errorCollector.Errors()[0].Message, "too deeply nested")
+		})
+
+		t.Run("Moderate nesting is accepted", func(t *testing.T) {
+			depth := 100
+			input := "func main() {\n" +
+				strings.Repeat("f(", depth) + "1" + strings.Repeat(")", depth) +
+				"\n}"
+
+			program, errorCollector := parseSource(input)
+
+			assert.False(t, errorCollector.HasErrors(),
+				"Parser should accept reasonably nested expressions")
+			assert.Len(t, program.Statements, 1)
+		})
+
+		t.Run("Limit is configurable", func(t *testing.T) {
+			input := "func main() {\nf(f(f(f(1))))\n}"
+
+			errorCollector := errors.New(input, "test.navi")
+			lexerInstance := lexer.New(input, "test.navi", errorCollector)
+			parserInstance := New(lexerInstance, errorCollector)
+			parserInstance.SetMaxExpressionDepth(3)
+			parserInstance.ParseProgram()
+
+			assert.True(t, errorCollector.HasErrors(),
+				"Parser should honor a lowered nesting limit")
+		})
+	})
+}
@@ -11,12 +11,12 @@ const (
 	NILCOALESCE        // 3: ?? (future)
 	LOGICAL_OR         // 4: || (future)
 	LOGICAL_AND        // 5: && (future)
-	TYPE_OP            // 6: is, as (future)
+	TYPE_OP            // 6: as, is (future)
 	BITWISE_OR         // 7: | (future)
 	BITWISE_XOR        // 8: ^ (future)
 	BITWISE_AND        // 9: & (future)
-	EQUALITY           // 10: ==, != (future)
-	COMPARISON         // 11: <, >, <=, >= (future)
+	EQUALITY           // 10: ==, !=
+	COMPARISON         // 11: <, >, <=, >=
 	RANGE              // 12: .., ..= (future)
 	SHIFT              // 13: <<, >>, >>> (future)
 	SUM                // 14: +, -
@@ -26,44 +26,75 @@ const (
 	CALL               // 18: function(), [], ., ?., :: (highest)
 )
 
-// precedenceMap maps token types to their precedence level
-var precedenceMap = map[token.TokenType]int{
-	token.PLUS:     SUM,
-	token.MINUS:    SUM,
-	token.ASTERISK: PRODUCT,
-	token.SLASH:    PRODUCT,
+// Associativity decides how operators of equal precedence are grouped
+type Associativity int
+
+const (
+	LeftAssociative  Associativity = iota // a - b - c  →  (a - b) - c
+	RightAssociative                      // a ** b ** c →  a ** (b ** c)
+)
+
+// operatorRule describes how an infix token binds to its operands
+type operatorRule struct {
+	precedence    int
+	associativity Associativity
+}
+
+// operatorTable maps infix tokens to their binding rules
+// Every operator token listed here is parsed as a binary expression,
+// so adding a new binary operator only needs a new entry
+var operatorTable = map[token.TokenType]operatorRule{
+	token.PLUS:     {SUM, LeftAssociative},
+	token.MINUS:    {SUM, LeftAssociative},
+	token.ASTERISK: {PRODUCT, LeftAssociative},
+	token.SLASH:    {PRODUCT, LeftAssociative},
 
 	// Comparison operators
-	// token.LESS_THAN:          COMPARISON,
-	// token.GREATER_THAN:       COMPARISON,
-	// token.LESS_THAN_EQUAL:    COMPARISON,
-	// token.GREATER_THAN_EQUAL: COMPARISON,
+	token.LESS_THAN:          {COMPARISON, LeftAssociative},
+	token.GREATER_THAN:       {COMPARISON, LeftAssociative},
+	token.LESS_THAN_EQUAL:    {COMPARISON, LeftAssociative},
+	token.GREATER_THAN_EQUAL: {COMPARISON, LeftAssociative},
+	token.IN:                 {COMPARISON, LeftAssociative},
+
+	// Type ascription
+	token.AS: {TYPE_OP, LeftAssociative},
 
 	// Equality operators
-	// token.EQUAL:     EQUALITY,
-	// token.NOT_EQUAL: EQUALITY,
+	token.EQUAL:     {EQUALITY, LeftAssociative},
+	token.NOT_EQUAL: {EQUALITY, LeftAssociative},
 
 	// Logical operators
-	// token.LOGICAL_AND: LOGICAL_AND,
-	// token.LOGICAL_OR:  LOGICAL_OR,
+	// token.LOGICAL_AND: {LOGICAL_AND, LeftAssociative},
+	// token.LOGICAL_OR:  {LOGICAL_OR, LeftAssociative},
 
 	// Assignment operators
-	// token.ASSIGN:          ASSIGNMENT,
-	// token.COLON_ASSIGN:    ASSIGNMENT,
-	// token.PLUS_ASSIGN:     ASSIGNMENT,
-	// token.MINUS_ASSIGN:    ASSIGNMENT,
-	// token.ASTERISK_ASSIGN: ASSIGNMENT,
-	// token.SLASH_ASSIGN:    ASSIGNMENT,
+	// token.ASSIGN:          {ASSIGNMENT, RightAssociative},
+	// token.COLON_ASSIGN:    {ASSIGNMENT, RightAssociative},
+	// token.PLUS_ASSIGN:     {ASSIGNMENT, RightAssociative},
+	// token.MINUS_ASSIGN:    {ASSIGNMENT, RightAssociative},
+	// token.ASTERISK_ASSIGN: {ASSIGNMENT, RightAssociative},
+	// token.SLASH_ASSIGN:    {ASSIGNMENT, RightAssociative},
 
-	// Function call has highest precedence
-	token.LEFT_PAREN: CALL,
+	// Function call and indexing have highest precedence
+	token.LEFT_PAREN:   {CALL, LeftAssociative},
+	token.LEFT_BRACKET: {CALL, LeftAssociative},
 }
 
 // getPrecedence returns the precedence level for a given token type
 // Returns LOWEST for non-operator tokens
 func getPrecedence(tokenType token.TokenType) int {
-	if precedence, ok := precedenceMap[tokenType]; ok {
-		return precedence
+	if rule, ok := operatorTable[tokenType]; ok {
+		return rule.precedence
 	}
 	return LOWEST
 }
+
+// getRightBindingPrecedence returns the precedence used to parse the right operand
+// Right-associative operators bind one level looser so equal operators nest to the right
+func getRightBindingPrecedence(tokenType token.TokenType) int {
+	rule := operatorTable[tokenType]
+	if rule.associativity == RightAssociative {
+		return rule.precedence - 1
+	}
+	return rule.precedence
+}
@@ -0,0 +1,80 @@
+package prelude
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/parser"
+	"fmt"
+)
+
+const (
+	FILE_NAME = "prelude.navi"
+)
+
+// Source holds the helpers simpler to maintain as Naviary than as codegen builtins: square and midpoint
+// midpoint rounds toward a and cannot overflow the way (a + b) / 2 can
+// clamp and abs each need a conditional, so they wait until the language has if/else
+// Helpers over arrays, such as sum and join, are runtime builtins instead
+const Source = `func square(x: int) -> int {
+  return x * x
+}
+
+func midpoint(a: int, b: int) -> int {
+  return a + (b - a) / 2
+}
+`
+
+// Merge returns a copy of program with the prelude's functions appended
+// A user function sharing a name with a prelude function is reported on the user's declaration
+func Merge(program *ast.Program, errorCollector *errors.ErrorCollector) *ast.Program {
+	merged := &ast.Program{
+		Globals:    program.Globals,
+		Statements: append([]ast.Statement{}, program.Statements...),
+	}
+
+	userFunctions := make(map[string]*ast.FunctionStatement)
+	for _, statement := range program.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok {
+			userFunctions[function.Name.Value] = function
+		}
+	}
+
+	for _, function := range Functions() {
+		if userFunction, exists := userFunctions[function.Name.Value]; exists {
+			errorCollector.Add(
+				errors.TypeError,
+				userFunction.Name.Token.Line,
+				userFunction.Name.Token.Column,
+				len(userFunction.Name.Value),
+				"Function %s conflicts with prelude function",
+				function.Name.Value,
+			)
+			continue
+		}
+
+		merged.Statements = append(merged.Statements, function)
+	}
+
+	return merged
+}
+
+// Functions parses the prelude and returns its functions, each marked as coming from the prelude
+// The prelude ships with the compiler, so a syntax error in it is a compiler bug
+func Functions() []*ast.FunctionStatement {
+	errorCollector := errors.New(Source, FILE_NAME)
+	program := parser.New(lexer.New(Source, FILE_NAME, errorCollector), errorCollector).ParseProgram()
+	if errorCollector.HasErrors() {
+		panic(fmt.Sprintf("prelude failed to parse: %s", errorCollector.Errors()[0].Error()))
+	}
+
+	functions := []*ast.FunctionStatement{}
+	for _, statement := range program.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok {
+			function.Prelude = true
+			functions = append(functions, function)
+		}
+	}
+
+	return functions
+}
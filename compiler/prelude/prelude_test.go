@@ -0,0 +1,56 @@
+package prelude
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/nir"
+	"compiler/parser"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func parseSource(input string) (*ast.Program, *errors.ErrorCollector) {
+	errorCollector := errors.New(input, "test.navi")
+	program := parser.New(lexer.New(input, "test.navi", errorCollector), errorCollector).ParseProgram()
+
+	return program, errorCollector
+}
+
+func TestPrelude(t *testing.T) {
+	t.Run("Prelude functions are appended and marked", func(t *testing.T) {
+		program, errorCollector := parseSource("func main() {\nprint(square(3))\n}")
+
+		merged := Merge(program, errorCollector)
+
+		assert.False(t, errorCollector.HasErrors())
+		assert.Len(t, program.Statements, 1, "Merge should not modify the user program")
+		if assert.Len(t, merged.Statements, 1+len(Functions())) {
+			assert.False(t, merged.Statements[0].(*ast.FunctionStatement).Prelude)
+			for _, statement := range merged.Statements[1:] {
+				assert.True(t, statement.(*ast.FunctionStatement).Prelude)
+			}
+		}
+	})
+
+	t.Run("Redefining a prelude function is an error", func(t *testing.T) {
+		program, errorCollector := parseSource("func square(x: int) -> int {\nreturn x\n}\nfunc main() {\n}")
+
+		Merge(program, errorCollector)
+
+		if assert.Len(t, errorCollector.Errors(), 1) {
+			conflict := errorCollector.Errors()[0]
+			assert.Equal(t, "Function square conflicts with prelude function", conflict.Message)
+			assert.Equal(t, 1, conflict.Line)
+			assert.Equal(t, 6, conflict.Column)
+		}
+	})
+
+	t.Run("Prelude functions do not reuse builtin names", func(t *testing.T) {
+		for _, function := range Functions() {
+			assert.False(t, nir.IsBuiltin(function.Name.Value),
+				"Prelude function %s conflicts with builtin function", function.Name.Value)
+		}
+	})
+}
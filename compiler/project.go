@@ -0,0 +1,109 @@
+package main
+
+import (
+	"compiler/ast"
+	"compiler/project"
+	"compiler/timing"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// BuildProject compiles every source file under <directory>/src as one program
+// and writes the result into <directory>/target
+func BuildProject(directory string, options CompileOptions) (err error) {
+	timings := timing.New()
+	defer reportTimings(timings, options.Timings, &err)
+
+	manifest, err := project.LoadManifest(filepath.Join(directory, project.MANIFEST_FILE))
+	if err != nil {
+		return err
+	}
+
+	program, err := loadProjectProgram(directory, manifest, options, timings)
+	if err != nil {
+		return err
+	}
+
+	targetDirectory := filepath.Join(directory, project.TARGET_DIRECTORY)
+	if err := os.MkdirAll(targetDirectory, 0755); err != nil {
+		return fmt.Errorf("failed to create %s: %v", targetDirectory, err)
+	}
+	outputPath := filepath.Join(targetDirectory, manifest.Output+".ll")
+
+	// Lowering diagnostics carry no source positions yet, so the project name is enough context
+	errorCollector := options.newErrorCollector("", manifest.Name)
+
+	llvmIR, err := generateIR(program, errorCollector, outputPath, options, timings)
+	if err != nil {
+		return err
+	}
+
+	return writeOutput(outputPath, llvmIR, timings)
+}
+
+// loadProjectProgram parses every project source and merges them into a single program
+func loadProjectProgram(directory string, manifest *project.Manifest, options CompileOptions, timings *timing.Report) (*ast.Program, error) {
+	sources, err := project.DiscoverSources(directory)
+	if err != nil {
+		return nil, err
+	}
+
+	entryPath := filepath.Join(directory, project.SOURCE_DIRECTORY, manifest.Entry)
+	if _, err := os.Stat(entryPath); err != nil {
+		return nil, fmt.Errorf("entry file %s not found", entryPath)
+	}
+
+	program := &ast.Program{
+		Statements: []ast.Statement{},
+	}
+	definedIn := make(map[string]string)
+
+	for _, sourcePath := range sources {
+		sourceCode, err := os.ReadFile(sourcePath)
+		if err != nil {
+			return nil, fmt.Errorf("failed to read file %s: %v", sourcePath, err)
+		}
+
+		fmt.Printf("Compiling %s...\n", sourcePath)
+		fileProgram, _, err := parseSource(filepath.Base(sourcePath), string(sourceCode), options, timings)
+		if err != nil {
+			return nil, err
+		}
+
+		// Globals are shared by every file; duplicates are reported by the lowerer
+		program.Globals = append(program.Globals, fileProgram.Globals...)
+
+		for _, statement := range fileProgram.Statements {
+			if function, ok := statement.(*ast.FunctionStatement); ok {
+				name := function.Name.Value
+				if previous, exists := definedIn[name]; exists {
+					return nil, fmt.Errorf("function %s is defined in both %s and %s", name, previous, sourcePath)
+				}
+				definedIn[name] = sourcePath
+			}
+
+			program.Statements = append(program.Statements, statement)
+		}
+	}
+
+	mainPath, hasMain := definedIn["main"]
+	if !hasMain {
+		return nil, fmt.Errorf("entry file %s does not define main", entryPath)
+	}
+	if mainPath != entryPath {
+		return nil, fmt.Errorf("main is defined in %s, but the manifest entry is %s", mainPath, entryPath)
+	}
+
+	return program, nil
+}
+
+// NewProject scaffolds a project directory with a manifest and a hello world entry file
+func NewProject(name string) error {
+	if err := project.Create(name); err != nil {
+		return err
+	}
+
+	fmt.Printf("Created project %s\n", name)
+	return nil
+}
@@ -0,0 +1,141 @@
+package project
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+)
+
+const (
+	MANIFEST_FILE    = "naviary.toml"
+	SOURCE_DIRECTORY = "src"
+	TARGET_DIRECTORY = "target"
+)
+
+// Manifest describes a Naviary project
+// Example naviary.toml:
+//
+//	[package]
+//	name = "hello"
+//	entry = "main.navi"
+//	output = "hello"
+//	optimization = 0
+type Manifest struct {
+	Name         string
+	Entry        string // path of the file defining main, relative to src/
+	Output       string // file name of the build artifact inside target/
+	Optimization int
+}
+
+// LoadManifest reads and parses a naviary.toml file
+func LoadManifest(path string) (*Manifest, error) {
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read manifest %s: %v", path, err)
+	}
+
+	return ParseManifest(string(content))
+}
+
+// ParseManifest parses the subset of TOML used by naviary.toml:
+// a [package] table of string and integer keys
+func ParseManifest(content string) (*Manifest, error) {
+	manifest := &Manifest{
+		Entry: "main.navi",
+	}
+
+	section := ""
+	for index, rawLine := range strings.Split(content, "\n") {
+		lineNumber := index + 1
+		line := strings.TrimSpace(stripComment(rawLine))
+
+		if line == "" {
+			continue
+		}
+
+		if strings.HasPrefix(line, "[") {
+			if !strings.HasSuffix(line, "]") {
+				return nil, fmt.Errorf("line %d: malformed table header %q", lineNumber, line)
+			}
+			section = strings.TrimSpace(line[1 : len(line)-1])
+			if section != "package" {
+				return nil, fmt.Errorf("line %d: unknown table [%s]", lineNumber, section)
+			}
+			continue
+		}
+
+		key, rawValue, found := strings.Cut(line, "=")
+		if !found {
+			return nil, fmt.Errorf("line %d: expected key = value", lineNumber)
+		}
+		if section != "package" {
+			return nil, fmt.Errorf("line %d: keys must be inside the [package] table", lineNumber)
+		}
+
+		key = strings.TrimSpace(key)
+		rawValue = strings.TrimSpace(rawValue)
+
+		switch key {
+		case "name", "entry", "output":
+			value, err := parseString(rawValue)
+			if err != nil {
+				return nil, fmt.Errorf("line %d: %s: %v", lineNumber, key, err)
+			}
+			manifest.setString(key, value)
+		case "optimization":
+			value, err := strconv.Atoi(rawValue)
+			if err != nil || value < 0 || value > 3 {
+				return nil, fmt.Errorf("line %d: optimization must be an integer between 0 and 3", lineNumber)
+			}
+			manifest.Optimization = value
+		default:
+			return nil, fmt.Errorf("line %d: unknown key %q", lineNumber, key)
+		}
+	}
+
+	if manifest.Name == "" {
+		return nil, fmt.Errorf("manifest is missing the package name")
+	}
+	if manifest.Output == "" {
+		manifest.Output = manifest.Name
+	}
+
+	return manifest, nil
+}
+
+func (manifest *Manifest) setString(key string, value string) {
+	switch key {
+	case "name":
+		manifest.Name = value
+	case "entry":
+		manifest.Entry = value
+	case "output":
+		manifest.Output = value
+	}
+}
+
+func parseString(rawValue string) (string, error) {
+	if len(rawValue) < 2 || rawValue[0] != '"' || rawValue[len(rawValue)-1] != '"' {
+		return "", fmt.Errorf("expected a quoted string, got %s", rawValue)
+	}
+
+	return rawValue[1 : len(rawValue)-1], nil
+}
+
+// stripComment removes a trailing # comment that is not inside a string
+func stripComment(line string) string {
+	inString := false
+	for index, char := range line {
+		switch char {
+		case '"':
+			inString = !inString
+		case '#':
+			if !inString {
+				return line[:index]
+			}
+		}
+	}
+
+	return line
+}
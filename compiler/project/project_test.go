@@ -0,0 +1,91 @@
+package project
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestManifest(t *testing.T) {
+	t.Run("Full manifest", func(t *testing.T) {
+		manifest, err := ParseManifest(`# project settings
+[package]
+name = "hello"
+entry = "app.navi"   # file defining main
+output = "hello-bin"
+optimization = 2
+`)
+
+		assert.NoError(t, err)
+		assert.Equal(t, &Manifest{Name: "hello", Entry: "app.navi", Output: "hello-bin", Optimization: 2}, manifest)
+	})
+
+	t.Run("Defaults", func(t *testing.T) {
+		manifest, err := ParseManifest("[package]\nname = \"hello\"\n")
+
+		assert.NoError(t, err)
+		assert.Equal(t, "main.navi", manifest.Entry)
+		assert.Equal(t, "hello", manifest.Output)
+		assert.Equal(t, 0, manifest.Optimization)
+	})
+
+	t.Run("Invalid manifests", func(t *testing.T) {
+		tests := []struct {
+			name          string
+			input         string
+			expectedError string
+		}{
+			{"Missing name", "[package]\nentry = \"main.navi\"\n", "missing the package name"},
+			{"Unknown key", "[package]\nname = \"a\"\nauthor = \"b\"\n", "unknown key"},
+			{"Unknown table", "[dependencies]\n", "unknown table"},
+			{"Key outside table", "name = \"a\"\n", "inside the [package] table"},
+			{"Unquoted string", "[package]\nname = hello\n", "quoted string"},
+			{"Optimization out of range", "[package]\nname = \"a\"\noptimization = 4\n", "between 0 and 3"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.name, func(t *testing.T) {
+				_, err := ParseManifest(testCase.input)
+				assert.ErrorContains(t, err, testCase.expectedError)
+			})
+		}
+	})
+}
+
+func TestScaffold(t *testing.T) {
+	root := filepath.Join(t.TempDir(), "hello")
+
+	assert.NoError(t, Create(root))
+
+	manifest, err := LoadManifest(filepath.Join(root, MANIFEST_FILE))
+	assert.NoError(t, err)
+	assert.Equal(t, "hello", manifest.Name)
+
+	sources, err := DiscoverSources(root)
+	assert.NoError(t, err)
+	assert.Equal(t, []string{filepath.Join(root, SOURCE_DIRECTORY, "main.navi")}, sources)
+
+	assert.Error(t, Create(root), "creating over an existing directory should fail")
+}
+
+func TestDiscoverSources(t *testing.T) {
+	root := t.TempDir()
+	sourceDirectory := filepath.Join(root, SOURCE_DIRECTORY)
+	assert.NoError(t, os.MkdirAll(filepath.Join(sourceDirectory, "util"), 0755))
+
+	for _, name := range []string{"main.navi", "util/math.navi", "notes.txt"} {
+		assert.NoError(t, os.WriteFile(filepath.Join(sourceDirectory, name), []byte(""), 0644))
+	}
+
+	sources, err := DiscoverSources(root)
+	assert.NoError(t, err)
+	assert.Equal(t, []string{
+		filepath.Join(sourceDirectory, "main.navi"),
+		filepath.Join(sourceDirectory, "util", "math.navi"),
+	}, sources)
+
+	_, err = DiscoverSources(t.TempDir())
+	assert.Error(t, err, "a project without sources should be rejected")
+}
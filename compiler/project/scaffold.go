@@ -0,0 +1,40 @@
+package project
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+const mainTemplate = `func main() {
+    print("Hello, Naviary!")
+}
+`
+
+// Create scaffolds a new project directory:
+//
+//	<name>/
+//	  naviary.toml
+//	  src/main.navi
+func Create(name string) error {
+	if _, err := os.Stat(name); err == nil {
+		return fmt.Errorf("%s already exists", name)
+	}
+
+	if err := os.MkdirAll(filepath.Join(name, SOURCE_DIRECTORY), 0755); err != nil {
+		return fmt.Errorf("failed to create project directory: %v", err)
+	}
+
+	projectName := filepath.Base(name)
+	manifest := fmt.Sprintf("[package]\nname = %q\nentry = \"main.navi\"\noptimization = 0\n", projectName)
+
+	if err := os.WriteFile(filepath.Join(name, MANIFEST_FILE), []byte(manifest), 0644); err != nil {
+		return fmt.Errorf("failed to write manifest: %v", err)
+	}
+
+	if err := os.WriteFile(filepath.Join(name, SOURCE_DIRECTORY, "main.navi"), []byte(mainTemplate), 0644); err != nil {
+		return fmt.Errorf("failed to write main.navi: %v", err)
+	}
+
+	return nil
+}
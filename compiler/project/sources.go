@@ -0,0 +1,36 @@
+package project
+
+import (
+	"compiler/constants"
+	"fmt"
+	"io/fs"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// DiscoverSources returns every Naviary source file under root/src, sorted by path
+func DiscoverSources(root string) ([]string, error) {
+	sourceDirectory := filepath.Join(root, SOURCE_DIRECTORY)
+	sources := []string{}
+
+	err := filepath.WalkDir(sourceDirectory, func(path string, entry fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if !entry.IsDir() && strings.HasSuffix(path, constants.NAVIARY_EXTENSION) {
+			sources = append(sources, path)
+		}
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to discover sources in %s: %v", sourceDirectory, err)
+	}
+
+	if len(sources) == 0 {
+		return nil, fmt.Errorf("no %s files found in %s", constants.NAVIARY_EXTENSION, sourceDirectory)
+	}
+
+	sort.Strings(sources)
+	return sources, nil
+}
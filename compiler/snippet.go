@@ -0,0 +1,91 @@
+package main
+
+import (
+	"compiler/constants"
+	"compiler/errors"
+	"compiler/timing"
+	goerrors "errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+)
+
+const SNIPPET_NAME = "snippet"
+
+// RunResult is what a snippet printed and the exit code it finished with
+// The runtime prints to stderr, so Output holds both streams in order
+type RunResult struct {
+	Output   string
+	ExitCode int
+}
+
+// RunSnippet compiles source, links and runs it with its output captured,
+// and removes everything it built
+// Diagnostics are returned instead of displayed; the error is for toolchain failures
+func RunSnippet(source string) (RunResult, []errors.CompileError, error) {
+	return RunSnippetWithOptions(source, CompileOptions{})
+}
+
+// RunSnippetWithOptions is RunSnippet with explicit driver options
+// The snippet goes through the same parse, lower and codegen steps as a compiled file,
+// so options such as --no-prelude, --verify-types, --entry and --tail-calls apply to it too
+func RunSnippetWithOptions(source string, options CompileOptions) (RunResult, []errors.CompileError, error) {
+	fileName := SNIPPET_NAME + constants.NAVIARY_EXTENSION
+	errorCollector := errors.New(source, fileName)
+	timings := timing.New()
+
+	program := parseProgram(fileName, source, errorCollector, timings)
+	if errorCollector.HasErrors() {
+		return RunResult{}, errorCollector.Errors(), nil
+	}
+	if err := requireFunctions(program, fileName); err != nil {
+		return RunResult{}, nil, err
+	}
+	program, err := applyEntry(program, options)
+	if err != nil {
+		return RunResult{}, nil, err
+	}
+
+	_, nirModule, err := lowerModule(program, errorCollector, true, options, timings)
+	if errorCollector.HasErrors() {
+		return RunResult{}, errorCollector.Errors(), nil
+	}
+	if err != nil {
+		return RunResult{}, nil, err
+	}
+
+	buildDirectory, err := os.MkdirTemp("", "naviary-snippet-")
+	if err != nil {
+		return RunResult{}, nil, fmt.Errorf("failed to create snippet build directory: %v", err)
+	}
+	defer os.RemoveAll(buildDirectory)
+
+	llvmPath := filepath.Join(buildDirectory, SNIPPET_NAME+".ll")
+	binaryPath := filepath.Join(buildDirectory, SNIPPET_NAME)
+
+	llvmIR, err := generateModule(nirModule, llvmPath, options, timings)
+	if err != nil {
+		return RunResult{}, nil, err
+	}
+
+	if err := writeOutput(llvmPath, llvmIR, timings); err != nil {
+		return RunResult{}, nil, err
+	}
+
+	if err := linkProgram(llvmPath, binaryPath, options.linkOptions(), timings); err != nil {
+		return RunResult{}, nil, err
+	}
+
+	output, err := exec.Command(binaryPath).CombinedOutput()
+	result := RunResult{Output: string(output)}
+
+	var exitError *exec.ExitError
+	if goerrors.As(err, &exitError) {
+		result.ExitCode = exitError.ExitCode()
+	} else if err != nil {
+		return RunResult{}, nil, fmt.Errorf("failed to run snippet: %v", err)
+	}
+
+	return result, nil, nil
+}
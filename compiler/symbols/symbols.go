@@ -0,0 +1,101 @@
+package symbols
+
+import (
+	"compiler/nir"
+	"compiler/nir/instruction"
+	"encoding/json"
+	"fmt"
+	"io"
+	"strings"
+	"text/tabwriter"
+)
+
+// Output formats accepted by --report=symbols
+const (
+	FormatTable = "table"
+	FormatJSON  = "json"
+)
+
+// Symbol describes one function of the compiled program
+type Symbol struct {
+	Name         string   `json:"name"`
+	Parameters   []string `json:"parameters"`
+	ReturnType   string   `json:"return_type"`
+	Instructions int      `json:"instructions"`
+	Calls        []string `json:"calls"`
+}
+
+// Collect lists the functions of a module in definition order
+// instructionCounts maps function names to their LLVM instruction count after codegen
+func Collect(module *nir.Module, instructionCounts map[string]int) []Symbol {
+	symbols := make([]Symbol, 0, len(module.Functions))
+
+	for _, function := range module.Functions {
+		parameters := make([]string, 0, len(function.Parameters))
+		for _, parameter := range function.Parameters {
+			parameters = append(parameters, parameter.String())
+		}
+
+		symbols = append(symbols, Symbol{
+			Name:         function.Name,
+			Parameters:   parameters,
+			ReturnType:   function.ReturnType.String(),
+			Instructions: instructionCounts[function.Name],
+			Calls:        callees(function),
+		})
+	}
+
+	return symbols
+}
+
+// callees returns every function called by function, once each, in order of first call
+func callees(function *nir.Function) []string {
+	names := []string{}
+	seen := map[string]bool{}
+
+	for _, block := range function.BasicBlocks {
+		for _, inst := range block.Instructions {
+			call, ok := inst.(*instruction.CallInstruction)
+			if !ok || seen[call.GetFunctionName()] {
+				continue
+			}
+			seen[call.GetFunctionName()] = true
+			names = append(names, call.GetFunctionName())
+		}
+	}
+
+	return names
+}
+
+// Print writes a human readable table with one function per line
+func Print(writer io.Writer, symbols []Symbol) {
+	table := tabwriter.NewWriter(writer, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(table, "function\tparameters\treturns\tinstructions\tcalls")
+	for _, symbol := range symbols {
+		fmt.Fprintf(table, "%s\t(%s)\t%s\t%d\t%s\n",
+			symbol.Name,
+			strings.Join(symbol.Parameters, ", "),
+			symbol.ReturnType,
+			symbol.Instructions,
+			strings.Join(symbol.Calls, ", "))
+	}
+	table.Flush()
+}
+
+// Write renders the symbols in the given format
+func Write(writer io.Writer, symbols []Symbol, format string) error {
+	switch format {
+	case FormatTable:
+		Print(writer, symbols)
+		return nil
+	case FormatJSON:
+		encoded, err := json.Marshal(symbols)
+		if err != nil {
+			return fmt.Errorf("failed to encode symbols: %w", err)
+		}
+		fmt.Fprintln(writer, string(encoded))
+		return nil
+	default:
+		return fmt.Errorf("unknown symbols format: %s", format)
+	}
+}
@@ -0,0 +1,88 @@
+package symbols
+
+import (
+	"bytes"
+	"compiler/codegen/llvm"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/nir"
+	"compiler/parser"
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+// collectSource compiles source through codegen and returns its symbol report
+func collectSource(t *testing.T, source string) []Symbol {
+	errorCollector := errors.New(source, "test.navi")
+	lexerInstance := lexer.New(source, "test.navi", errorCollector)
+	program := parser.New(lexerInstance, errorCollector).ParseProgram()
+	nirModule := nir.NewLowerer(errorCollector).Lower(program)
+	assert.False(t, errorCollector.HasErrors(), "source should compile without errors")
+
+	generator := llvm.NewGenerator()
+	defer generator.Dispose()
+	_, err := generator.Generate(nirModule)
+	assert.NoError(t, err)
+
+	return Collect(nirModule, generator.InstructionCounts())
+}
+
+func TestSymbols(t *testing.T) {
+	source := "func double(x: int) -> int {\n  return x + x\n}\n\n" +
+		"func main() {\n  print(double(21))\n  print(double(1))\n}\n"
+
+	t.Run("JSON lists signatures, sizes and callees", func(t *testing.T) {
+		var buffer bytes.Buffer
+		assert.NoError(t, Write(&buffer, collectSource(t, source), FormatJSON))
+
+		var decoded []Symbol
+		assert.NoError(t, json.Unmarshal(buffer.Bytes(), &decoded))
+		assert.Len(t, decoded, 2)
+
+		double := decoded[0]
+		assert.Equal(t, "double", double.Name)
+		assert.Equal(t, []string{"x: int"}, double.Parameters)
+		assert.Equal(t, "int", double.ReturnType)
+		assert.Greater(t, double.Instructions, 0)
+		assert.Equal(t, []string{}, double.Calls)
+
+		main := decoded[1]
+		assert.Equal(t, "main", main.Name)
+		assert.Equal(t, []string{}, main.Parameters)
+		assert.Equal(t, "int", main.ReturnType)
+		assert.Greater(t, main.Instructions, 0)
+		assert.Equal(t, []string{"double", "print"}, main.Calls, "each callee is listed once, in call order")
+	})
+
+	t.Run("JSON uses snake case keys", func(t *testing.T) {
+		var buffer bytes.Buffer
+		assert.NoError(t, Write(&buffer, collectSource(t, source), FormatJSON))
+
+		var decoded []map[string]any
+		assert.NoError(t, json.Unmarshal(buffer.Bytes(), &decoded))
+		for _, key := range []string{"name", "parameters", "return_type", "instructions", "calls"} {
+			assert.Contains(t, decoded[0], key)
+		}
+	})
+
+	t.Run("Table has one line per function", func(t *testing.T) {
+		var buffer bytes.Buffer
+		assert.NoError(t, Write(&buffer, collectSource(t, source), FormatTable))
+
+		lines := strings.Split(strings.TrimSpace(buffer.String()), "\n")
+		assert.Len(t, lines, 3)
+		assert.True(t, strings.HasPrefix(lines[0], "function"))
+		assert.True(t, strings.HasPrefix(lines[1], "double"))
+		assert.Contains(t, lines[1], "(x: int)")
+		assert.True(t, strings.HasPrefix(lines[2], "main"))
+		assert.Contains(t, lines[2], "double, print")
+	})
+
+	t.Run("Unknown format is an error", func(t *testing.T) {
+		var buffer bytes.Buffer
+		assert.Error(t, Write(&buffer, nil, "xml"))
+	})
+}
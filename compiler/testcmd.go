@@ -0,0 +1,104 @@
+package main
+
+import (
+	"compiler/nir"
+	"compiler/testrunner"
+	"compiler/timing"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+)
+
+// RunTests compiles every test_ function in a file into its own executable,
+// runs them all, and reports the results
+// The file is lowered once, so its errors are reported once against its own source;
+// each test then only adds the main that calls it
+// A test fails when its process exits with a nonzero status
+func RunTests(inputPath string, options CompileOptions) (err error) {
+	timings := timing.New()
+	defer reportTimings(timings, options.Timings, &err)
+
+	sourceCode, err := readSource(inputPath)
+	if err != nil {
+		return err
+	}
+
+	fileName := filepath.Base(inputPath)
+	program, errorCollector, err := parseSource(fileName, sourceCode, options, timings)
+	if err != nil {
+		return err
+	}
+	if err := requireFunctions(program, inputPath); err != nil {
+		return err
+	}
+
+	tests := testrunner.Discover(program)
+	if len(tests) == 0 {
+		fmt.Printf("no %s functions found in %s\n", testrunner.TEST_PREFIX, fileName)
+		return nil
+	}
+
+	lowerer, nirModule, err := lowerProgram(program, errorCollector, false, options, timings)
+	if err != nil {
+		return err
+	}
+
+	buildDirectory, err := os.MkdirTemp("", "naviary-test-")
+	if err != nil {
+		return fmt.Errorf("failed to create test build directory: %v", err)
+	}
+	defer os.RemoveAll(buildDirectory)
+
+	fmt.Printf("running %d tests\n", len(tests))
+
+	failures := []string{}
+	for _, testName := range tests {
+		entryModule := lowerer.LowerEntry(nirModule, testrunner.EntryMain(program, testName))
+		output, err := runTest(entryModule, testName, buildDirectory, options, timings)
+		if err != nil {
+			fmt.Printf("test %s ... FAILED\n", testName)
+			failures = append(failures, fmt.Sprintf("---- %s ----\n%v\n%s", testName, err, output))
+			continue
+		}
+
+		fmt.Printf("test %s ... ok\n", testName)
+	}
+
+	if len(failures) > 0 {
+		fmt.Println()
+		fmt.Println("failures:")
+		fmt.Println(strings.Join(failures, "\n"))
+	}
+
+	fmt.Printf("\ntest result: %d passed; %d failed\n", len(tests)-len(failures), len(failures))
+
+	if len(failures) > 0 {
+		return fmt.Errorf("%d of %d tests failed", len(failures), len(tests))
+	}
+
+	return nil
+}
+
+// runTest builds and executes a single test from its lowered module, returning its combined output
+func runTest(entryModule *nir.Module, testName string, buildDirectory string, options CompileOptions, timings *timing.Report) (string, error) {
+	llvmPath := filepath.Join(buildDirectory, testName+".ll")
+	binaryPath := filepath.Join(buildDirectory, testName)
+
+	llvmIR, err := emitIR(entryModule, llvmPath, options, timings)
+	if err != nil {
+		return "", err
+	}
+
+	if err := writeOutput(llvmPath, llvmIR, timings); err != nil {
+		return "", err
+	}
+
+	if err := linkProgram(llvmPath, binaryPath, options.linkOptions(), timings); err != nil {
+		return "", err
+	}
+
+	output, err := exec.Command(binaryPath).CombinedOutput()
+	return string(output), err
+}
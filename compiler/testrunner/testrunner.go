@@ -0,0 +1,147 @@
+package testrunner
+
+import (
+	"compiler/ast"
+	"compiler/token"
+	"fmt"
+	"strings"
+)
+
+const (
+	TEST_PREFIX = "test_"
+)
+
+// Discover returns the names of all test functions in declaration order
+// A test function starts with "test_" and takes no parameters
+func Discover(program *ast.Program) []string {
+	tests := []string{}
+
+	for _, statement := range program.Statements {
+		function, ok := statement.(*ast.FunctionStatement)
+		if !ok {
+			continue
+		}
+
+		if strings.HasPrefix(function.Name.Value, TEST_PREFIX) && len(function.Parameters) == 0 {
+			tests = append(tests, function.Name.Value)
+		}
+	}
+
+	return tests
+}
+
+// EntryCandidates returns the functions that can be used as an entry point, in declaration order:
+// those that take no parameters and return nothing or an int
+func EntryCandidates(program *ast.Program) []string {
+	candidates := []string{}
+
+	for _, statement := range program.Statements {
+		function, ok := statement.(*ast.FunctionStatement)
+		if ok && len(function.Parameters) == 0 && returnsNothingOrInt(function) {
+			candidates = append(candidates, function.Name.Value)
+		}
+	}
+
+	return candidates
+}
+
+// CheckEntry reports why the named function cannot be used as an entry point, or nil if it can
+// Errors list the candidates so a typo is easy to fix
+func CheckEntry(program *ast.Program, name string) error {
+	function := findFunction(program, name)
+
+	switch {
+	case function == nil:
+		return fmt.Errorf("entry function %s does not exist; %s", name, describeCandidates(program))
+	case len(function.Parameters) > 0:
+		return fmt.Errorf("entry function %s must take no parameters but takes %d; %s",
+			name, len(function.Parameters), describeCandidates(program))
+	case !returnsNothingOrInt(function):
+		return fmt.Errorf("entry function %s must return nothing or int but returns %s; %s",
+			name, function.ReturnType.Value, describeCandidates(program))
+	}
+
+	return nil
+}
+
+func findFunction(program *ast.Program, name string) *ast.FunctionStatement {
+	for _, statement := range program.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok && function.Name.Value == name {
+			return function
+		}
+	}
+	return nil
+}
+
+func returnsNothingOrInt(function *ast.FunctionStatement) bool {
+	return function.ReturnType == nil || function.ReturnType.Value == "int"
+}
+
+func describeCandidates(program *ast.Program) string {
+	candidates := EntryCandidates(program)
+	if len(candidates) == 0 {
+		return "no function in this file can be an entry point"
+	}
+	return "candidates: " + strings.Join(candidates, ", ")
+}
+
+// WithEntry returns a copy of program whose main only calls the given function
+// Any user-defined main is dropped so each test runs in its own process
+// An int result of the entry becomes the exit status, as it would for main
+func WithEntry(program *ast.Program, testName string) *ast.Program {
+	if testName == "main" {
+		return program
+	}
+
+	entryProgram := &ast.Program{
+		Globals:    program.Globals,
+		Statements: []ast.Statement{},
+	}
+
+	for _, statement := range program.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok && function.Name.Value == "main" {
+			continue
+		}
+		entryProgram.Statements = append(entryProgram.Statements, statement)
+	}
+
+	entryProgram.Statements = append(entryProgram.Statements, EntryMain(program, testName))
+
+	return entryProgram
+}
+
+// EntryMain returns the main that runs the given function in place of the program's own
+func EntryMain(program *ast.Program, testName string) *ast.FunctionStatement {
+	entry := findFunction(program, testName)
+	returnsResult := entry != nil && entry.ReturnType != nil && entry.ReturnType.Value == "int"
+	return syntheticMain(testName, returnsResult)
+}
+
+// syntheticMain builds the AST for `func main() { <testName>() }`,
+// or `func main() { return <testName>() }` when the result is the exit status
+func syntheticMain(testName string, returnsResult bool) *ast.FunctionStatement {
+	callToken := token.Token{Type: token.IDENTIFIER, Value: testName}
+	call := &ast.CallExpression{
+		Token:     token.Token{Type: token.LEFT_PAREN, Value: "("},
+		Function:  &ast.Identifier{Token: callToken, Value: testName},
+		Arguments: []ast.Expression{},
+	}
+
+	var body ast.Statement = &ast.ExpressionStatement{Token: callToken, Expression: call}
+	if returnsResult {
+		body = &ast.ReturnStatement{Token: token.Token{Type: token.RETURN, Value: "return"}, ReturnValue: call}
+	}
+
+	return &ast.FunctionStatement{
+		Token: token.Token{Type: token.FUNC, Value: "func"},
+		Name: &ast.Identifier{
+			Token: token.Token{Type: token.IDENTIFIER, Value: "main"},
+			Value: "main",
+		},
+		Parameters: []*ast.FunctionParameter{},
+		Body: &ast.BlockStatement{
+			Token:      token.Token{Type: token.LEFT_BRACE, Value: "{"},
+			Statements: []ast.Statement{body},
+		},
+	}
+}
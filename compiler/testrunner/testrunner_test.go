@@ -0,0 +1,110 @@
+package testrunner
+
+import (
+	"compiler/ast"
+	"compiler/errors"
+	"compiler/lexer"
+	"compiler/parser"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func parseSource(input string) *ast.Program {
+	errorCollector := errors.New(input, "test.navi")
+	lexerInstance := lexer.New(input, "test.navi", errorCollector)
+
+	return parser.New(lexerInstance, errorCollector).ParseProgram()
+}
+
+const source = `func add_one(x: int) {
+  print(x + 1)
+}
+
+func test_first() {
+  add_one(1)
+}
+
+func test_with_parameter(x: int) {
+  print(x)
+}
+
+func main() {
+  print(0)
+}
+
+func test_second() {
+  add_one(2)
+}
+`
+
+func TestDiscover(t *testing.T) {
+	program := parseSource(source)
+
+	assert.Equal(t, []string{"test_first", "test_second"}, Discover(program))
+}
+
+func TestWithEntry(t *testing.T) {
+	program := parseSource(source)
+
+	entryProgram := WithEntry(program, "test_second")
+
+	mains := []*ast.FunctionStatement{}
+	for _, statement := range entryProgram.Statements {
+		if function, ok := statement.(*ast.FunctionStatement); ok && function.Name.Value == "main" {
+			mains = append(mains, function)
+		}
+	}
+
+	if assert.Len(t, mains, 1, "the user main must be replaced") {
+		assert.Equal(t, "func main() {\n  test_second()\n}", mains[0].String())
+	}
+	assert.Len(t, program.Statements, 5, "the original program must be left untouched")
+}
+
+func TestWithEntryResult(t *testing.T) {
+	program := parseSource("func answer() -> int {\n  return 42\n}\n")
+
+	entryProgram := WithEntry(program, "answer")
+	main := entryProgram.Statements[len(entryProgram.Statements)-1]
+
+	assert.Equal(t, "func main() {\n  return answer()\n}", main.String(), "an int result becomes the exit status")
+	assert.Same(t, program, WithEntry(program, "main"), "main needs no wrapper")
+}
+
+func TestCheckEntry(t *testing.T) {
+	program := parseSource(source + "\nfunc name() -> string {\n  return \"x\"\n}\n")
+	candidates := "candidates: test_first, main, test_second"
+
+	assert.Equal(t, []string{"test_first", "main", "test_second"}, EntryCandidates(program))
+
+	tests := []struct {
+		name          string
+		entry         string
+		expectedError string
+	}{
+		{"Function without parameters", "test_second", ""},
+		{"Main", "main", ""},
+		{"Unknown function", "test_third", "entry function test_third does not exist; " + candidates},
+		{"Function with parameters", "add_one", "entry function add_one must take no parameters but takes 1; " + candidates},
+		{"Function returning a string", "name", "entry function name must return nothing or int but returns string; " + candidates},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			err := CheckEntry(program, testCase.entry)
+
+			if testCase.expectedError == "" {
+				assert.NoError(t, err)
+			} else {
+				assert.EqualError(t, err, testCase.expectedError)
+			}
+		})
+	}
+
+	t.Run("No candidates", func(t *testing.T) {
+		err := CheckEntry(parseSource("func add(x: int) {\n  print(x)\n}\n"), "add")
+
+		assert.EqualError(t, err, "entry function add must take no parameters but takes 1; no function in this file can be an entry point")
+	})
+}
@@ -0,0 +1,123 @@
+package timing
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"time"
+)
+
+// Phase names recorded by the compile pipeline
+const (
+	PhaseParse   = "parse"
+	PhaseLower   = "lower"
+	PhaseCodegen = "codegen"
+	PhaseWrite   = "write"
+	PhaseLink    = "link"
+)
+
+// Phases lists every pipeline phase in execution order
+var Phases = []string{
+	PhaseParse,
+	PhaseLower,
+	PhaseCodegen,
+	PhaseWrite,
+	PhaseLink,
+}
+
+// Output formats accepted by --timings
+const (
+	FormatTable = "table"
+	FormatJSON  = "json"
+)
+
+type Entry struct {
+	Phase    string
+	Duration time.Duration
+}
+
+// Report collects the wall-clock duration of each compile phase
+type Report struct {
+	entries []Entry
+}
+
+func New() *Report {
+	return &Report{
+		entries: make([]Entry, 0),
+	}
+}
+
+// Start begins measuring a phase and returns a function that stops the measurement
+// Example:
+//
+//	done := report.Start(timing.PhaseParse)
+//	program := parser.ParseProgram()
+//	done()
+func (report *Report) Start(phase string) func() {
+	start := time.Now()
+
+	return func() {
+		report.entries = append(report.entries, Entry{
+			Phase:    phase,
+			Duration: time.Since(start),
+		})
+	}
+}
+
+func (report *Report) Entries() []Entry {
+	return report.entries
+}
+
+func (report *Report) Total() time.Duration {
+	var total time.Duration
+	for _, entry := range report.entries {
+		total += entry.Duration
+	}
+	return total
+}
+
+// Print writes a human readable table of all recorded phases
+func (report *Report) Print(writer io.Writer) {
+	fmt.Fprintf(writer, "%-10s %12s\n", "phase", "time")
+	for _, entry := range report.entries {
+		fmt.Fprintf(writer, "%-10s %12s\n", entry.Phase, formatMilliseconds(entry.Duration))
+	}
+	fmt.Fprintf(writer, "%-10s %12s\n", "total", formatMilliseconds(report.Total()))
+}
+
+// JSON encodes the recorded phases as {"<phase>": milliseconds, ..., "total": milliseconds}
+func (report *Report) JSON() ([]byte, error) {
+	milliseconds := make(map[string]float64, len(report.entries)+1)
+	for _, entry := range report.entries {
+		milliseconds[entry.Phase] += toMilliseconds(entry.Duration)
+	}
+	milliseconds["total"] = toMilliseconds(report.Total())
+
+	return json.Marshal(milliseconds)
+}
+
+// Write renders the report in the given format
+func (report *Report) Write(writer io.Writer, format string) error {
+	switch format {
+	case FormatTable:
+		report.Print(writer)
+		return nil
+	case FormatJSON:
+		encoded, err := report.JSON()
+		if err != nil {
+			return fmt.Errorf("failed to encode timings: %w", err)
+		}
+		fmt.Fprintln(writer, string(encoded))
+		return nil
+	default:
+		return fmt.Errorf("unknown timings format: %s", format)
+	}
+}
+
+func toMilliseconds(duration time.Duration) float64 {
+	return float64(duration.Microseconds()) / 1000
+}
+
+func formatMilliseconds(duration time.Duration) string {
+	return fmt.Sprintf("%.3fms", toMilliseconds(duration))
+}
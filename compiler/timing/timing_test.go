@@ -0,0 +1,56 @@
+package timing
+
+import (
+	"bytes"
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestReport(t *testing.T) {
+	t.Run("JSON contains every phase and the total", func(t *testing.T) {
+		report := New()
+		for _, phase := range Phases {
+			done := report.Start(phase)
+			done()
+		}
+
+		encoded, err := report.JSON()
+		assert.NoError(t, err)
+
+		var decoded map[string]float64
+		assert.NoError(t, json.Unmarshal(encoded, &decoded))
+
+		for _, phase := range Phases {
+			assert.Contains(t, decoded, phase, "missing phase %s", phase)
+		}
+		assert.Contains(t, decoded, "total")
+	})
+
+	t.Run("Table lists phases in order", func(t *testing.T) {
+		report := New()
+		for _, phase := range Phases {
+			done := report.Start(phase)
+			done()
+		}
+
+		var buffer bytes.Buffer
+		assert.NoError(t, report.Write(&buffer, FormatTable))
+
+		output := buffer.String()
+		previous := -1
+		for _, phase := range Phases {
+			index := strings.Index(output, phase)
+			assert.Greater(t, index, previous, "phase %s out of order", phase)
+			previous = index
+		}
+		assert.Contains(t, output, "total")
+	})
+
+	t.Run("Unknown format is rejected", func(t *testing.T) {
+		var buffer bytes.Buffer
+		assert.Error(t, New().Write(&buffer, "xml"))
+	})
+}
@@ -8,6 +8,7 @@ var tokenMap = [...]string{
 	// Literals
 	INT_LITERAL:    "INT_LITERAL",
 	STRING_LITERAL: "STRING_LITERAL",
+	CHAR_LITERAL:   "CHAR_LITERAL",
 
 	// Identifier
 	IDENTIFIER: "IDENTIFIER",
@@ -19,31 +20,49 @@ var tokenMap = [...]string{
 	RETURN: "return",
 	CLASS:  "class",
 	THIS:   "this",
+	TRUE:   "true",
+	FALSE:  "false",
+	IN:     "in",
+	AS:     "as",
+	FOR:    "for",
 
 	// Type keywords
 	INT:    "int",
 	FLOAT:  "float",
 	STRING: "string",
 	BOOL:   "bool",
+	CHAR:   "char",
 
 	// Operators
-	PLUS:         "+",
-	MINUS:        "-",
-	ASTERISK:     "*",
-	SLASH:        "/",
-	ASSIGN:       "=",
-	COLON_ASSIGN: ":=",
-	DOT:          ".",
+	PLUS:          "+",
+	MINUS:         "-",
+	ASTERISK:      "*",
+	SLASH:         "/",
+	ASSIGN:        "=",
+	COLON_ASSIGN:  ":=",
+	DOT:           ".",
+	DOT_DOT:       "..",
+	DOT_DOT_EQUAL: "..=",
+
+	EQUAL:              "==",
+	NOT_EQUAL:          "!=",
+	LESS_THAN:          "<",
+	GREATER_THAN:       ">",
+	LESS_THAN_EQUAL:    "<=",
+	GREATER_THAN_EQUAL: ">=",
 
 	// Delimiters
-	LEFT_PAREN:  "(",
-	RIGHT_PAREN: ")",
-	LEFT_BRACE:  "{",
-	RIGHT_BRACE: "}",
-	COMMA:       ",",
-	SEMICOLON:   ";",
-	COLON:       ":",
-	ARROW:       "->",
+	LEFT_PAREN:    "(",
+	RIGHT_PAREN:   ")",
+	LEFT_BRACE:    "{",
+	RIGHT_BRACE:   "}",
+	LEFT_BRACKET:  "[",
+	RIGHT_BRACKET: "]",
+	COMMA:         ",",
+	SEMICOLON:     ";",
+	COLON:         ":",
+	ARROW:         "->",
+	AT:            "@",
 
 	NEW_LINE: "\\n",
 }
@@ -6,6 +6,10 @@ type Token struct {
 	Value  string
 	Line   int
 	Column int
+	// Offset and End are the byte range of the token in the source, End exclusive
+	// For literals with escapes the range is longer than Value
+	Offset int
+	End    int
 }
 
 func New(tokenType TokenType, value string, line int, column int) Token {
@@ -25,11 +29,17 @@ var keywords = map[string]TokenType{
 	"mut":    MUT,
 	"class":  CLASS,
 	"this":   THIS,
+	"true":   TRUE,
+	"false":  FALSE,
+	"in":     IN,
+	"as":     AS,
+	"for":    FOR,
 
 	"int":    INT,
 	"float":  FLOAT,
 	"string": STRING,
 	"bool":   BOOL,
+	"char":   CHAR,
 }
 
 // LookupIdentifier checks if an identifier is a keyword
@@ -13,6 +13,7 @@ const (
 	// Literals
 	INT_LITERAL    // 123
 	STRING_LITERAL // "hello"
+	CHAR_LITERAL   // 'a'
 	IDENTIFIER     // variable names
 
 	// Keywords
@@ -22,34 +23,52 @@ const (
 	FUNC   // func
 	CLASS  // class
 	THIS   // this
+	TRUE   // true
+	FALSE  // false
+	IN     // in
+	AS     // as
+	FOR    // for
 
 	// Type keywords
 	INT    // int
 	FLOAT  // float
 	STRING // string
 	BOOL   // bool
+	CHAR   // char
 
 	operatorBegin
 	// Operators
-	PLUS         // +
-	MINUS        // -
-	ASTERISK     // *
-	SLASH        // /
-	ASSIGN       // =
-	COLON_ASSIGN // :=
-	DOT          // .
+	PLUS          // +
+	MINUS         // -
+	ASTERISK      // *
+	SLASH         // /
+	ASSIGN        // =
+	COLON_ASSIGN  // :=
+	DOT           // .
+	DOT_DOT       // ..
+	DOT_DOT_EQUAL // ..=
+
+	EQUAL              // ==
+	NOT_EQUAL          // !=
+	LESS_THAN          // <
+	GREATER_THAN       // >
+	LESS_THAN_EQUAL    // <=
+	GREATER_THAN_EQUAL // >=
 
 	operatorEnd
 
 	// Delimiters
-	LEFT_PAREN  // (
-	RIGHT_PAREN // )
-	LEFT_BRACE  // {
-	RIGHT_BRACE // }
-	COMMA       // ,
-	SEMICOLON   // ;
-	COLON       // :
-	ARROW       // ->
+	LEFT_PAREN    // (
+	RIGHT_PAREN   // )
+	LEFT_BRACE    // {
+	RIGHT_BRACE   // }
+	LEFT_BRACKET  // [
+	RIGHT_BRACKET // ]
+	COMMA         // ,
+	SEMICOLON     // ;
+	COLON         // :
+	ARROW         // ->
+	AT            // @
 
 	NEW_LINE // \n
 )
@@ -0,0 +1,192 @@
+package toolchain
+
+import (
+	"compiler/constants"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"os/signal"
+	"runtime"
+	"strings"
+	"sync"
+)
+
+// DEFAULT_C_COMPILERS are tried in order when neither --cc nor CC names a compiler
+// The compiler must understand LLVM IR, so cc comes last: it is clang on macOS but often gcc elsewhere
+var DEFAULT_C_COMPILERS = []string{"clang", "cc"}
+
+// compilerChoice remembers the compiler found for one --cc and CC combination
+var compilerChoice struct {
+	sync.Mutex
+	key  string
+	path string
+}
+
+// RuntimeLibrary returns the path of the runtime archive generated programs link against
+func RuntimeLibrary() string {
+	if path := os.Getenv("NAVIARY_RUNTIME"); path != "" {
+		return path
+	}
+
+	return constants.DEFAULT_RUNTIME_LIBRARY
+}
+
+// SharedRuntimeLibrary returns the path of the runtime built as a shared library for C hosts
+// It sits next to the archive; override with the NAVIARY_RUNTIME_SHARED environment variable
+func SharedRuntimeLibrary() string {
+	if path := os.Getenv("NAVIARY_RUNTIME_SHARED"); path != "" {
+		return path
+	}
+
+	extension := ".so"
+	if runtime.GOOS == "darwin" {
+		extension = ".dylib"
+	}
+	return strings.TrimSuffix(RuntimeLibrary(), ".a") + extension
+}
+
+// LinkOptions controls how generated IR is compiled and linked
+type LinkOptions struct {
+	NoPIE    bool   // emit position-dependent code and a non-PIE executable
+	CC       string // compiler to link with, from --cc; empty means search for one
+	Optimize bool   // compile the IR with -O2 instead of clang's default -O0
+}
+
+// FindCompiler returns the C compiler used to compile IR and link executables
+// override (the --cc flag) wins, then the CC environment variable, then DEFAULT_C_COMPILERS
+// The choice is cached, so the search runs once per process
+func FindCompiler(override string) (string, error) {
+	environment := os.Getenv("CC")
+	key := override + "\x00" + environment
+
+	compilerChoice.Lock()
+	defer compilerChoice.Unlock()
+	if compilerChoice.path != "" && compilerChoice.key == key {
+		return compilerChoice.path, nil
+	}
+
+	path, err := findCompiler(override, environment)
+	if err != nil {
+		return "", err
+	}
+
+	compilerChoice.key = key
+	compilerChoice.path = path
+	return path, nil
+}
+
+// findCompiler probes each candidate on PATH and reports every one it tried if none exists
+func findCompiler(override string, environment string) (string, error) {
+	candidates := []string{}
+	tried := []string{}
+
+	if override != "" {
+		candidates = append(candidates, override)
+		tried = append(tried, "--cc="+override)
+	}
+	if environment != "" {
+		candidates = append(candidates, environment)
+		tried = append(tried, "CC="+environment)
+	}
+	candidates = append(candidates, DEFAULT_C_COMPILERS...)
+	tried = append(tried, DEFAULT_C_COMPILERS...)
+
+	for _, candidate := range candidates {
+		if path, err := exec.LookPath(candidate); err == nil {
+			return path, nil
+		}
+	}
+
+	return "", fmt.Errorf("no C compiler found to link with (tried %s)\n"+
+		"Install clang (for example `apt install clang` or `xcode-select --install`), "+
+		"or name a compiler with --cc=<path> or the CC environment variable",
+		strings.Join(tried, ", "))
+}
+
+// Link compiles an LLVM IR file and links it with the runtime into an executable
+func Link(llvmPath string, outputPath string, options LinkOptions) error {
+	flags, err := relocationFlags(runtime.GOOS, options)
+	if err != nil {
+		return err
+	}
+
+	compiler, err := FindCompiler(options.CC)
+	if err != nil {
+		return err
+	}
+
+	arguments := append(flags, optimizationFlags(options)...)
+	arguments = append(arguments, llvmPath, RuntimeLibrary(), "-o", outputPath)
+	command := exec.Command(compiler, arguments...)
+
+	output, err := command.CombinedOutput()
+	if err != nil {
+		return fmt.Errorf("failed to link %s: %v\n%s", llvmPath, err, strings.TrimSpace(string(output)))
+	}
+
+	return nil
+}
+
+// optimizationFlags returns -O2 for an optimized build and nothing otherwise
+// Calls marked tail are only a hint to LLVM; at clang's default -O0 each one still takes
+// a new stack frame, so --tail-calls needs the optimized build to run in constant stack
+func optimizationFlags(options LinkOptions) []string {
+	if options.Optimize {
+		return []string{"-O2"}
+	}
+	return []string{}
+}
+
+// relocationFlags returns the clang flags that keep code generation and linking consistent
+// Linux toolchains disagree on whether PIE is the default, so it is always spelled out.
+// macOS only produces PIE executables and warns about either spelling, so it gets none
+func relocationFlags(goos string, options LinkOptions) ([]string, error) {
+	if goos == "darwin" {
+		if options.NoPIE {
+			return nil, fmt.Errorf("--no-pie is not supported on macOS, where executables are always position independent")
+		}
+		return []string{}, nil
+	}
+
+	if options.NoPIE {
+		return []string{"-fno-pie", "-no-pie"}, nil
+	}
+	return []string{"-fPIE", "-pie"}, nil
+}
+
+// Run executes a program attached to the driver's stdin, stdout, and stderr
+// so output appears as it is produced, and returns the program's exit code
+// Interrupts received by the driver are forwarded to the program
+func Run(binaryPath string, args ...string) (int, error) {
+	command := exec.Command(binaryPath, args...)
+	command.Stdin = os.Stdin
+	command.Stdout = os.Stdout
+	command.Stderr = os.Stderr
+
+	if err := command.Start(); err != nil {
+		return -1, fmt.Errorf("failed to start %s: %v", binaryPath, err)
+	}
+
+	interrupts := make(chan os.Signal, 1)
+	signal.Notify(interrupts, os.Interrupt)
+	go func() {
+		for interrupt := range interrupts {
+			command.Process.Signal(interrupt)
+		}
+	}()
+
+	err := command.Wait()
+	signal.Stop(interrupts)
+	close(interrupts)
+
+	var exitError *exec.ExitError
+	if errors.As(err, &exitError) {
+		return exitError.ExitCode(), nil
+	}
+	if err != nil {
+		return -1, fmt.Errorf("failed to run %s: %v", binaryPath, err)
+	}
+
+	return 0, nil
+}
@@ -0,0 +1,170 @@
+package toolchain
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestRun(t *testing.T) {
+	shell, err := exec.LookPath("sh")
+	if err != nil {
+		t.Skip("sh is not available")
+	}
+
+	t.Run("Exit status is propagated", func(t *testing.T) {
+		exitCode, err := Run(shell, "-c", "exit 3")
+
+		assert.NoError(t, err)
+		assert.Equal(t, 3, exitCode)
+	})
+
+	t.Run("Successful programs exit with zero", func(t *testing.T) {
+		exitCode, err := Run(shell, "-c", "true")
+
+		assert.NoError(t, err)
+		assert.Equal(t, 0, exitCode)
+	})
+
+	t.Run("Missing programs are reported", func(t *testing.T) {
+		_, err := Run("/nonexistent/naviary-program")
+
+		assert.Error(t, err)
+	})
+}
+
+func TestRelocationFlags(t *testing.T) {
+	tests := []struct {
+		name     string
+		goos     string
+		options  LinkOptions
+		expected []string
+	}{
+		{"Linux defaults to PIE", "linux", LinkOptions{}, []string{"-fPIE", "-pie"}},
+		{"Linux without PIE", "linux", LinkOptions{NoPIE: true}, []string{"-fno-pie", "-no-pie"}},
+		{"macOS needs no flags", "darwin", LinkOptions{}, []string{}},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			flags, err := relocationFlags(testCase.goos, testCase.options)
+
+			assert.NoError(t, err)
+			assert.Equal(t, testCase.expected, flags)
+		})
+	}
+
+	t.Run("macOS rejects --no-pie", func(t *testing.T) {
+		_, err := relocationFlags("darwin", LinkOptions{NoPIE: true})
+
+		assert.Error(t, err)
+	})
+}
+
+func TestOptimizationFlags(t *testing.T) {
+	assert.Equal(t, []string{}, optimizationFlags(LinkOptions{}))
+	assert.Equal(t, []string{"-O2"}, optimizationFlags(LinkOptions{Optimize: true}))
+}
+
+// fakeCompilers creates executables with the given names in a new directory
+// and makes that directory the whole PATH
+func fakeCompilers(t *testing.T, names ...string) string {
+	directory := t.TempDir()
+	for _, name := range names {
+		path := filepath.Join(directory, name)
+		assert.NoError(t, os.WriteFile(path, []byte("#!/bin/sh\n"), 0755))
+	}
+	t.Setenv("PATH", directory)
+
+	return directory
+}
+
+func TestFindCompiler(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("fake compilers are shell scripts")
+	}
+
+	tests := []struct {
+		name        string
+		installed   []string
+		override    string
+		environment string
+		expected    string
+	}{
+		{"Clang is preferred", []string{"clang", "cc"}, "", "", "clang"},
+		{"Falls back to cc", []string{"cc"}, "", "", "cc"},
+		{"CC beats the defaults", []string{"clang", "cc", "my-cc"}, "", "my-cc", "my-cc"},
+		{"Missing CC falls through", []string{"cc"}, "", "my-cc", "cc"},
+		{"Flag beats CC", []string{"clang", "my-cc", "clang-18"}, "clang-18", "my-cc", "clang-18"},
+	}
+
+	for _, testCase := range tests {
+		t.Run(testCase.name, func(t *testing.T) {
+			directory := fakeCompilers(t, testCase.installed...)
+
+			path, err := findCompiler(testCase.override, testCase.environment)
+
+			assert.NoError(t, err)
+			assert.Equal(t, filepath.Join(directory, testCase.expected), path)
+		})
+	}
+
+	t.Run("Nothing found lists what was tried", func(t *testing.T) {
+		fakeCompilers(t)
+
+		_, err := findCompiler("clang-18", "my-cc")
+
+		if assert.Error(t, err) {
+			assert.Contains(t, err.Error(), "tried --cc=clang-18, CC=my-cc, clang, cc")
+			assert.Contains(t, err.Error(), "--cc=<path> or the CC environment variable")
+		}
+	})
+
+	t.Run("Choice is cached", func(t *testing.T) {
+		directory := fakeCompilers(t, "clang")
+		t.Setenv("CC", "")
+
+		first, err := FindCompiler("")
+		assert.NoError(t, err)
+		assert.Equal(t, filepath.Join(directory, "clang"), first)
+
+		assert.NoError(t, os.Remove(first))
+		second, err := FindCompiler("")
+		assert.NoError(t, err)
+		assert.Equal(t, first, second, "the search should not run again")
+	})
+}
+
+func TestEmbedExample(t *testing.T) {
+	compiler, err := FindCompiler("")
+	if err != nil {
+		t.Skip(err)
+	}
+	library, err := filepath.Abs(SharedRuntimeLibrary())
+	assert.NoError(t, err)
+	if _, err := os.Stat(library); err != nil {
+		t.Skipf("shared runtime library %s is not built", library)
+	}
+
+	repository := filepath.Join("..", "..")
+	hostPath := filepath.Join(t.TempDir(), "embed_host")
+	build := exec.Command(compiler,
+		filepath.Join(repository, "examples", "embed", "host.c"),
+		"-I"+filepath.Join(repository, "runtime", "include"),
+		library,
+		"-Wl,-rpath,"+filepath.Dir(library),
+		"-o", hostPath,
+	)
+	buildOutput, err := build.CombinedOutput()
+	if !assert.NoError(t, err, "building the C host failed:\n%s", buildOutput) {
+		return
+	}
+
+	output, err := exec.Command(hostPath).CombinedOutput()
+	assert.NoError(t, err, "the C host failed:\n%s", output)
+	assert.Equal(t, "ababab\n6\nff 42 z\n", string(output))
+}
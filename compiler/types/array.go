@@ -0,0 +1,33 @@
+package types
+
+import "strings"
+
+// ArrayType is a sequence of values of one element type, written int[]
+type ArrayType struct {
+	Element Type
+}
+
+func (array *ArrayType) String() string {
+	return array.Element.String() + "[]"
+}
+
+func (array *ArrayType) Equals(other Type) bool {
+	if otherArray, ok := other.(*ArrayType); ok {
+		return array.Element.Equals(otherArray.Element)
+	}
+	return false
+}
+
+// GetType resolves a type name as written in source, such as int or string[][]
+// Returns nil when the name, or the element type of an array, is not known
+func GetType(name string) Type {
+	if element, isArray := strings.CutSuffix(name, "[]"); isArray {
+		elementType := GetType(element)
+		if elementType == nil {
+			return nil
+		}
+		return &ArrayType{Element: elementType}
+	}
+
+	return GetPrimitiveType(name)
+}
@@ -20,6 +20,7 @@ var (
 	Float  = &PrimitiveType{Name: "float"}
 	String = &PrimitiveType{Name: "string"}
 	Bool   = &PrimitiveType{Name: "bool"}
+	Char   = &PrimitiveType{Name: "char"}
 )
 
 func GetPrimitiveType(name string) Type {
@@ -32,6 +33,8 @@ func GetPrimitiveType(name string) Type {
 		return String
 	case "bool":
 		return Bool
+	case "char":
+		return Char
 	default:
 		return nil
 	}
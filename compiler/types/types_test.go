@@ -0,0 +1,53 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestTypeNames(t *testing.T) {
+	t.Run("Types render as they are written in source", func(t *testing.T) {
+		tests := []struct {
+			naviaryType Type
+			expected    string
+		}{
+			{Int, "int"},
+			{Float, "float"},
+			{String, "string"},
+			{Bool, "bool"},
+			{Char, "char"},
+			{Nil, "nil"},
+			{&FunctionType{ParameterTypes: []Type{}, ReturnType: Nil}, "func() -> nil"},
+			{&FunctionType{ParameterTypes: []Type{Int, Bool}, ReturnType: String}, "func(int, bool) -> string"},
+			{&ArrayType{Element: Int}, "int[]"},
+			{&ArrayType{Element: &ArrayType{Element: Char}}, "char[][]"},
+		}
+
+		for _, testCase := range tests {
+			t.Run(testCase.expected, func(t *testing.T) {
+				assert.Equal(t, testCase.expected, testCase.naviaryType.String())
+			})
+		}
+	})
+
+	t.Run("Primitive names round trip", func(t *testing.T) {
+		for _, name := range []string{"int", "float", "string", "bool", "char"} {
+			primitiveType := GetPrimitiveType(name)
+			if assert.NotNil(t, primitiveType, "%s should be a primitive type", name) {
+				assert.Equal(t, name, primitiveType.String())
+			}
+		}
+	})
+
+	t.Run("Array names round trip", func(t *testing.T) {
+		for _, name := range []string{"int[]", "string[][]", "char[]"} {
+			arrayType := GetType(name)
+			if assert.NotNil(t, arrayType, "%s should be an array type", name) {
+				assert.Equal(t, name, arrayType.String())
+			}
+		}
+		assert.Nil(t, GetType("widget[]"))
+		assert.Nil(t, GetType("[]"))
+	})
+}
@@ -0,0 +1,47 @@
+/*
+ * A C host calling the Naviary runtime through its shared library.
+ *
+ *   make embed-example
+ *
+ * It checks the ABI version, captures the runtime's output with a handler,
+ * builds strings through the runtime and prints them. It exits nonzero if
+ * the captured output is not what the runtime should have printed.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "naviary.h"
+
+static char captured[256];
+static size_t captured_length = 0;
+
+static void capture(const uint8_t *bytes, size_t length) {
+    if (captured_length + length >= sizeof(captured)) {
+        length = sizeof(captured) - 1 - captured_length;
+    }
+    memcpy(captured + captured_length, bytes, length);
+    captured_length += length;
+    captured[captured_length] = '\0';
+}
+
+int main(void) {
+    naviary_runtime_check_abi(NAVIARY_RUNTIME_ABI_VERSION);
+
+    naviary_set_output_handler(capture);
+
+    const char *line = naviary_string_repeat("ab", 3);
+    print_string(line);
+    print_int(naviary_string_length(line));
+    print_values("sic", naviary_to_hex(255), (int64_t)42, (int)'z');
+
+    naviary_set_output_handler(NULL);
+
+    const char *expected = "ababab\n6\nff 42 z\n";
+    if (strcmp(captured, expected) != 0) {
+        fprintf(stderr, "unexpected output:\n%s", captured);
+        return 1;
+    }
+
+    printf("%s", captured);
+    return 0;
+}
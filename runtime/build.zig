@@ -1,16 +1,33 @@
 const std = @import("std");
 
 pub fn build(b: *std.Build) void {
+    const target = b.standardTargetOptions(.{});
+    const optimize = b.standardOptimizeOption(.{});
+
     const lib = b.addLibrary(.{
         .name = "naviary_runtime",
         .linkage = .static,
         .root_module = b.createModule(.{
             .root_source_file = b.path("src/lib.zig"),
-            .target = b.standardTargetOptions(.{}),
-            .optimize = b.standardOptimizeOption(.{}),
+            .target = target,
+            .optimize = optimize,
         }),
     });
 
     lib.linkLibC();
     b.installArtifact(lib);
+
+    // Shared library for C and C++ hosts; the API is declared in include/naviary.h
+    const shared = b.addLibrary(.{
+        .name = "naviary_runtime",
+        .linkage = .dynamic,
+        .root_module = b.createModule(.{
+            .root_source_file = b.path("src/lib.zig"),
+            .target = target,
+            .optimize = optimize,
+        }),
+    });
+
+    shared.linkLibC();
+    b.installArtifact(shared);
 }
@@ -0,0 +1,94 @@
+/*
+ * C API of the Naviary runtime, for hosts that embed Naviary-compiled code
+ * or call the runtime directly through libnaviary_runtime.so / .dylib.
+ *
+ * The runtime never unwinds across these functions: errors such as a
+ * negative repeat count or running out of memory print a message and abort.
+ *
+ * Strings are NUL-terminated UTF-8. Strings returned by the runtime are
+ * owned by the runtime; there is no GC yet, so they are never freed.
+ */
+#ifndef NAVIARY_H
+#define NAVIARY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Must match ABI_VERSION in runtime/src/abi.zig */
+#define NAVIARY_RUNTIME_ABI_VERSION 11
+
+/* Aborts with a message unless the runtime was built for this ABI version */
+void naviary_runtime_check_abi(uint32_t version);
+
+/* Output: every print goes through the handler; NULL restores stderr */
+typedef void (*naviary_output_handler)(const uint8_t *bytes, size_t length);
+void naviary_set_output_handler(naviary_output_handler handler);
+void naviary_write(const uint8_t *bytes, size_t length);
+
+void print_int(int64_t value);
+void print_float(double value);
+void print_bool(_Bool value);
+void print_char(int32_t value);
+void print_string(const char *string);
+/* Prints several values on one line; descriptor has one letter per value: i, f, b, c or s */
+void print_values(const char *descriptor, ...);
+/* Prints without a trailing newline */
+void naviary_print_cstr(const char *string);
+
+/* Chars are Unicode scalar values */
+int64_t naviary_char_code(int32_t value);
+int32_t naviary_char_from(int64_t code);
+
+/* Strings */
+int64_t naviary_string_length(const char *string);
+uint8_t naviary_string_char_at(const char *string, int64_t index);
+/* The character starting at byte index; aborts with a message when index is out of range or inside a character */
+int32_t naviary_string_index(const char *string, int64_t index);
+/* The character starting at byte offset and how many bytes it takes; invalid UTF-8 reads as U+FFFD, one byte wide */
+int32_t naviary_string_decode_char(const char *string, int64_t offset);
+int64_t naviary_string_char_width(const char *string, int64_t offset);
+const char *naviary_string_repeat(const char *string, int64_t count);
+
+/* The strings of a string array with separator between each pair; "" for an empty array */
+const char *naviary_string_array_join(const void *array, const char *separator);
+
+/* Arrays: an int64_t length followed by the elements, each element_size bytes */
+void *naviary_array_new(int64_t length, int64_t element_size);
+/* A new int array holding a copy of count values */
+void *naviary_array_init_int(const int64_t *values, int64_t count);
+/* Aborts with a message unless 0 <= index < length */
+void naviary_array_check_index(const void *array, int64_t index);
+/* The length recorded in the array's header */
+int64_t naviary_array_length(const void *array);
+/* Lower the length in the array's header; truncate aborts with a message unless 0 <= length <= the current length */
+void naviary_array_clear(void *array);
+void naviary_array_truncate(void *array, int64_t length);
+/* Reductions over an int or double array; min and max abort with a message when it is empty */
+int64_t naviary_array_sum_int(const void *array);
+int64_t naviary_array_min_int(const void *array);
+int64_t naviary_array_max_int(const void *array);
+double naviary_array_sum_float(const void *array);
+double naviary_array_min_float(const void *array);
+double naviary_array_max_float(const void *array);
+/* The first index holding value, or -1 */
+int64_t naviary_array_find_int(const void *array, int64_t value);
+/* Whether a string array holds a string equal to value by content */
+_Bool naviary_array_contains_string(const void *array, const char *value);
+
+/* Formatting */
+const char *naviary_format_pad(int64_t value, int64_t width);
+const char *naviary_to_hex(int64_t value);
+const char *naviary_float_to_string(double value);
+
+/* Prints the message and aborts */
+_Noreturn void naviary_panic(const char *message);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
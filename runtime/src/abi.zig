@@ -0,0 +1,15 @@
+const std = @import("std");
+
+// Version of the exported function signatures; generated code passes the version it was
+// compiled for to naviary_runtime_check_abi at the start of main
+// Must match RUNTIME_ABI_VERSION in compiler/constants and be bumped whenever an export changes
+pub const ABI_VERSION: u32 = 11;
+
+pub fn naviary_runtime_check_abi(version: u32) void {
+    if (version == ABI_VERSION) {
+        return;
+    }
+
+    std.debug.print("program was compiled for runtime ABI {d} but this runtime is ABI {d}\n", .{ version, ABI_VERSION });
+    std.process.abort();
+}
@@ -0,0 +1,147 @@
+const std = @import("std");
+const memory = @import("memory.zig");
+
+// An array is one allocation: its length, then the elements back to back
+// Generated code stores and loads elements itself; the runtime allocates, checks indices
+// and implements the builtins that read a whole array
+pub const Header = extern struct {
+    length: i64,
+};
+
+pub fn naviary_array_new(length: i64, element_size: i64) ?*Header {
+    const element_bytes = std.math.mul(usize, @intCast(length), @intCast(element_size)) catch return null;
+    const total = std.math.add(usize, @sizeOf(Header), element_bytes) catch return null;
+
+    const words = memory.allocateWords(total) orelse return null;
+    const header: *Header = @ptrCast(words);
+    header.length = length;
+    return header;
+}
+
+// An int array literal whose elements are all constants is copied from a constant table
+// in one call, instead of being stored element by element
+pub fn naviary_array_init_int(values: [*]const i64, count: i64) ?*Header {
+    const array = naviary_array_new(count, @sizeOf(i64)) orelse return null;
+    const elements: [*]i64 = @ptrCast(@as([*]Header, @ptrCast(array)) + 1);
+    @memcpy(elements[0..@intCast(count)], values[0..@intCast(count)]);
+    return array;
+}
+
+// xs[i] in Naviary aborts unless 0 <= i < length, like indexing a string
+pub fn naviary_array_check_index(array: *const Header, index: i64) void {
+    if (index < 0 or index >= array.length) {
+        std.debug.print("panic: array index {d} out of range for length {d}\n", .{ index, array.length });
+        std.process.abort();
+    }
+}
+
+// len(xs) in Naviary: the length recorded in the header, without touching the elements
+pub fn naviary_array_length(array: *const Header) i64 {
+    return array.length;
+}
+
+// clear(xs) and truncate(xs, n) in Naviary only lower the length in the header, so a buffer
+// can be reused without allocating; the dropped elements stay in the allocation but are no
+// longer reachable through the array, so they need no clearing, even in a string array
+pub fn naviary_array_clear(array: *Header) void {
+    array.length = 0;
+}
+
+// truncate aborts rather than grow the array, since the slots past its length hold nothing
+pub fn naviary_array_truncate(array: *Header, length: i64) void {
+    if (length < 0 or length > array.length) {
+        std.debug.print("panic: cannot truncate an array of length {d} to {d}\n", .{ array.length, length });
+        std.process.abort();
+    }
+    array.length = length;
+}
+
+// The elements of an array of T, which start right after its header
+pub fn elementsOf(comptime T: type, array: *const Header) []const T {
+    const elements: [*]const T = @ptrCast(@as([*]const Header, @ptrCast(array)) + 1);
+    return elements[0..@intCast(array.length)];
+}
+
+// sum(xs) in Naviary wraps on overflow like +; the sum of an empty array is 0
+pub fn naviary_array_sum_int(array: *const Header) i64 {
+    var total: i64 = 0;
+    for (elementsOf(i64, array)) |element| {
+        total +%= element;
+    }
+    return total;
+}
+
+// min_of(xs) and max_of(xs) have no answer for an empty array, so they abort
+pub fn naviary_array_min_int(array: *const Header) i64 {
+    return std.mem.min(i64, nonEmptyElements(i64, array, "min_of"));
+}
+
+pub fn naviary_array_max_int(array: *const Header) i64 {
+    return std.mem.max(i64, nonEmptyElements(i64, array, "max_of"));
+}
+
+// The float reductions add in index order, so the result is the same on every run
+pub fn naviary_array_sum_float(array: *const Header) f64 {
+    var total: f64 = 0;
+    for (elementsOf(f64, array)) |element| {
+        total += element;
+    }
+    return total;
+}
+
+pub fn naviary_array_min_float(array: *const Header) f64 {
+    return std.mem.min(f64, nonEmptyElements(f64, array, "min_of"));
+}
+
+pub fn naviary_array_max_float(array: *const Header) f64 {
+    return std.mem.max(f64, nonEmptyElements(f64, array, "max_of"));
+}
+
+// find(xs, value) in Naviary: the first index holding value, or -1 when none does
+pub fn naviary_array_find_int(array: *const Header, value: i64) i64 {
+    const index = std.mem.indexOfScalar(i64, elementsOf(i64, array), value) orelse return -1;
+    return @intCast(index);
+}
+
+// value in xs for a string array: strings compare by content, not by pointer
+pub fn naviary_array_contains_string(array: *const Header, value: [*:0]const u8) bool {
+    const needle = std.mem.span(value);
+    for (elementsOf(?[*:0]const u8, array)) |element| {
+        // A slot no string was stored in reads as "", as it does for join
+        const text: []const u8 = if (element) |pointer| std.mem.span(pointer) else "";
+        if (std.mem.eql(u8, text, needle)) {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn nonEmptyElements(comptime T: type, array: *const Header, builtin: []const u8) []const T {
+    if (array.length == 0) {
+        std.debug.print("panic: {s} of an empty array\n", .{builtin});
+        std.process.abort();
+    }
+    return elementsOf(T, array);
+}
+
+// There are no float literals yet, so no program builds a non-empty float[]; these tests do
+fn testFloatArray(values: []const f64) *Header {
+    const array = naviary_array_new(@intCast(values.len), @sizeOf(f64)).?;
+    const elements: [*]f64 = @ptrCast(@as([*]Header, @ptrCast(array)) + 1);
+    @memcpy(elements[0..values.len], values);
+    return array;
+}
+
+test "float reductions over a non-empty array" {
+    const xs = testFloatArray(&.{ 1.5, -2.25, 4.0 });
+    try std.testing.expectEqual(@as(f64, 3.25), naviary_array_sum_float(xs));
+    try std.testing.expectEqual(@as(f64, -2.25), naviary_array_min_float(xs));
+    try std.testing.expectEqual(@as(f64, 4.0), naviary_array_max_float(xs));
+}
+
+test "float reductions over a single element" {
+    const xs = testFloatArray(&.{-0.5});
+    try std.testing.expectEqual(@as(f64, -0.5), naviary_array_sum_float(xs));
+    try std.testing.expectEqual(@as(f64, -0.5), naviary_array_min_float(xs));
+    try std.testing.expectEqual(@as(f64, -0.5), naviary_array_max_float(xs));
+}
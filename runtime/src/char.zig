@@ -0,0 +1,15 @@
+const std = @import("std");
+
+// Chars are Unicode scalar values: 0 through 0x10FFFF, excluding surrogates
+pub fn naviary_char_code(value: i32) i64 {
+    return value;
+}
+
+pub fn naviary_char_from(code: i64) i32 {
+    if (code < 0 or code > 0x10FFFF or (code >= 0xD800 and code <= 0xDFFF)) {
+        std.debug.print("panic: char_from: {d} is not a valid Unicode scalar value\n", .{code});
+        std.process.abort();
+    }
+
+    return @intCast(code);
+}
@@ -0,0 +1,66 @@
+const std = @import("std");
+const memory = @import("memory.zig");
+
+// Large enough for any f64 in decimal notation; anything longer falls back to exponent form
+pub const FLOAT_BUFFER_SIZE = 512;
+
+// Left-pads the decimal form of value with spaces to width characters
+// Widths smaller than the number (including negative widths) add no padding
+pub fn naviary_format_pad(value: i64, width: i64) ?[*:0]const u8 {
+    var digits: [24]u8 = undefined;
+    const text = std.fmt.bufPrint(&digits, "{d}", .{value}) catch unreachable;
+
+    const text_length: i64 = @intCast(text.len);
+    const padding: usize = if (width > text_length) @intCast(width - text_length) else 0;
+
+    const result = memory.allocateString(padding + text.len) orelse return null;
+    @memset(result[0..padding], ' ');
+    @memcpy(result[padding..], text);
+    return result.ptr;
+}
+
+// Formats value in lowercase hexadecimal; negative values keep their sign: -255 is "-ff"
+pub fn naviary_to_hex(value: i64) ?[*:0]const u8 {
+    var digits: [24]u8 = undefined;
+    const text = if (value < 0)
+        std.fmt.bufPrint(&digits, "-{x}", .{@abs(value)}) catch unreachable
+    else
+        std.fmt.bufPrint(&digits, "{x}", .{value}) catch unreachable;
+
+    const result = memory.allocateString(text.len) orelse return null;
+    @memcpy(result, text);
+    return result.ptr;
+}
+
+// Formats value as its shortest round-tripping decimal form: 0.5 is "0.5", 2.0 is "2"
+// std.fmt never consults the C locale, so the output is the same on every machine
+// print and to_string both use this, so the two always agree
+pub fn formatFloat(buffer: []u8, value: f64) []const u8 {
+    if (std.fmt.bufPrint(buffer, "{d}", .{value})) |text| {
+        return text;
+    } else |_| {
+        return std.fmt.bufPrint(buffer, "{e}", .{value}) catch unreachable;
+    }
+}
+
+pub fn naviary_float_to_string(value: f64) ?[*:0]const u8 {
+    var digits: [FLOAT_BUFFER_SIZE]u8 = undefined;
+    const text = formatFloat(&digits, value);
+
+    const result = memory.allocateString(text.len) orelse return null;
+    @memcpy(result, text);
+    return result.ptr;
+}
+
+test "to_string formats floats in their shortest round-tripping form" {
+    const cases = [_]struct { value: f64, text: []const u8 }{
+        .{ .value = 0.5, .text = "0.5" },
+        .{ .value = 2.0, .text = "2" },
+        .{ .value = -1.25, .text = "-1.25" },
+        .{ .value = 0.1, .text = "0.1" },
+    };
+    for (cases) |case| {
+        const text = naviary_float_to_string(case.value).?;
+        try std.testing.expectEqualStrings(case.text, std.mem.span(text));
+    }
+}
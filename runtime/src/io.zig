@@ -1,9 +1,127 @@
 const std = @import("std");
+const format = @import("format.zig");
+
+// A host embedding the runtime can capture program output by installing a handler
+// Without one, output goes to stderr exactly as before
+pub const OutputHandler = *const fn (bytes: [*]const u8, length: usize) callconv(.C) void;
+
+var output_handler: ?OutputHandler = null;
+
+// Installs handler for all printed output; null restores the default
+pub fn naviary_set_output_handler(handler: ?OutputHandler) void {
+    output_handler = handler;
+}
+
+// Every print goes through here, so a handler sees the exact bytes the program prints
+// The default path is unbuffered: each print reaches the terminal or pipe before the next
+// statement runs, so progress from long-running programs shows up while they run and
+// needs no flush
+pub fn naviary_write(bytes: [*]const u8, length: usize) void {
+    if (output_handler) |handler| {
+        handler(bytes, length);
+    } else {
+        std.debug.print("{s}", .{bytes[0..length]});
+    }
+}
+
+// Writes the string without a trailing newline
+pub fn naviary_print_cstr(string_pointer: [*:0]const u8) void {
+    write(std.mem.span(string_pointer));
+}
 
 pub fn naviary_print_int(value: i64) void {
-    std.debug.print("{d}\n", .{value});
+    printInt(value);
+    write("\n");
+}
+
+pub fn naviary_print_float(value: f64) void {
+    printFloat(value);
+    write("\n");
 }
 
 pub fn naviary_print_string(string_pointer: [*:0]const u8) void {
-    std.debug.print("{s}\n", .{string_pointer});
+    naviary_print_cstr(string_pointer);
+    write("\n");
+}
+
+pub fn naviary_print_bool(value: bool) void {
+    printBool(value);
+    write("\n");
+}
+
+pub fn naviary_panic(message_pointer: [*:0]const u8) noreturn {
+    std.debug.print("panic: {s}\n", .{message_pointer});
+    std.process.abort();
+}
+
+pub fn naviary_print_char(value: i32) void {
+    printChar(value);
+    write("\n");
+}
+
+// Prints the varargs separated by spaces and ends the line
+// descriptor has one letter per value: i int, f float, s string, b bool, c char;
+// bools and chars arrive promoted to C int
+pub fn naviary_print_values(descriptor: [*:0]const u8, arguments: *std.builtin.VaList) void {
+    var index: usize = 0;
+    while (descriptor[index] != 0) : (index += 1) {
+        if (index > 0) {
+            write(" ");
+        }
+
+        switch (descriptor[index]) {
+            'i' => printInt(@cVaArg(arguments, i64)),
+            'f' => printFloat(@cVaArg(arguments, f64)),
+            's' => naviary_print_cstr(@cVaArg(arguments, [*:0]const u8)),
+            'b' => printBool(@cVaArg(arguments, c_int) != 0),
+            'c' => printChar(@cVaArg(arguments, c_int)),
+            else => unreachable,
+        }
+    }
+    write("\n");
+}
+
+fn write(bytes: []const u8) void {
+    naviary_write(bytes.ptr, bytes.len);
+}
+
+fn printInt(value: i64) void {
+    var digits: [24]u8 = undefined;
+    write(std.fmt.bufPrint(&digits, "{d}", .{value}) catch unreachable);
+}
+
+fn printFloat(value: f64) void {
+    var digits: [format.FLOAT_BUFFER_SIZE]u8 = undefined;
+    write(format.formatFloat(&digits, value));
+}
+
+fn printBool(value: bool) void {
+    write(if (value) "true" else "false");
+}
+
+fn printChar(value: i32) void {
+    var buffer: [4]u8 = undefined;
+    const length = std.unicode.utf8Encode(@intCast(value), &buffer) catch {
+        write("\u{FFFD}");
+        return;
+    };
+    write(buffer[0..length]);
+}
+
+var test_output: [64]u8 = undefined;
+var test_output_length: usize = 0;
+
+fn captureTestOutput(bytes: [*]const u8, length: usize) callconv(.C) void {
+    @memcpy(test_output[test_output_length..][0..length], bytes[0..length]);
+    test_output_length += length;
+}
+
+test "print_float prints the text to_string returns" {
+    test_output_length = 0;
+    naviary_set_output_handler(captureTestOutput);
+    defer naviary_set_output_handler(null);
+
+    naviary_print_float(0.5);
+    naviary_print_float(-3.0);
+    try std.testing.expectEqualStrings("0.5\n-3\n", test_output[0..test_output_length]);
 }
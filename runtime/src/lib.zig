@@ -1,4 +1,10 @@
 const io = @import("io.zig");
+const string = @import("string.zig");
+const char = @import("char.zig");
+const format = @import("format.zig");
+const memory = @import("memory.zig");
+const abi = @import("abi.zig");
+const array = @import("array.zig");
 
 // TODO: add types
 pub export fn print_int(value: i64) void {
@@ -8,3 +14,156 @@ pub export fn print_int(value: i64) void {
 pub export fn print_string(string_pointer: [*:0]const u8) void {
     io.naviary_print_string(string_pointer);
 }
+
+pub export fn print_float(value: f64) void {
+    io.naviary_print_float(value);
+}
+
+pub export fn print_bool(value: bool) void {
+    io.naviary_print_bool(value);
+}
+
+pub export fn print_char(value: i32) void {
+    io.naviary_print_char(value);
+}
+
+pub export fn print_values(descriptor: [*:0]const u8, ...) callconv(.C) void {
+    var arguments = @cVaStart();
+    defer @cVaEnd(&arguments);
+    io.naviary_print_values(descriptor, &arguments);
+}
+
+pub export fn naviary_set_output_handler(handler: ?io.OutputHandler) void {
+    io.naviary_set_output_handler(handler);
+}
+
+pub export fn naviary_write(bytes: [*]const u8, length: usize) void {
+    io.naviary_write(bytes, length);
+}
+
+pub export fn naviary_print_cstr(string_pointer: [*:0]const u8) void {
+    io.naviary_print_cstr(string_pointer);
+}
+
+pub export fn naviary_runtime_check_abi(version: u32) void {
+    abi.naviary_runtime_check_abi(version);
+}
+
+pub export fn naviary_panic(message_pointer: [*:0]const u8) noreturn {
+    io.naviary_panic(message_pointer);
+}
+
+pub export fn naviary_string_length(string_pointer: [*:0]const u8) i64 {
+    return string.naviary_string_length(string_pointer);
+}
+
+pub export fn naviary_string_char_at(string_pointer: [*:0]const u8, index: i64) u8 {
+    return string.naviary_string_char_at(string_pointer, index);
+}
+
+pub export fn naviary_string_index(string_pointer: [*:0]const u8, index: i64) i32 {
+    return string.naviary_string_index(string_pointer, index);
+}
+
+pub export fn naviary_string_decode_char(string_pointer: [*:0]const u8, offset: i64) i32 {
+    return string.naviary_string_decode_char(string_pointer, offset);
+}
+
+pub export fn naviary_string_char_width(string_pointer: [*:0]const u8, offset: i64) i64 {
+    return string.naviary_string_char_width(string_pointer, offset);
+}
+
+pub export fn naviary_string_repeat(string_pointer: [*:0]const u8, count: i64) [*:0]const u8 {
+    return string.naviary_string_repeat(string_pointer, count) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_string_array_join(array_pointer: *const array.Header, separator_pointer: [*:0]const u8) [*:0]const u8 {
+    return string.naviary_string_array_join(array_pointer, separator_pointer) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_array_new(length: i64, element_size: i64) *array.Header {
+    return array.naviary_array_new(length, element_size) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_array_init_int(values: [*]const i64, count: i64) *array.Header {
+    return array.naviary_array_init_int(values, count) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_array_check_index(array_pointer: *const array.Header, index: i64) void {
+    array.naviary_array_check_index(array_pointer, index);
+}
+
+pub export fn naviary_array_length(array_pointer: *const array.Header) i64 {
+    return array.naviary_array_length(array_pointer);
+}
+
+pub export fn naviary_array_clear(array_pointer: *array.Header) void {
+    array.naviary_array_clear(array_pointer);
+}
+
+pub export fn naviary_array_truncate(array_pointer: *array.Header, length: i64) void {
+    array.naviary_array_truncate(array_pointer, length);
+}
+
+pub export fn naviary_array_sum_int(array_pointer: *const array.Header) i64 {
+    return array.naviary_array_sum_int(array_pointer);
+}
+
+pub export fn naviary_array_min_int(array_pointer: *const array.Header) i64 {
+    return array.naviary_array_min_int(array_pointer);
+}
+
+pub export fn naviary_array_max_int(array_pointer: *const array.Header) i64 {
+    return array.naviary_array_max_int(array_pointer);
+}
+
+pub export fn naviary_array_sum_float(array_pointer: *const array.Header) f64 {
+    return array.naviary_array_sum_float(array_pointer);
+}
+
+pub export fn naviary_array_min_float(array_pointer: *const array.Header) f64 {
+    return array.naviary_array_min_float(array_pointer);
+}
+
+pub export fn naviary_array_max_float(array_pointer: *const array.Header) f64 {
+    return array.naviary_array_max_float(array_pointer);
+}
+
+pub export fn naviary_array_find_int(array_pointer: *const array.Header, value: i64) i64 {
+    return array.naviary_array_find_int(array_pointer, value);
+}
+
+pub export fn naviary_array_contains_string(array_pointer: *const array.Header, value: [*:0]const u8) bool {
+    return array.naviary_array_contains_string(array_pointer, value);
+}
+
+pub export fn naviary_char_code(value: i32) i64 {
+    return char.naviary_char_code(value);
+}
+
+pub export fn naviary_char_from(code: i64) i32 {
+    return char.naviary_char_from(code);
+}
+
+pub export fn naviary_format_pad(value: i64, width: i64) [*:0]const u8 {
+    return format.naviary_format_pad(value, width) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_to_hex(value: i64) [*:0]const u8 {
+    return format.naviary_to_hex(value) orelse memory.naviary_out_of_memory();
+}
+
+pub export fn naviary_float_to_string(value: f64) [*:0]const u8 {
+    return format.naviary_float_to_string(value) orelse memory.naviary_out_of_memory();
+}
+
+// zig test src/lib.zig runs the tests of every file the runtime is built from
+test {
+    _ = io;
+    _ = string;
+    _ = char;
+    _ = format;
+    _ = memory;
+    _ = abi;
+    _ = array;
+}
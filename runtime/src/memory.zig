@@ -0,0 +1,23 @@
+const std = @import("std");
+
+// Runtime strings and arrays are heap allocated and never freed until the runtime has a collector
+const allocator = std.heap.c_allocator;
+
+// Allocates a NUL-terminated buffer for length bytes
+// Returns null instead of aborting, so a caller can release what it already built
+pub fn allocateString(length: usize) ?[:0]u8 {
+    return allocator.allocSentinel(u8, length, 0) catch null;
+}
+
+// Allocates at least size bytes, aligned for any value generated code stores
+pub fn allocateWords(size: usize) ?[*]i64 {
+    const words = allocator.alloc(i64, (size + 7) / 8) catch return null;
+    return words.ptr;
+}
+
+// Generated code cannot recover from a failed allocation yet, so the exported
+// builtins turn null into this abort with a message
+pub fn naviary_out_of_memory() noreturn {
+    std.debug.print("panic: out of memory\n", .{});
+    std.process.abort();
+}
@@ -0,0 +1,138 @@
+const std = @import("std");
+const memory = @import("memory.zig");
+const array = @import("array.zig");
+
+// Strings are NUL-terminated UTF-8 and are indexed byte-wise:
+// a multi-byte character occupies several indices
+pub fn naviary_string_length(string_pointer: [*:0]const u8) i64 {
+    return @intCast(std.mem.len(string_pointer));
+}
+
+pub fn naviary_string_char_at(string_pointer: [*:0]const u8, index: i64) u8 {
+    checkIndex(string_pointer, index);
+    return string_pointer[@intCast(index)];
+}
+
+// s[i] and char_at(s, i) in Naviary: the character starting at byte index i, decoded
+// An index inside a multi-byte character aborts rather than return a piece of it
+pub fn naviary_string_index(string_pointer: [*:0]const u8, index: i64) i32 {
+    checkIndex(string_pointer, index);
+    if ((string_pointer[@intCast(index)] & 0xC0) == 0x80) {
+        std.debug.print("panic: string index {d} is inside a multi-byte character\n", .{index});
+        std.process.abort();
+    }
+
+    return decodeAt(string_pointer, index).code;
+}
+
+// Aborts unless 0 <= index < length; only the bytes up to index are read, so indexing
+// near the start of a long string does not measure all of it
+fn checkIndex(string_pointer: [*:0]const u8, index: i64) void {
+    if (index >= 0) {
+        const end: usize = @intCast(index);
+        var offset: usize = 0;
+        while (offset <= end and string_pointer[offset] != 0) : (offset += 1) {}
+        if (offset > end) {
+            return;
+        }
+    }
+
+    const length = naviary_string_length(string_pointer);
+    std.debug.print("panic: string index {d} out of range for length {d}\n", .{ index, length });
+    std.process.abort();
+}
+
+// for ch in s walks s one character at a time: the loop reads the character at a byte offset,
+// then skips its width; neither call measures the string, so the loop stays linear
+// A byte that does not start a valid UTF-8 sequence reads as U+FFFD and is skipped on its own
+pub fn naviary_string_decode_char(string_pointer: [*:0]const u8, offset: i64) i32 {
+    return decodeAt(string_pointer, offset).code;
+}
+
+pub fn naviary_string_char_width(string_pointer: [*:0]const u8, offset: i64) i64 {
+    return decodeAt(string_pointer, offset).width;
+}
+
+const Decoded = struct {
+    code: i32,
+    width: i64,
+};
+
+const replacement = Decoded{ .code = 0xFFFD, .width = 1 };
+
+fn decodeAt(string_pointer: [*:0]const u8, offset: i64) Decoded {
+    const start: usize = @intCast(offset);
+    const width = std.unicode.utf8ByteSequenceLength(string_pointer[start]) catch return replacement;
+
+    // The terminating NUL is not a continuation byte, so a truncated sequence stops at it
+    var index: usize = 1;
+    while (index < width) : (index += 1) {
+        if ((string_pointer[start + index] & 0xC0) != 0x80) {
+            return replacement;
+        }
+    }
+
+    const code = std.unicode.utf8Decode(string_pointer[start .. start + width]) catch return replacement;
+    return .{ .code = code, .width = width };
+}
+
+// Concatenates count copies of the string in a single allocation; a count of 0 yields ""
+pub fn naviary_string_repeat(string_pointer: [*:0]const u8, count: i64) ?[*:0]const u8 {
+    if (count < 0) {
+        std.debug.print("panic: repeat count {d} is negative\n", .{count});
+        std.process.abort();
+    }
+
+    const text = std.mem.span(string_pointer);
+    const total = std.math.mul(usize, text.len, @intCast(count)) catch {
+        std.debug.print("panic: repeat result is too large\n", .{});
+        std.process.abort();
+    };
+
+    const result = memory.allocateString(total) orelse return null;
+    var offset: usize = 0;
+    while (offset < total) : (offset += text.len) {
+        @memcpy(result[offset .. offset + text.len], text);
+    }
+    return result.ptr;
+}
+
+// join(xs, sep) in Naviary: the elements with sep between each pair, sized first and copied
+// into a single allocation; no elements give "" and one element gives a copy of it
+// A slot that was never stored reads as null and joins as ""
+pub fn naviary_string_array_join(array_pointer: *const array.Header, separator_pointer: [*:0]const u8) ?[*:0]const u8 {
+    const elements = array.elementsOf(?[*:0]const u8, array_pointer);
+    const separator = std.mem.span(separator_pointer);
+
+    var total: usize = 0;
+    for (elements, 0..) |element, index| {
+        if (index > 0) {
+            total += separator.len;
+        }
+        if (element) |text| {
+            total += std.mem.len(text);
+        }
+    }
+
+    const result = memory.allocateString(total) orelse return null;
+    var offset: usize = 0;
+    for (elements, 0..) |element, index| {
+        if (index > 0) {
+            @memcpy(result[offset .. offset + separator.len], separator);
+            offset += separator.len;
+        }
+        if (element) |text| {
+            const piece = std.mem.span(text);
+            @memcpy(result[offset .. offset + piece.len], piece);
+            offset += piece.len;
+        }
+    }
+    return result.ptr;
+}
+
+test "indexing decodes the character starting at a byte index" {
+    const s: [*:0]const u8 = "a\u{e9}!";
+    try std.testing.expectEqual(@as(i32, 'a'), naviary_string_index(s, 0));
+    try std.testing.expectEqual(@as(i32, 0xE9), naviary_string_index(s, 1));
+    try std.testing.expectEqual(@as(i32, '!'), naviary_string_index(s, 3));
+}